@@ -204,6 +204,9 @@ pub enum AccessType {
 
     /// Written as a buffer during acceleration structure building (e.g. a staging buffer)
     AccelerationStructureBufferWrite,
+
+    /// Read as a buffer during acceleration structure building (e.g. a serialized acceleration structure)
+    AccelerationStructureBufferRead,
 }
 
 /// Defines a handful of layout options for images.
@@ -821,6 +824,11 @@ pub(crate) fn get_access_info(access_type: AccessType) -> AccessInfo {
             access_mask: vk::AccessFlags::TRANSFER_WRITE,
             image_layout: vk::ImageLayout::UNDEFINED,
         },
+        AccessType::AccelerationStructureBufferRead => AccessInfo {
+            stage_mask: vk::PipelineStageFlags::ACCELERATION_STRUCTURE_BUILD_KHR,
+            access_mask: vk::AccessFlags::TRANSFER_READ,
+            image_layout: vk::ImageLayout::UNDEFINED,
+        },
     }
 }
 
@@ -885,6 +893,7 @@ pub(crate) fn is_write_access(access_type: AccessType) -> bool {
         | AccessType::RayTracingShaderReadDepthStencilInputAttachment
         | AccessType::RayTracingShaderReadAccelerationStructure
         | AccessType::RayTracingShaderReadOther
-        | AccessType::AccelerationStructureBuildRead => false,
+        | AccessType::AccelerationStructureBuildRead
+        | AccessType::AccelerationStructureBufferRead => false,
     }
 }
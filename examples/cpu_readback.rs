@@ -21,10 +21,12 @@ fn main() -> Result<(), DriverError> {
         vk::BufferUsageFlags::TRANSFER_SRC,
         [1, 2, 3, 4],
     )?);
-    let dst_buf = render_graph.bind_node(Buffer::create_from_slice(
+
+    // Readback buffers use host-cached memory which is much faster for the CPU to read than the
+    // write-combined memory used by Buffer::create_from_slice
+    let dst_buf = render_graph.bind_node(Buffer::create(
         &device,
-        vk::BufferUsageFlags::TRANSFER_DST,
-        [0, 0, 0, 0],
+        BufferInfo::readback_mem(4, vk::BufferUsageFlags::TRANSFER_DST),
     )?);
 
     // We are using the GPU to copy data, but the same thing works if you're executing a pipeline
@@ -106,73 +106,23 @@ fn main() -> anyhow::Result<()> {
     // Setup the ray tracing pipeline
     // ------------------------------------------------------------------------------------------ //
 
-    let &RayTraceProperties {
-        shader_group_base_alignment,
-        shader_group_handle_size,
-        ..
-    } = window
-        .device
-        .physical_device
-        .ray_trace_properties
-        .as_ref()
-        .unwrap();
     let ray_trace_pipeline = create_ray_trace_pipeline(&window.device)?;
 
     // ------------------------------------------------------------------------------------------ //
     // Setup a shader binding table
     // ------------------------------------------------------------------------------------------ //
 
-    let sbt_rgen_size = shader_group_handle_size;
-    let sbt_hit_start = sbt_rgen_size.next_multiple_of(shader_group_base_alignment);
-    let sbt_hit_size = shader_group_handle_size;
-    let sbt_miss_start =
-        (sbt_hit_start + sbt_hit_size).next_multiple_of(shader_group_base_alignment);
-    let sbt_miss_size = shader_group_handle_size;
-    let sbt_buf = Arc::new({
-        let mut buf = Buffer::create(
-            &window.device,
-            BufferInfo::host_mem(
-                (sbt_miss_start + sbt_miss_size) as _,
-                vk::BufferUsageFlags::SHADER_BINDING_TABLE_KHR
-                    | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS,
-            )
-            .to_builder()
-            .alignment(shader_group_base_alignment as _),
-        )
-        .unwrap();
-
-        let data = Buffer::mapped_slice_mut(&mut buf);
-
-        let rgen_handle = RayTracePipeline::group_handle(&ray_trace_pipeline, 0)?;
-        data[0..rgen_handle.len()].copy_from_slice(rgen_handle);
-
-        let hit_handle = RayTracePipeline::group_handle(&ray_trace_pipeline, 1)?;
-        data[sbt_hit_start as usize..sbt_hit_start as usize + hit_handle.len()]
-            .copy_from_slice(hit_handle);
-
-        let miss_handle = RayTracePipeline::group_handle(&ray_trace_pipeline, 2)?;
-        data[sbt_miss_start as usize..sbt_miss_start as usize + miss_handle.len()]
-            .copy_from_slice(miss_handle);
-
-        buf
-    });
-    let sbt_address = Buffer::device_address(&sbt_buf);
-    let sbt_rgen = vk::StridedDeviceAddressRegionKHR {
-        device_address: sbt_address,
-        stride: shader_group_handle_size as _,
-        size: sbt_rgen_size as _,
-    };
-    let sbt_hit = vk::StridedDeviceAddressRegionKHR {
-        device_address: sbt_address + sbt_hit_start as vk::DeviceAddress,
-        stride: shader_group_handle_size as _,
-        size: sbt_hit_size as _,
-    };
-    let sbt_miss = vk::StridedDeviceAddressRegionKHR {
-        device_address: sbt_address + sbt_miss_start as vk::DeviceAddress,
-        stride: shader_group_handle_size as _,
-        size: sbt_miss_size as _,
-    };
-    let sbt_callable = vk::StridedDeviceAddressRegionKHR::default();
+    let ShaderBindingTable {
+        buffer: sbt_buf,
+        callable: sbt_callable,
+        hit: sbt_hit,
+        miss: sbt_miss,
+        ray_gen: sbt_rgen,
+    } = ShaderBindingTable::create(
+        &window.device,
+        &ray_trace_pipeline,
+        &ShaderBindingTableInfo::new(0).hit(1).miss(2),
+    )?;
 
     // ------------------------------------------------------------------------------------------ //
     // Generate the geometry and load it into buffers
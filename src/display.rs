@@ -6,6 +6,7 @@ use {
             device::Device,
             image::Image,
             image_access_layout,
+            surface::Surface,
             swapchain::{Swapchain, SwapchainImage, SwapchainInfo},
             vk_sync::{AccessType, ImageBarrier, cmd::pipeline_barrier},
         },
@@ -17,20 +18,35 @@ use {
     derive_builder::{Builder, UninitializedFieldError},
     log::{trace, warn},
     std::{
+        collections::VecDeque,
         error::Error,
         fmt::{Debug, Formatter},
         slice,
         sync::Arc,
-        thread::panicking,
-        time::Instant,
+        thread::{panicking, sleep},
+        time::{Duration, Instant},
     },
 };
 
+/// The maximum number of presented frames whose presentation is tracked.
+const MAX_PENDING_PRESENTS: usize = 16;
+
+/// The longest time to wait for a previous frame to be presented when limiting latency.
+const PRESENT_TIMEOUT: Duration = Duration::from_secs(1);
+
+type SurfaceLostCallback = Box<dyn FnMut(&Arc<Device>) -> Result<Surface, DriverError> + Send>;
+
 /// A physical display interface.
 pub struct Display {
+    acquired: Option<Instant>,
+    events: Vec<DisplayEvent>,
     exec_idx: usize,
     execs: Box<[Execution]>,
+    info: DisplayInfo,
+    presents: VecDeque<(u64, Instant)>,
     queue_family_idx: u32,
+    stats: DisplayStats,
+    surface_lost_callback: Option<SurfaceLostCallback>,
     swapchain: Swapchain,
 }
 
@@ -54,7 +70,9 @@ impl Display {
 
             execs.push(Execution {
                 cmd_buf,
+                present_fence: None,
                 queue: None,
+                shared_fence: None,
                 swapchain_acquired,
                 swapchain_rendered,
             });
@@ -62,20 +80,40 @@ impl Display {
         let execs = execs.into_boxed_slice();
 
         Ok(Self {
+            acquired: None,
+            events: vec![],
             exec_idx: info.command_buffer_count,
             execs,
+            info,
+            presents: Default::default(),
             queue_family_idx: info.queue_family_index,
+            stats: Default::default(),
+            surface_lost_callback: None,
             swapchain,
         })
     }
 
     /// Gets the next available swapchain image which should be rendered to and then presented using
-    /// [`present_image`][Self::present_image].
+    /// [`present_image`][Self::present_image] or [`present_images`][Self::present_images].
+    ///
+    /// Out-of-date and suboptimal swapchains are recreated using the current extent of the surface
+    /// and lost surfaces are recreated using the function provided by
+    /// [`set_surface_lost_callback`][Self::set_surface_lost_callback]. These events are reported by
+    /// [`events`][Self::events].
+    ///
+    /// Returns `Ok(None)` if no image is currently available, such as when the surface has a zero
+    /// extent because a window is minimized. Other errors, such as running out of memory while
+    /// recreating the swapchain, are returned as [`DisplayError::Driver`].
     pub fn acquire_next_image(&mut self) -> Result<Option<SwapchainImage>, DisplayError> {
+        self.events.clear();
+        self.pace_frame()?;
+
         self.exec_idx += 1;
         self.exec_idx %= self.execs.len();
         let exec = &mut self.execs[self.exec_idx];
 
+        let started = Instant::now();
+
         if exec.queue.is_some() {
             CommandBuffer::wait_until_executed(&mut exec.cmd_buf).inspect_err(|err| {
                 warn!("unable to wait for display fence: {err}");
@@ -84,6 +122,16 @@ impl Display {
             exec.queue = None;
         }
 
+        if let Some(shared_fence) = exec.shared_fence.take() {
+            Device::wait_for_fence(&shared_fence.device, &shared_fence.fence).inspect_err(
+                |err| {
+                    warn!("unable to wait for shared display fence: {err}");
+                },
+            )?;
+        }
+
+        self.stats.fence_wait_duration = started.elapsed();
+
         CommandBuffer::drop_fenced(&mut exec.cmd_buf);
 
         unsafe {
@@ -97,16 +145,42 @@ impl Display {
                 })?;
         }
 
-        let acquire_next_image = self.swapchain.acquire_next_image(exec.swapchain_acquired);
+        let swapchain_acquired = exec.swapchain_acquired;
+        let swapchain = Swapchain::swapchain(&self.swapchain);
+        let started = Instant::now();
+        let mut acquire_next_image = self.swapchain.acquire_next_image(swapchain_acquired);
+
+        if matches!(acquire_next_image, Err(SwapchainError::SurfaceLost))
+            && self.recreate_surface()?
+        {
+            acquire_next_image = self.swapchain.acquire_next_image(swapchain_acquired);
+        }
+
+        self.stats.acquire_duration = started.elapsed();
 
         if let Err(err) = acquire_next_image {
             warn!("unable to acquire next swapchain image: {err:?}");
         }
 
+        if Swapchain::swapchain(&self.swapchain) != swapchain {
+            // Presentation of previous images can no longer be waited upon
+            self.presents.clear();
+
+            let SwapchainInfo { width, height, .. } = self.swapchain.info();
+
+            self.events
+                .push(DisplayEvent::SwapchainRecreated { width, height });
+        }
+
         let mut swapchain_image = match acquire_next_image {
             Err(SwapchainError::DeviceLost) => Err(DisplayError::DeviceLost),
-            Err(SwapchainError::Suboptimal) => return Ok(None),
-            Err(SwapchainError::SurfaceLost) => Err(DisplayError::Driver(DriverError::InvalidData)),
+            Err(SwapchainError::Driver(err)) => Err(DisplayError::Driver(err)),
+            Err(SwapchainError::Suboptimal) => {
+                self.events.push(DisplayEvent::ImageUnavailable);
+
+                return Ok(None);
+            }
+            Err(SwapchainError::SurfaceLost) => Err(DisplayError::SurfaceLost),
             Ok(swapchain_image) => Ok(swapchain_image),
         }?;
         swapchain_image.exec_idx = self.exec_idx;
@@ -114,7 +188,66 @@ impl Display {
         Ok(Some(swapchain_image))
     }
 
+    /// Limits the frame rate and latency, if enabled, and updates the frame statistics.
+    fn pace_frame(&mut self) -> Result<(), DisplayError> {
+        let started = Instant::now();
+
+        if let (Some(min_frame_duration), Some(acquired)) =
+            (self.info.min_frame_duration, self.acquired)
+        {
+            let elapsed = started - acquired;
+
+            if elapsed < min_frame_duration {
+                sleep(min_frame_duration - elapsed);
+            }
+        }
+
+        if self.swapchain.present_id().is_some() {
+            // Wait for older frames so that no more than the maximum latency are in-flight
+            let max_frame_latency = self
+                .info
+                .max_frame_latency
+                .map(|max_frame_latency| max_frame_latency.max(1) as usize);
+
+            while let Some(&(present_id, submitted)) = self.presents.front() {
+                let timeout = if max_frame_latency
+                    .is_some_and(|max_frame_latency| self.presents.len() >= max_frame_latency)
+                {
+                    PRESENT_TIMEOUT
+                } else {
+                    Duration::ZERO
+                };
+
+                if !self.swapchain.wait_for_present(present_id, timeout)? {
+                    break;
+                }
+
+                self.presents.pop_front();
+                self.stats.present_latency = Some(submitted.elapsed());
+            }
+        }
+
+        let acquired = Instant::now();
+
+        self.stats.frame_duration = self
+            .acquired
+            .map(|prev_acquired| acquired - prev_acquired)
+            .unwrap_or_default();
+        self.stats.pacing_duration = acquired - started;
+        self.acquired = Some(acquired);
+
+        Ok(())
+    }
+
+    /// Returns the events which occurred during the most recent call to
+    /// [`acquire_next_image`][Self::acquire_next_image].
+    pub fn events(&self) -> &[DisplayEvent] {
+        &self.events
+    }
+
     /// Displays the given swapchain image using passes specified in `render_graph`, if possible.
+    ///
+    /// See [`present_images`][Self::present_images] to present to multiple displays at once.
     #[profiling::function]
     pub fn present_image(
         &mut self,
@@ -125,19 +258,68 @@ impl Display {
     ) -> Result<(), DisplayError> {
         trace!("present_image");
 
-        let mut resolver = render_graph.resolve();
-        let wait_dst_stage_mask = resolver.node_pipeline_stages(swapchain_image);
+        Self::present_images(
+            pool,
+            render_graph,
+            &mut [(self, swapchain_image)],
+            queue_index,
+        )
+    }
 
-        // The swapchain should have been written to, otherwise it would be noise and that's a panic
-        assert!(
-            !wait_dst_stage_mask.is_empty(),
-            "uninitialized swapchain image: write something each frame!",
-        );
+    /// Displays the given swapchain images of multiple displays using passes specified in
+    /// `render_graph`, if possible.
+    ///
+    /// The passes of all swapchain images are recorded into a single command buffer which is
+    /// submitted once, and all swapchains are presented using a single `vkQueuePresentKHR` call.
+    /// Each swapchain image must have been acquired from the display it is paired with, and all
+    /// displays must share the same device and queue family.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `displays` is empty or if any swapchain image has not been written to.
+    #[profiling::function]
+    pub fn present_images(
+        pool: &mut impl ResolverPool,
+        render_graph: RenderGraph,
+        displays: &mut [(&mut Self, SwapchainImageNode)],
+        queue_index: u32,
+    ) -> Result<(), DisplayError> {
+        trace!("present_images");
 
-        let exec_idx = resolver.swapchain_image(swapchain_image).exec_idx;
-        let exec = &mut self.execs[exec_idx];
+        assert!(!displays.is_empty(), "no swapchain images to present");
 
-        debug_assert!(exec.queue.is_none());
+        let mut resolver = render_graph.resolve();
+        let queue_family_idx = displays[0].0.queue_family_idx;
+        let swapchain_images = displays
+            .iter()
+            .map(|(_, swapchain_image)| *swapchain_image)
+            .collect::<Vec<_>>();
+        let mut swapchain_acquired = Vec::with_capacity(displays.len());
+        let mut swapchain_rendered = Vec::with_capacity(displays.len());
+        let mut wait_dst_stage_masks = Vec::with_capacity(displays.len());
+
+        for (display, swapchain_image) in displays.iter() {
+            let wait_dst_stage_mask = resolver.node_pipeline_stages(*swapchain_image);
+
+            // The swapchain should have been written to, otherwise it would be noise and that's a panic
+            assert!(
+                !wait_dst_stage_mask.is_empty(),
+                "uninitialized swapchain image: write something each frame!",
+            );
+
+            let exec = &display.execs[resolver.swapchain_image(*swapchain_image).exec_idx];
+
+            debug_assert!(exec.queue.is_none());
+            debug_assert_eq!(display.queue_family_idx, queue_family_idx);
+
+            swapchain_acquired.push(exec.swapchain_acquired);
+            swapchain_rendered.push(exec.swapchain_rendered);
+            wait_dst_stage_masks.push(wait_dst_stage_mask);
+        }
+
+        // All commands are recorded into the command buffer of the first display
+        let exec_idx = resolver.swapchain_image(swapchain_images[0]).exec_idx;
+        let exec = &mut displays[0].0.execs[exec_idx];
 
         let started = Instant::now();
 
@@ -152,10 +334,12 @@ impl Display {
                 .map_err(|_| ())?;
         }
 
-        // resolver.record_node_dependencies(&mut *self.pool, cmd_buf, swapchain_image)?;
-        resolver.record_node(pool, &mut exec.cmd_buf, swapchain_image)?;
+        for &swapchain_image in &swapchain_images {
+            // resolver.record_node_dependencies(&mut *self.pool, cmd_buf, swapchain_image)?;
+            resolver.record_node(pool, &mut exec.cmd_buf, swapchain_image)?;
+        }
 
-        {
+        for &swapchain_image in &swapchain_images {
             let swapchain_image = resolver.swapchain_image(swapchain_image);
             for (access, range) in Image::access(
                 swapchain_image,
@@ -202,8 +386,19 @@ impl Display {
         // things so we do them last
         resolver.record_unscheduled_passes(pool, &mut exec.cmd_buf)?;
 
-        let queue =
-            exec.cmd_buf.device.queues[self.queue_family_idx as usize][queue_index as usize];
+        let queue = exec.cmd_buf.device.queues[queue_family_idx as usize][queue_index as usize];
+
+        // A single submission signals a single fence, so when presenting to multiple displays the
+        // fence is shared by each display which must wait for it
+        let shared_fence = if swapchain_images.len() > 1 {
+            Some(Self::present_fence(exec)?)
+        } else {
+            None
+        };
+        let fence = shared_fence
+            .as_ref()
+            .map(|shared_fence| shared_fence.fence)
+            .unwrap_or(exec.cmd_buf.fence);
 
         unsafe {
             exec.cmd_buf
@@ -221,11 +416,11 @@ impl Display {
                     slice::from_ref(
                         &vk::SubmitInfo::default()
                             .command_buffers(slice::from_ref(&exec.cmd_buf))
-                            .wait_semaphores(slice::from_ref(&exec.swapchain_acquired))
-                            .wait_dst_stage_mask(slice::from_ref(&wait_dst_stage_mask))
-                            .signal_semaphores(slice::from_ref(&exec.swapchain_rendered)),
+                            .wait_semaphores(&swapchain_acquired)
+                            .wait_dst_stage_mask(&wait_dst_stage_masks)
+                            .signal_semaphores(&swapchain_rendered),
                     ),
-                    exec.cmd_buf.fence,
+                    fence,
                 )
                 .map_err(|err| {
                     warn!("unable to submit display command buffer: {err}");
@@ -234,29 +429,122 @@ impl Display {
                 })?
         }
 
-        exec.cmd_buf.waiting = true;
-        exec.queue = Some(queue);
+        exec.cmd_buf.waiting = shared_fence.is_none();
 
         let elapsed = Instant::now() - started;
         trace!("🔜🔜🔜 vkQueueSubmit took {} μs", elapsed.as_micros(),);
 
-        let swapchain_image =
-            SwapchainImage::clone_swapchain(resolver.swapchain_image(swapchain_image));
+        let submitted = Instant::now();
+        let mut images = Vec::with_capacity(displays.len());
+
+        for (display, swapchain_image) in displays.iter_mut() {
+            let swapchain_image = resolver.swapchain_image(*swapchain_image);
+            let exec = &mut display.execs[swapchain_image.exec_idx];
+
+            exec.queue = Some(queue);
+            exec.shared_fence = shared_fence.clone();
+            display.stats.submit_duration = elapsed;
+
+            images.push(SwapchainImage::clone_swapchain(swapchain_image));
+        }
 
-        self.swapchain.present_image(
-            swapchain_image,
-            slice::from_ref(&exec.swapchain_rendered),
-            self.queue_family_idx,
+        Swapchain::present_images(
+            displays
+                .iter_mut()
+                .map(|(display, _)| &mut display.swapchain)
+                .zip(images),
+            &swapchain_rendered,
+            queue_family_idx,
             queue_index,
         );
 
+        for (display, _) in displays.iter_mut() {
+            if let Some(present_id) = display.swapchain.present_id() {
+                // Unobserved presents are forgotten so that tracking stays bounded
+                if display.presents.len() == MAX_PENDING_PRESENTS {
+                    display.presents.pop_front();
+                }
+
+                display.presents.push_back((present_id, submitted));
+            }
+        }
+
         // Store the resolved graph because it contains bindings, leases, and other shared resources
         // that need to be kept alive until the fence is waited upon.
-        CommandBuffer::push_fenced_drop(&mut exec.cmd_buf, resolver);
+        CommandBuffer::push_fenced_drop(&mut displays[0].0.execs[exec_idx].cmd_buf, resolver);
 
         Ok(())
     }
 
+    /// Returns the fence of the given execution slot which is signalled by a submission presenting
+    /// to multiple displays.
+    ///
+    /// The fence is reset and reused once no other display is waiting for it.
+    fn present_fence(exec: &mut Execution) -> Result<Arc<SharedFence>, DriverError> {
+        let present_fence = match exec.present_fence.take() {
+            Some(present_fence) if Arc::strong_count(&present_fence) == 1 => {
+                unsafe {
+                    exec.cmd_buf
+                        .device
+                        .reset_fences(slice::from_ref(&present_fence.fence))
+                        .map_err(|err| {
+                            warn!("unable to reset shared display fence: {err}");
+
+                            DriverError::InvalidData
+                        })?;
+                }
+
+                present_fence
+            }
+            _ => Arc::new(SharedFence::create(&exec.cmd_buf.device)?),
+        };
+
+        exec.present_fence = Some(Arc::clone(&present_fence));
+
+        Ok(present_fence)
+    }
+
+    /// Recreates the surface using the surface lost callback, if set, returning `true` when the
+    /// surface was replaced.
+    fn recreate_surface(&mut self) -> Result<bool, DisplayError> {
+        let Some(surface_lost_callback) = self.surface_lost_callback.as_mut() else {
+            return Ok(false);
+        };
+
+        let device = &self.execs[0].cmd_buf.device;
+
+        // The swapchain images of the lost surface must not be in use
+        unsafe { device.device_wait_idle() }.map_err(|err| {
+            warn!("unable to wait for device: {err}");
+
+            DisplayError::DeviceLost
+        })?;
+
+        let surface = surface_lost_callback(device).inspect_err(|err| {
+            warn!("unable to recreate surface: {err}");
+        })?;
+
+        self.swapchain.set_surface(surface);
+        self.events.push(DisplayEvent::SurfaceRecreated);
+
+        Ok(true)
+    }
+
+    /// Sets a function which is called to recreate the surface when it is lost, such as when the
+    /// operating system window has been destroyed.
+    ///
+    /// The function is called from [`acquire_next_image`][Self::acquire_next_image] after the
+    /// device is idle. Without this function a lost surface is reported as
+    /// [`DisplayError::SurfaceLost`].
+    ///
+    /// Any previously set function is replaced.
+    pub fn set_surface_lost_callback(
+        &mut self,
+        callback: impl FnMut(&Arc<Device>) -> Result<Surface, DriverError> + Send + 'static,
+    ) {
+        self.surface_lost_callback = Some(Box::new(callback));
+    }
+
     /// Sets information about the swapchain.
     ///
     /// Previously acquired swapchain images should be discarded after calling this function.
@@ -264,6 +552,17 @@ impl Display {
         self.swapchain.set_info(info);
     }
 
+    /// Returns timing statistics of the most recently acquired and presented frames.
+    pub fn stats(&self) -> DisplayStats {
+        self.stats
+    }
+
+    /// Gets the swapchain, which reports the present mode and other values chosen when it was
+    /// most recently created.
+    pub fn swapchain(&self) -> &Swapchain {
+        &self.swapchain
+    }
+
     /// Gets information about the swapchain.
     pub fn swapchain_info(&self) -> SwapchainInfo {
         self.swapchain.info()
@@ -322,6 +621,63 @@ pub enum DisplayError {
 
     /// Recoverable driver error
     Driver(DriverError),
+
+    /// The surface was lost and could not be recreated; see
+    /// [`Display::set_surface_lost_callback`]
+    SurfaceLost,
+}
+
+/// Timing statistics of a [`Display`], used for frame pacing and latency measurement.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct DisplayStats {
+    /// The time spent acquiring the most recent swapchain image.
+    pub acquire_duration: Duration,
+
+    /// The CPU time spent waiting on the fence of a previous frame before acquiring the most recent
+    /// swapchain image.
+    pub fence_wait_duration: Duration,
+
+    /// The time between the two most recent calls to [`Display::acquire_next_image`].
+    pub frame_duration: Duration,
+
+    /// The time spent limiting the frame rate and latency before acquiring the most recent
+    /// swapchain image.
+    ///
+    /// See [`DisplayInfoBuilder::min_frame_duration`] and
+    /// [`DisplayInfoBuilder::max_frame_latency`].
+    pub pacing_duration: Duration,
+
+    /// The most recently observed time between submitting a frame and it being presented.
+    ///
+    /// Requires `VK_KHR_present_wait` support; otherwise this is always `None`. Presentation is
+    /// observed when the next image is acquired, so without a maximum frame latency this value is
+    /// an upper bound.
+    pub present_latency: Option<Duration>,
+
+    /// The time spent recording and submitting the commands of the most recently presented frame.
+    pub submit_duration: Duration,
+}
+
+/// Describes recovery events which occurred while acquiring a swapchain image.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DisplayEvent {
+    /// No swapchain image was available, such as when the surface has a zero extent.
+    ImageUnavailable,
+
+    /// The surface was lost and recreated using the surface lost callback.
+    SurfaceRecreated,
+
+    /// The swapchain was recreated because it was out-of-date or suboptimal, or because the
+    /// swapchain information changed.
+    SwapchainRecreated {
+        /// The height of the recreated swapchain images.
+        height: u32,
+
+        /// The width of the recreated swapchain images.
+        width: u32,
+    },
 }
 
 impl Error for DisplayError {}
@@ -359,6 +715,21 @@ pub struct DisplayInfo {
     #[builder(default = "4")]
     command_buffer_count: usize,
 
+    /// The maximum number of presented frames which may be waiting to be displayed when acquiring
+    /// the next swapchain image.
+    ///
+    /// Lower values reduce latency at the expense of throughput. Requires `VK_KHR_present_wait`
+    /// support; otherwise this value is ignored.
+    #[builder(default, setter(strip_option))]
+    max_frame_latency: Option<u32>,
+
+    /// The minimum time between acquiring swapchain images, which limits the frame rate.
+    ///
+    /// For example, `Duration::from_secs_f64(1.0 / 60.0)` limits the frame rate to 60 frames per
+    /// second.
+    #[builder(default, setter(strip_option))]
+    min_frame_duration: Option<Duration>,
+
     /// The device queue family which will be used to submit and present images.
     #[builder(default = "0")]
     queue_family_index: u32,
@@ -370,6 +741,8 @@ impl DisplayInfo {
     pub fn to_builder(self) -> DisplayInfoBuilder {
         DisplayInfoBuilder {
             command_buffer_count: Some(self.command_buffer_count),
+            max_frame_latency: Some(self.max_frame_latency),
+            min_frame_duration: Some(self.min_frame_duration),
             queue_family_index: Some(self.queue_family_index),
         }
     }
@@ -379,6 +752,8 @@ impl Default for DisplayInfo {
     fn default() -> Self {
         Self {
             command_buffer_count: 4,
+            max_frame_latency: None,
+            min_frame_duration: None,
             queue_family_index: 0,
         }
     }
@@ -425,11 +800,42 @@ impl From<UninitializedFieldError> for DisplayInfoBuilderError {
 
 struct Execution {
     cmd_buf: CommandBuffer,
+    present_fence: Option<Arc<SharedFence>>,
     queue: Option<vk::Queue>,
+    shared_fence: Option<Arc<SharedFence>>,
     swapchain_acquired: vk::Semaphore,
     swapchain_rendered: vk::Semaphore,
 }
 
+/// A fence signalled by a single submission which presents to multiple displays.
+struct SharedFence {
+    device: Arc<Device>,
+    fence: vk::Fence,
+}
+
+impl SharedFence {
+    fn create(device: &Arc<Device>) -> Result<Self, DriverError> {
+        let fence = Device::create_fence(device, false)?;
+
+        Ok(Self {
+            device: Arc::clone(device),
+            fence,
+        })
+    }
+}
+
+impl Drop for SharedFence {
+    fn drop(&mut self) {
+        if panicking() {
+            return;
+        }
+
+        unsafe {
+            self.device.destroy_fence(self.fence, None);
+        }
+    }
+}
+
 /// Combination trait which groups together all [`Pool`] traits required for a [`Resolver`]
 /// instance.
 ///
@@ -462,6 +868,8 @@ mod tests {
     pub fn display_info() {
         let info = Info {
             command_buffer_count: 42,
+            max_frame_latency: Some(2),
+            min_frame_duration: Some(Duration::from_millis(16)),
             queue_family_index: 16,
         };
         let builder = info.to_builder().build();
@@ -473,10 +881,14 @@ mod tests {
     pub fn display_info_builder() {
         let info = Info {
             command_buffer_count: 42,
+            max_frame_latency: Some(2),
+            min_frame_duration: Some(Duration::from_millis(16)),
             queue_family_index: 16,
         };
         let builder = Builder::default()
             .command_buffer_count(42)
+            .max_frame_latency(2)
+            .min_frame_duration(Duration::from_millis(16))
             .queue_family_index(16)
             .build();
 
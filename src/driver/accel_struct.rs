@@ -189,6 +189,76 @@ impl AccelerationStructure {
         replace(&mut access_guard, access)
     }
 
+    /// Returns the information used to create an acceleration structure which `data` may be
+    /// deserialized into.
+    ///
+    /// `data` is the serialized form of an acceleration structure, as written by
+    /// [`RenderGraph::serialize_accel_struct`](crate::graph::RenderGraph::serialize_accel_struct).
+    /// Returns [`DriverError::InvalidData`] if `data` is too small or was serialized by a device or
+    /// driver which is not compatible with the given device, such as data cached by a previous
+    /// driver version. Returns [`DriverError::Unsupported`] if the device does not support
+    /// acceleration structures.
+    ///
+    /// # Top-level acceleration structures
+    ///
+    /// Serialized top-level acceleration structures store the device addresses of the bottom-level
+    /// acceleration structures they reference. These addresses are not updated by deserialization
+    /// and are stale once the bottom-level acceleration structures are deserialized or recreated:
+    /// the caller must replace the addresses which follow the serialized header in `data` with the
+    /// current addresses before deserializing, or rebuild the top-level acceleration structure.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::accel_struct::AccelerationStructure;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let my_cached_blas: Vec<u8> = vec![];
+    /// let ty = vk::AccelerationStructureTypeKHR::BOTTOM_LEVEL;
+    /// let info = AccelerationStructure::deserialized_info(&device, ty, &my_cached_blas)?;
+    /// let blas = AccelerationStructure::create(&device, info)?;
+    /// # Ok(()) }
+    /// ```
+    #[profiling::function]
+    pub fn deserialized_info(
+        device: &Device,
+        ty: vk::AccelerationStructureTypeKHR,
+        data: &[u8],
+    ) -> Result<AccelerationStructureInfo, DriverError> {
+        if device.physical_device.accel_struct_properties.is_none() {
+            warn!("Unsupported acceleration structure deserialization");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        let (version_data, size) = serialized_header(data).ok_or_else(|| {
+            warn!("Invalid serialized acceleration structure");
+
+            DriverError::InvalidData
+        })?;
+
+        let accel_struct_ext = Device::expect_accel_struct_ext(device);
+        let compatibility = unsafe {
+            accel_struct_ext.get_device_acceleration_structure_compatibility(
+                &vk::AccelerationStructureVersionInfoKHR::default().version_data(version_data),
+            )
+        };
+
+        if compatibility != vk::AccelerationStructureCompatibilityKHR::COMPATIBLE {
+            warn!("Incompatible serialized acceleration structure");
+
+            return Err(DriverError::InvalidData);
+        }
+
+        Ok(AccelerationStructureInfo { ty, size })
+    }
+
     /// Returns the device address of this object.
     ///
     /// # Examples
@@ -324,6 +394,22 @@ impl AccelerationStructure {
     }
 }
 
+/// Returns the driver and compatibility UUIDs and the deserialized size of serialized acceleration
+/// structure data.
+fn serialized_header(data: &[u8]) -> Option<(&[u8; 2 * vk::UUID_SIZE], vk::DeviceSize)> {
+    // The header is the driver UUID, the compatibility UUID, the serialized size, the deserialized
+    // size and the number of bottom-level handles which follow
+    let (version_data, data) = data.split_first_chunk::<{ 2 * vk::UUID_SIZE }>()?;
+    let (_serialized_size, data) = data.split_first_chunk::<8>()?;
+    let (deserialized_size, data) = data.split_first_chunk::<8>()?;
+    let _handle_count = data.first_chunk::<8>()?;
+
+    Some((
+        version_data,
+        vk::DeviceSize::from_ne_bytes(*deserialized_size),
+    ))
+}
+
 impl Deref for AccelerationStructure {
     type Target = vk::AccelerationStructureKHR;
 
@@ -725,6 +811,169 @@ impl From<UninitializedFieldError> for AccelerationStructureInfoBuilderError {
     }
 }
 
+/// Smart pointer handle to a [query pool] object which stores properties of acceleration
+/// structures, such as the compacted size.
+///
+/// Queries are written using
+/// [`Acceleration::write_structure_properties`](crate::graph::pass_ref::Acceleration::write_structure_properties)
+/// or [`RenderGraph::query_accel_struct`](crate::graph::RenderGraph::query_accel_struct) and
+/// may be read using [`AccelerationStructureQueryPool::result`] once the render graph commands
+/// have executed.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use ash::vk;
+/// # use screen_13::driver::DriverError;
+/// # use screen_13::driver::device::{Device, DeviceInfo};
+/// # use screen_13::driver::accel_struct::AccelerationStructureQueryPool;
+/// # fn main() -> Result<(), DriverError> {
+/// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+/// let query_ty = vk::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR;
+/// let query_pool = AccelerationStructureQueryPool::create(&device, query_ty, 1)?;
+///
+/// assert_eq!(AccelerationStructureQueryPool::query_count(&query_pool), 1);
+/// # Ok(()) }
+/// ```
+///
+/// [query pool]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkQueryPool.html
+#[derive(Debug)]
+pub struct AccelerationStructureQueryPool {
+    device: Arc<Device>,
+    query_count: u32,
+    query_pool: vk::QueryPool,
+    query_ty: vk::QueryType,
+}
+
+impl AccelerationStructureQueryPool {
+    /// Creates a new query pool of `query_count` queries on the given device.
+    ///
+    /// `query_ty` must be [`vk::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR`] or
+    /// [`vk::QueryType::ACCELERATION_STRUCTURE_SERIALIZATION_SIZE_KHR`]. Compacted sizes may only be
+    /// queried for acceleration structures built using
+    /// [`vk::BuildAccelerationStructureFlagsKHR::ALLOW_COMPACTION`].
+    #[profiling::function]
+    pub fn create(
+        device: &Arc<Device>,
+        query_ty: vk::QueryType,
+        query_count: u32,
+    ) -> Result<Self, DriverError> {
+        debug_assert!(device.physical_device.accel_struct_properties.is_some());
+        debug_assert!(
+            query_ty == vk::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR
+                || query_ty == vk::QueryType::ACCELERATION_STRUCTURE_SERIALIZATION_SIZE_KHR,
+            "Unsupported query type"
+        );
+        debug_assert_ne!(query_count, 0, "Query count must be non-zero");
+
+        let query_pool = unsafe {
+            device.create_query_pool(
+                &vk::QueryPoolCreateInfo::default()
+                    .query_type(query_ty)
+                    .query_count(query_count),
+                None,
+            )
+        }
+        .map_err(|err| {
+            warn!("{err}");
+
+            match err {
+                vk::Result::ERROR_OUT_OF_DEVICE_MEMORY | vk::Result::ERROR_OUT_OF_HOST_MEMORY => {
+                    DriverError::OutOfMemory
+                }
+                _ => DriverError::Unsupported,
+            }
+        })?;
+
+        // Queries must be reset before the first use; results are unavailable until written
+        if device.physical_device.features_v1_2.host_query_reset {
+            unsafe {
+                device.reset_query_pool(query_pool, 0, query_count);
+            }
+        }
+
+        let device = Arc::clone(device);
+
+        Ok(Self {
+            device,
+            query_count,
+            query_pool,
+            query_ty,
+        })
+    }
+
+    /// Returns the number of queries in this pool.
+    pub fn query_count(this: &Self) -> u32 {
+        this.query_count
+    }
+
+    /// Returns the type of queries in this pool.
+    pub fn query_ty(this: &Self) -> vk::QueryType {
+        this.query_ty
+    }
+
+    /// Returns the result of the given query, or `None` if the query has not been written or the
+    /// commands which write it have not finished executing.
+    ///
+    /// The result is a size in bytes, which may be used as [`AccelerationStructureInfo::size`] when
+    /// creating or leasing the destination of a compacting copy.
+    #[profiling::function]
+    pub fn result(this: &Self, query: u32) -> Result<Option<vk::DeviceSize>, DriverError> {
+        debug_assert!(query < this.query_count, "Query out of bounds");
+
+        // The second value is the availability of the query
+        let mut result = [[0u64; 2]];
+
+        match unsafe {
+            this.device.get_query_pool_results(
+                this.query_pool,
+                query,
+                &mut result,
+                vk::QueryResultFlags::TYPE_64 | vk::QueryResultFlags::WITH_AVAILABILITY,
+            )
+        } {
+            Ok(()) | Err(vk::Result::NOT_READY) => {}
+            Err(err) => {
+                warn!("{err}");
+
+                return Err(match err {
+                    vk::Result::ERROR_OUT_OF_DEVICE_MEMORY
+                    | vk::Result::ERROR_OUT_OF_HOST_MEMORY => DriverError::OutOfMemory,
+                    _ => DriverError::Unsupported,
+                });
+            }
+        }
+
+        let [[size, available]] = result;
+
+        Ok((available != 0).then_some(size))
+    }
+}
+
+impl Deref for AccelerationStructureQueryPool {
+    type Target = vk::QueryPool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.query_pool
+    }
+}
+
+impl Drop for AccelerationStructureQueryPool {
+    #[profiling::function]
+    fn drop(&mut self) {
+        if panicking() {
+            return;
+        }
+
+        unsafe {
+            self.device.destroy_query_pool(self.query_pool, None);
+        }
+    }
+}
+
 /// Holds the results of the [`AccelerationStructure::size_of`] function.
 #[derive(Clone, Copy, Debug)]
 pub struct AccelerationStructureSize {
@@ -822,4 +1071,20 @@ mod tests {
     pub fn accel_struct_info_builder_uninit_size() {
         Builder::default().build();
     }
+
+    #[test]
+    pub fn accel_struct_serialized_header() {
+        let mut data = vec![0xab; 2 * vk::UUID_SIZE];
+        data.extend_from_slice(&1024u64.to_ne_bytes());
+        data.extend_from_slice(&512u64.to_ne_bytes());
+
+        assert!(serialized_header(&data).is_none());
+
+        data.extend_from_slice(&0u64.to_ne_bytes());
+
+        let (version_data, size) = serialized_header(&data).unwrap();
+
+        assert_eq!(version_data, &[0xab; 2 * vk::UUID_SIZE]);
+        assert_eq!(size, 512);
+    }
 }
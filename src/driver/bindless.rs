@@ -0,0 +1,936 @@
+//! Bindless descriptor heap types
+
+use {
+    super::{
+        Buffer, DescriptorSetLayout, DriverError, descriptor_image_layout,
+        device::Device,
+        format_aspect_mask,
+        image::{Image, ImageViewInfo},
+        shader::SamplerInfo,
+    },
+    ash::vk,
+    derive_builder::{Builder, UninitializedFieldError},
+    log::warn,
+    std::{
+        mem::take,
+        slice::from_ref,
+        sync::{Arc, Weak},
+        thread::panicking,
+    },
+};
+
+#[cfg(feature = "parking_lot")]
+use parking_lot::Mutex;
+
+#[cfg(not(feature = "parking_lot"))]
+use std::sync::Mutex;
+
+/// Smart pointer handle to a long-lived descriptor set which hands out stable indices for images,
+/// buffers and samplers.
+///
+/// A bindless heap is created once and may be shared by any number of compute, graphic or ray
+/// trace pipelines which were created using the same [`BindlessHeapInfo`] for one of their
+/// descriptor sets (see [`ComputePipelineInfoBuilder::bindless_heap`],
+/// [`GraphicPipelineInfoBuilder::bindless_heap`] and
+/// [`RayTracePipelineInfoBuilder::bindless_heap`]). The heap descriptor set uses the following
+/// bindings:
+///
+/// | Binding | Descriptor type | Constant                                 |
+/// |---------|-----------------|------------------------------------------|
+/// | `0`     | Sampled image   | [`BindlessHeap::SAMPLED_IMAGE_BINDING`]  |
+/// | `1`     | Storage image   | [`BindlessHeap::STORAGE_IMAGE_BINDING`]  |
+/// | `2`     | Storage buffer  | [`BindlessHeap::STORAGE_BUFFER_BINDING`] |
+/// | `3`     | Sampler         | [`BindlessHeap::SAMPLER_BINDING`]        |
+///
+/// Descriptors are written when resources are inserted and, because every binding is created
+/// using `UPDATE_AFTER_BIND` and `PARTIALLY_BOUND`, the heap may be modified while previously
+/// recorded command buffers are pending. Removed indices are not handed out again, and removed
+/// resources are kept alive, until every execution which bound the heap before the removal has
+/// been retired.
+///
+/// # Synchronization
+///
+/// The heap does not know which indices a shader accesses. When the heap is bound to a pass using
+/// [`PipelinePassRef::bind_bindless_heap`], passes must declare the access of each index a shader
+/// uses with [`PipelinePassRef::access_bindless`]; the image or buffer stored at that index is
+/// bound to the render graph so that the correct pipeline barriers and image layout transitions
+/// are recorded.
+///
+/// # Examples
+///
+/// Basic usage (GLSL):
+///
+/// ```
+/// # inline_spirv::inline_spirv!(r#"
+/// #version 460 core
+/// #extension GL_EXT_nonuniform_qualifier : require
+///
+/// layout(set = 1, binding = 0) uniform texture2D images[];
+/// layout(set = 1, binding = 3) uniform sampler samplers[];
+///
+/// layout(push_constant) uniform PushConstants {
+///     uint image_idx;
+///     uint sampler_idx;
+/// } push_constants;
+///
+/// layout(location = 0) in vec2 texcoord_in;
+/// layout(location = 0) out vec4 color_out;
+///
+/// void main()
+/// {
+///     color_out = texture(
+///         sampler2D(
+///             images[nonuniformEXT(push_constants.image_idx)],
+///             samplers[nonuniformEXT(push_constants.sampler_idx)]
+///         ),
+///         texcoord_in
+///     );
+/// }
+/// # "#, frag);
+/// ```
+///
+/// [`ComputePipelineInfoBuilder::bindless_heap`]: super::compute::ComputePipelineInfoBuilder::bindless_heap
+/// [`GraphicPipelineInfoBuilder::bindless_heap`]: super::graphic::GraphicPipelineInfoBuilder::bindless_heap
+/// [`RayTracePipelineInfoBuilder::bindless_heap`]: super::ray_trace::RayTracePipelineInfoBuilder::bindless_heap
+/// [`PipelinePassRef::access_bindless`]: crate::graph::pass_ref::PipelinePassRef::access_bindless
+/// [`PipelinePassRef::bind_bindless_heap`]: crate::graph::pass_ref::PipelinePassRef::bind_bindless_heap
+#[derive(Debug)]
+pub struct BindlessHeap {
+    descriptor_pool: vk::DescriptorPool,
+    descriptor_set: vk::DescriptorSet,
+    device: Arc<Device>,
+
+    /// Information used to create this object.
+    pub info: BindlessHeapInfo,
+
+    slots: Mutex<BindlessHeapSlots>,
+
+    #[allow(dead_code)]
+    descriptor_set_layout: DescriptorSetLayout,
+}
+
+impl BindlessHeap {
+    /// The descriptor binding of sampled image descriptors.
+    pub const SAMPLED_IMAGE_BINDING: u32 = 0;
+
+    /// The descriptor binding of storage image descriptors.
+    pub const STORAGE_IMAGE_BINDING: u32 = 1;
+
+    /// The descriptor binding of storage buffer descriptors.
+    pub const STORAGE_BUFFER_BINDING: u32 = 2;
+
+    /// The descriptor binding of sampler descriptors.
+    pub const SAMPLER_BINDING: u32 = 3;
+
+    /// Creates a new bindless heap on the given device.
+    ///
+    /// The device must support the `descriptorBindingPartiallyBound`,
+    /// `descriptorBindingUpdateUnusedWhilePending` and `runtimeDescriptorArray` features, as well
+    /// as the update-after-bind feature of each descriptor type with a non-zero count.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::bindless::{BindlessHeap, BindlessHeapInfo};
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// let info = BindlessHeapInfo::default().to_builder().sampled_image_count(1024);
+    /// let heap = BindlessHeap::create(&device, info)?;
+    ///
+    /// assert_eq!(heap.info.sampled_image_count, 1024);
+    /// # Ok(()) }
+    /// ```
+    #[profiling::function]
+    pub fn create(
+        device: &Arc<Device>,
+        info: impl Into<BindlessHeapInfo>,
+    ) -> Result<Self, DriverError> {
+        let info = info.into();
+
+        Self::validate(device, info)?;
+
+        let descriptor_set_layout = create_descriptor_set_layout(device, info)?;
+
+        let mut pool_sizes = Vec::with_capacity(4);
+
+        for (ty, descriptor_count) in [
+            (vk::DescriptorType::SAMPLED_IMAGE, info.sampled_image_count),
+            (vk::DescriptorType::STORAGE_IMAGE, info.storage_image_count),
+            (
+                vk::DescriptorType::STORAGE_BUFFER,
+                info.storage_buffer_count,
+            ),
+            (vk::DescriptorType::SAMPLER, info.sampler_count),
+        ] {
+            if descriptor_count > 0 {
+                pool_sizes.push(vk::DescriptorPoolSize {
+                    ty,
+                    descriptor_count,
+                });
+            }
+        }
+
+        let descriptor_pool = unsafe {
+            device.create_descriptor_pool(
+                &vk::DescriptorPoolCreateInfo::default()
+                    .flags(vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND)
+                    .max_sets(1)
+                    .pool_sizes(&pool_sizes),
+                None,
+            )
+        }
+        .map_err(|err| {
+            warn!("{err}");
+
+            DriverError::Unsupported
+        })?;
+
+        let descriptor_set = unsafe {
+            device.allocate_descriptor_sets(
+                &vk::DescriptorSetAllocateInfo::default()
+                    .descriptor_pool(descriptor_pool)
+                    .set_layouts(from_ref(&*descriptor_set_layout)),
+            )
+        }
+        .map_err(|err| {
+            warn!("{err}");
+
+            unsafe {
+                device.destroy_descriptor_pool(descriptor_pool, None);
+            }
+
+            DriverError::OutOfMemory
+        })?[0];
+
+        let device = Arc::clone(device);
+
+        Ok(Self {
+            descriptor_pool,
+            descriptor_set,
+            descriptor_set_layout,
+            device,
+            info,
+            slots: Mutex::new(BindlessHeapSlots::default()),
+        })
+    }
+
+    /// Returns the Vulkan descriptor set of this heap.
+    pub(crate) fn descriptor_set(this: &Self) -> vk::DescriptorSet {
+        this.descriptor_set
+    }
+
+    /// Inserts an image into the heap as a sampled image descriptor, returning its index.
+    ///
+    /// The image is kept alive until it is removed from the heap. Shaders must only access the
+    /// image while it is in a shader read-only layout, see [`BindlessHeap`] for details.
+    ///
+    /// Returns [`DriverError::OutOfMemory`] if all sampled image descriptors are in use.
+    #[profiling::function]
+    pub fn insert_sampled_image(
+        this: &Self,
+        image: &Arc<Image>,
+        view_info: impl Into<ImageViewInfo>,
+    ) -> Result<BindlessIndex, DriverError> {
+        let descriptor_type = vk::DescriptorType::SAMPLED_IMAGE;
+        let (image_view, view_info) = Self::image_view(image, view_info.into())?;
+        let image_info = vk::DescriptorImageInfo::default()
+            .image_layout(descriptor_image_layout(
+                descriptor_type,
+                view_info.aspect_mask,
+            ))
+            .image_view(image_view);
+
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut slots = this.slots.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut slots = slots.unwrap();
+
+        slots.release_retired();
+
+        let idx = slots
+            .sampled_images
+            .insert(
+                (Arc::clone(image), view_info),
+                this.info.sampled_image_count,
+            )
+            .ok_or_else(Self::full)?;
+
+        Self::write_descriptor(
+            this,
+            vk::WriteDescriptorSet::default()
+                .dst_binding(Self::SAMPLED_IMAGE_BINDING)
+                .dst_array_element(idx)
+                .descriptor_type(descriptor_type)
+                .image_info(from_ref(&image_info)),
+        );
+
+        Ok(BindlessIndex::SampledImage(idx))
+    }
+
+    /// Inserts a sampler into the heap, returning its index.
+    ///
+    /// Returns [`DriverError::OutOfMemory`] if all sampler descriptors are in use.
+    #[profiling::function]
+    pub fn insert_sampler(
+        this: &Self,
+        info: impl Into<SamplerInfo>,
+    ) -> Result<BindlessIndex, DriverError> {
+        let info = info.into();
+        let image_info =
+            vk::DescriptorImageInfo::default().sampler(Device::sampler(&this.device, info)?);
+
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut slots = this.slots.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut slots = slots.unwrap();
+
+        slots.release_retired();
+
+        let idx = slots
+            .samplers
+            .insert(info, this.info.sampler_count)
+            .ok_or_else(Self::full)?;
+
+        Self::write_descriptor(
+            this,
+            vk::WriteDescriptorSet::default()
+                .dst_binding(Self::SAMPLER_BINDING)
+                .dst_array_element(idx)
+                .descriptor_type(vk::DescriptorType::SAMPLER)
+                .image_info(from_ref(&image_info)),
+        );
+
+        Ok(BindlessIndex::Sampler(idx))
+    }
+
+    /// Inserts a buffer into the heap as a storage buffer descriptor, returning its index.
+    ///
+    /// The buffer is kept alive until it is removed from the heap.
+    ///
+    /// Returns [`DriverError::OutOfMemory`] if all storage buffer descriptors are in use.
+    #[profiling::function]
+    pub fn insert_storage_buffer(
+        this: &Self,
+        buffer: &Arc<Buffer>,
+    ) -> Result<BindlessIndex, DriverError> {
+        let buffer_info = vk::DescriptorBufferInfo::default()
+            .buffer(***buffer)
+            .offset(0)
+            .range(vk::WHOLE_SIZE);
+
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut slots = this.slots.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut slots = slots.unwrap();
+
+        slots.release_retired();
+
+        let idx = slots
+            .storage_buffers
+            .insert(Arc::clone(buffer), this.info.storage_buffer_count)
+            .ok_or_else(Self::full)?;
+
+        Self::write_descriptor(
+            this,
+            vk::WriteDescriptorSet::default()
+                .dst_binding(Self::STORAGE_BUFFER_BINDING)
+                .dst_array_element(idx)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .buffer_info(from_ref(&buffer_info)),
+        );
+
+        Ok(BindlessIndex::StorageBuffer(idx))
+    }
+
+    /// Inserts an image into the heap as a storage image descriptor, returning its index.
+    ///
+    /// The image is kept alive until it is removed from the heap. Shaders must only access the
+    /// image while it is in the general layout, see [`BindlessHeap`] for details.
+    ///
+    /// Returns [`DriverError::OutOfMemory`] if all storage image descriptors are in use.
+    #[profiling::function]
+    pub fn insert_storage_image(
+        this: &Self,
+        image: &Arc<Image>,
+        view_info: impl Into<ImageViewInfo>,
+    ) -> Result<BindlessIndex, DriverError> {
+        let descriptor_type = vk::DescriptorType::STORAGE_IMAGE;
+        let (image_view, view_info) = Self::image_view(image, view_info.into())?;
+        let image_info = vk::DescriptorImageInfo::default()
+            .image_layout(descriptor_image_layout(
+                descriptor_type,
+                view_info.aspect_mask,
+            ))
+            .image_view(image_view);
+
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut slots = this.slots.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut slots = slots.unwrap();
+
+        slots.release_retired();
+
+        let idx = slots
+            .storage_images
+            .insert(
+                (Arc::clone(image), view_info),
+                this.info.storage_image_count,
+            )
+            .ok_or_else(Self::full)?;
+
+        Self::write_descriptor(
+            this,
+            vk::WriteDescriptorSet::default()
+                .dst_binding(Self::STORAGE_IMAGE_BINDING)
+                .dst_array_element(idx)
+                .descriptor_type(descriptor_type)
+                .image_info(from_ref(&image_info)),
+        );
+
+        Ok(BindlessIndex::StorageImage(idx))
+    }
+
+    /// Removes a previously inserted descriptor from the heap, returning `true` if the index was
+    /// in use.
+    ///
+    /// The resource is released, and the index is returned to a free-list to be handed out by a
+    /// later insert, once every execution which bound the heap before this call has been retired.
+    #[profiling::function]
+    pub fn remove(this: &Self, index: BindlessIndex) -> bool {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut slots = this.slots.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut slots = slots.unwrap();
+
+        let resource = match index {
+            BindlessIndex::SampledImage(idx) => slots
+                .sampled_images
+                .remove(idx)
+                .map(|(image, view_info)| Some(BindlessResource::Image(image, view_info))),
+            BindlessIndex::Sampler(idx) => slots.samplers.remove(idx).map(|_| None),
+            BindlessIndex::StorageBuffer(idx) => slots
+                .storage_buffers
+                .remove(idx)
+                .map(|buffer| Some(BindlessResource::Buffer(buffer))),
+            BindlessIndex::StorageImage(idx) => slots
+                .storage_images
+                .remove(idx)
+                .map(|(image, view_info)| Some(BindlessResource::Image(image, view_info))),
+        };
+
+        let Some(resource) = resource else {
+            return false;
+        };
+
+        slots.uses.retain(|heap_use| heap_use.strong_count() > 0);
+
+        let uses = slots.uses.clone();
+        slots.pending.push(BindlessRemoval {
+            index,
+            _resource: resource,
+            uses,
+        });
+        slots.release_retired();
+
+        true
+    }
+
+    /// Returns the image or buffer which is currently resident at `index`, if any.
+    ///
+    /// Samplers are not resources and always return `None`.
+    pub(crate) fn resource(this: &Self, index: BindlessIndex) -> Option<BindlessResource> {
+        let slots = this.slots.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let slots = slots.unwrap();
+
+        let image = |(image, view_info): &(Arc<Image>, ImageViewInfo)| {
+            BindlessResource::Image(Arc::clone(image), *view_info)
+        };
+
+        match index {
+            BindlessIndex::SampledImage(idx) => slots.sampled_images.get(idx).map(image),
+            BindlessIndex::Sampler(_) => None,
+            BindlessIndex::StorageBuffer(idx) => slots
+                .storage_buffers
+                .get(idx)
+                .map(|buffer| BindlessResource::Buffer(Arc::clone(buffer))),
+            BindlessIndex::StorageImage(idx) => slots.storage_images.get(idx).map(image),
+        }
+    }
+
+    /// Returns a use which delays the release of descriptors removed from the heap until it is
+    /// dropped.
+    pub(crate) fn use_heap(this: &Self) -> BindlessHeapUse {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut slots = this.slots.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut slots = slots.unwrap();
+
+        let heap_use = Arc::new(());
+        slots.uses.retain(|heap_use| heap_use.strong_count() > 0);
+        slots.uses.push(Arc::downgrade(&heap_use));
+
+        BindlessHeapUse(heap_use)
+    }
+
+    fn full() -> DriverError {
+        warn!("bindless heap is full");
+
+        DriverError::OutOfMemory
+    }
+
+    fn image_view(
+        image: &Image,
+        mut view_info: ImageViewInfo,
+    ) -> Result<(vk::ImageView, ImageViewInfo), DriverError> {
+        // Handle default views which did not specify a particular aspect
+        if view_info.aspect_mask.is_empty() {
+            view_info.aspect_mask = format_aspect_mask(image.info.fmt);
+        }
+
+        Ok((Image::view(image, view_info)?, view_info))
+    }
+
+    fn validate(device: &Device, info: BindlessHeapInfo) -> Result<(), DriverError> {
+        let features = &device.physical_device.features_v1_2;
+        let properties = &device.physical_device.properties_v1_2;
+
+        if !features.descriptor_binding_partially_bound
+            || !features.descriptor_binding_update_unused_while_pending
+            || !features.runtime_descriptor_array
+        {
+            warn!("unsupported descriptor indexing");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if ((info.sampled_image_count > 0 || info.sampler_count > 0)
+            && !features.descriptor_binding_sampled_image_update_after_bind)
+            || (info.storage_image_count > 0
+                && !features.descriptor_binding_storage_image_update_after_bind)
+            || (info.storage_buffer_count > 0
+                && !features.descriptor_binding_storage_buffer_update_after_bind)
+        {
+            warn!("unsupported descriptor update after bind");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if info.sampled_image_count > properties.max_descriptor_set_update_after_bind_sampled_images
+            || info.storage_image_count
+                > properties.max_descriptor_set_update_after_bind_storage_images
+            || info.storage_buffer_count
+                > properties.max_descriptor_set_update_after_bind_storage_buffers
+            || info.sampler_count > properties.max_descriptor_set_update_after_bind_samplers
+        {
+            warn!("unsupported bindless heap descriptor count");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        Ok(())
+    }
+
+    fn write_descriptor(this: &Self, write: vk::WriteDescriptorSet) {
+        unsafe {
+            this.device
+                .update_descriptor_sets(from_ref(&write.dst_set(this.descriptor_set)), &[]);
+        }
+    }
+}
+
+impl Drop for BindlessHeap {
+    #[profiling::function]
+    fn drop(&mut self) {
+        if panicking() {
+            return;
+        }
+
+        unsafe {
+            self.device
+                .destroy_descriptor_pool(self.descriptor_pool, None);
+        }
+    }
+}
+
+/// Information used to create a [`BindlessHeap`] instance.
+///
+/// Pipelines which use a bindless heap must be created using the same information as the heap.
+#[derive(Builder, Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[builder(
+    build_fn(
+        private,
+        name = "fallible_build",
+        error = "BindlessHeapInfoBuilderError"
+    ),
+    derive(Clone, Copy, Debug),
+    pattern = "owned"
+)]
+#[non_exhaustive]
+pub struct BindlessHeapInfo {
+    /// The number of sampled image descriptors.
+    ///
+    /// The default is `4096`.
+    #[builder(default = "4096")]
+    pub sampled_image_count: u32,
+
+    /// The number of sampler descriptors.
+    ///
+    /// The default is `64`.
+    #[builder(default = "64")]
+    pub sampler_count: u32,
+
+    /// The number of storage buffer descriptors.
+    ///
+    /// The default is `1024`.
+    #[builder(default = "1024")]
+    pub storage_buffer_count: u32,
+
+    /// The number of storage image descriptors.
+    ///
+    /// The default is `1024`.
+    #[builder(default = "1024")]
+    pub storage_image_count: u32,
+}
+
+impl BindlessHeapInfo {
+    /// Converts a `BindlessHeapInfo` into a `BindlessHeapInfoBuilder`.
+    #[inline(always)]
+    pub fn to_builder(self) -> BindlessHeapInfoBuilder {
+        BindlessHeapInfoBuilder {
+            sampled_image_count: Some(self.sampled_image_count),
+            sampler_count: Some(self.sampler_count),
+            storage_buffer_count: Some(self.storage_buffer_count),
+            storage_image_count: Some(self.storage_image_count),
+        }
+    }
+}
+
+impl Default for BindlessHeapInfo {
+    fn default() -> Self {
+        Self {
+            sampled_image_count: 4096,
+            sampler_count: 64,
+            storage_buffer_count: 1024,
+            storage_image_count: 1024,
+        }
+    }
+}
+
+impl From<BindlessHeapInfoBuilder> for BindlessHeapInfo {
+    fn from(info: BindlessHeapInfoBuilder) -> Self {
+        info.build()
+    }
+}
+
+impl BindlessHeapInfoBuilder {
+    /// Builds a new `BindlessHeapInfo`.
+    #[inline(always)]
+    pub fn build(self) -> BindlessHeapInfo {
+        let res = self.fallible_build();
+
+        #[cfg(test)]
+        let res = res.unwrap();
+
+        #[cfg(not(test))]
+        let res = unsafe { res.unwrap_unchecked() };
+
+        res
+    }
+}
+
+#[derive(Debug)]
+struct BindlessHeapInfoBuilderError;
+
+impl From<UninitializedFieldError> for BindlessHeapInfoBuilderError {
+    fn from(_: UninitializedFieldError) -> Self {
+        Self
+    }
+}
+
+/// Delays the release of descriptors removed from a [`BindlessHeap`] until dropped.
+#[derive(Debug)]
+pub(crate) struct BindlessHeapUse(#[allow(dead_code)] Arc<()>);
+
+#[derive(Debug, Default)]
+struct BindlessHeapSlots {
+    pending: Vec<BindlessRemoval>,
+    sampled_images: BindlessSlots<(Arc<Image>, ImageViewInfo)>,
+    samplers: BindlessSlots<SamplerInfo>,
+    storage_buffers: BindlessSlots<Arc<Buffer>>,
+    storage_images: BindlessSlots<(Arc<Image>, ImageViewInfo)>,
+    uses: Vec<Weak<()>>,
+}
+
+impl BindlessHeapSlots {
+    /// Returns the indices of removed descriptors to the free-lists once all uses of the heap which
+    /// existed at the time of removal have been dropped.
+    fn release_retired(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let (retired, pending): (Vec<_>, _) = take(&mut self.pending)
+            .into_iter()
+            .partition(BindlessRemoval::is_retired);
+        self.pending = pending;
+
+        for removal in retired {
+            match removal.index {
+                BindlessIndex::SampledImage(idx) => self.sampled_images.release(idx),
+                BindlessIndex::Sampler(idx) => self.samplers.release(idx),
+                BindlessIndex::StorageBuffer(idx) => self.storage_buffers.release(idx),
+                BindlessIndex::StorageImage(idx) => self.storage_images.release(idx),
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BindlessRemoval {
+    index: BindlessIndex,
+    _resource: Option<BindlessResource>,
+    uses: Vec<Weak<()>>,
+}
+
+impl BindlessRemoval {
+    fn is_retired(&self) -> bool {
+        self.uses
+            .iter()
+            .all(|heap_use| heap_use.strong_count() == 0)
+    }
+}
+
+/// An image or buffer which is resident in a [`BindlessHeap`].
+#[derive(Debug)]
+pub(crate) enum BindlessResource {
+    Buffer(Arc<Buffer>),
+    Image(Arc<Image>, ImageViewInfo),
+}
+
+/// A stable index of a descriptor stored in a [`BindlessHeap`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BindlessIndex {
+    /// An index into the sampled image descriptors.
+    SampledImage(u32),
+
+    /// An index into the sampler descriptors.
+    Sampler(u32),
+
+    /// An index into the storage buffer descriptors.
+    StorageBuffer(u32),
+
+    /// An index into the storage image descriptors.
+    StorageImage(u32),
+}
+
+impl BindlessIndex {
+    /// Returns the descriptor binding of this index within a [`BindlessHeap`].
+    pub const fn binding(self) -> u32 {
+        match self {
+            Self::SampledImage(_) => BindlessHeap::SAMPLED_IMAGE_BINDING,
+            Self::Sampler(_) => BindlessHeap::SAMPLER_BINDING,
+            Self::StorageBuffer(_) => BindlessHeap::STORAGE_BUFFER_BINDING,
+            Self::StorageImage(_) => BindlessHeap::STORAGE_IMAGE_BINDING,
+        }
+    }
+
+    /// Returns the array element of this index, which is the value shaders use to access the
+    /// descriptor.
+    pub const fn index(self) -> u32 {
+        match self {
+            Self::SampledImage(idx)
+            | Self::Sampler(idx)
+            | Self::StorageBuffer(idx)
+            | Self::StorageImage(idx) => idx,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BindlessSlots<T> {
+    free: Vec<u32>,
+    items: Vec<Option<T>>,
+}
+
+impl<T> BindlessSlots<T> {
+    fn insert(&mut self, item: T, capacity: u32) -> Option<u32> {
+        if let Some(idx) = self.free.pop() {
+            self.items[idx as usize] = Some(item);
+
+            return Some(idx);
+        }
+
+        let idx = self.items.len() as u32;
+
+        if idx >= capacity {
+            return None;
+        }
+
+        self.items.push(Some(item));
+
+        Some(idx)
+    }
+
+    fn get(&self, idx: u32) -> Option<&T> {
+        self.items.get(idx as usize)?.as_ref()
+    }
+
+    /// Returns a removed index to the free-list.
+    fn release(&mut self, idx: u32) {
+        debug_assert!(self.items[idx as usize].is_none());
+
+        self.free.push(idx);
+    }
+
+    /// Removes an item; the index is not handed out again until it is released.
+    fn remove(&mut self, idx: u32) -> Option<T> {
+        self.items.get_mut(idx as usize)?.take()
+    }
+}
+
+impl<T> Default for BindlessSlots<T> {
+    fn default() -> Self {
+        Self {
+            free: vec![],
+            items: vec![],
+        }
+    }
+}
+
+/// Creates a descriptor set layout which is compatible with any bindless heap created using the
+/// given information.
+pub(super) fn create_descriptor_set_layout(
+    device: &Arc<Device>,
+    info: BindlessHeapInfo,
+) -> Result<DescriptorSetLayout, DriverError> {
+    let bindings = [
+        (
+            BindlessHeap::SAMPLED_IMAGE_BINDING,
+            vk::DescriptorType::SAMPLED_IMAGE,
+            info.sampled_image_count,
+        ),
+        (
+            BindlessHeap::STORAGE_IMAGE_BINDING,
+            vk::DescriptorType::STORAGE_IMAGE,
+            info.storage_image_count,
+        ),
+        (
+            BindlessHeap::STORAGE_BUFFER_BINDING,
+            vk::DescriptorType::STORAGE_BUFFER,
+            info.storage_buffer_count,
+        ),
+        (
+            BindlessHeap::SAMPLER_BINDING,
+            vk::DescriptorType::SAMPLER,
+            info.sampler_count,
+        ),
+    ]
+    .into_iter()
+    .filter(|&(_, _, descriptor_count)| descriptor_count > 0)
+    .map(|(binding, descriptor_type, descriptor_count)| {
+        vk::DescriptorSetLayoutBinding::default()
+            .binding(binding)
+            .descriptor_count(descriptor_count)
+            .descriptor_type(descriptor_type)
+            .stage_flags(vk::ShaderStageFlags::ALL)
+    })
+    .collect::<Box<_>>();
+    let binding_flags = vec![
+        vk::DescriptorBindingFlags::PARTIALLY_BOUND
+            | vk::DescriptorBindingFlags::UPDATE_AFTER_BIND
+            | vk::DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING;
+        bindings.len()
+    ];
+    let mut binding_flags =
+        vk::DescriptorSetLayoutBindingFlagsCreateInfo::default().binding_flags(&binding_flags);
+
+    DescriptorSetLayout::create(
+        device,
+        &vk::DescriptorSetLayoutCreateInfo::default()
+            .flags(vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL)
+            .bindings(&bindings)
+            .push_next(&mut binding_flags),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Info = BindlessHeapInfo;
+    type Builder = BindlessHeapInfoBuilder;
+
+    #[test]
+    pub fn bindless_heap_info() {
+        let info = Info::default();
+        let builder = info.to_builder().build();
+
+        assert_eq!(info, builder);
+    }
+
+    #[test]
+    pub fn bindless_heap_info_builder() {
+        let info = Info::default();
+        let builder = Builder::default().build();
+
+        assert_eq!(info, builder);
+    }
+
+    #[test]
+    pub fn bindless_slots() {
+        let mut slots = BindlessSlots::default();
+
+        assert_eq!(slots.insert('a', 2), Some(0));
+        assert_eq!(slots.insert('b', 2), Some(1));
+        assert_eq!(slots.insert('c', 2), None);
+        assert_eq!(slots.remove(0), Some('a'));
+        assert_eq!(slots.remove(0), None);
+        assert_eq!(slots.insert('d', 2), None);
+        assert_eq!(slots.get(0), None);
+        assert_eq!(slots.get(1), Some(&'b'));
+
+        slots.release(0);
+
+        assert_eq!(slots.insert('d', 2), Some(0));
+        assert_eq!(slots.remove(2), None);
+    }
+
+    #[test]
+    pub fn bindless_heap_slots_release_retired() {
+        let mut slots = BindlessHeapSlots::default();
+        let heap_use = Arc::new(());
+
+        slots.uses.push(Arc::downgrade(&heap_use));
+
+        assert_eq!(slots.samplers.insert(SamplerInfo::default(), 1), Some(0));
+        assert!(slots.samplers.remove(0).is_some());
+
+        slots.pending.push(BindlessRemoval {
+            index: BindlessIndex::Sampler(0),
+            _resource: None,
+            uses: slots.uses.clone(),
+        });
+        slots.release_retired();
+
+        assert_eq!(slots.samplers.insert(SamplerInfo::default(), 1), None);
+
+        drop(heap_use);
+        slots.release_retired();
+
+        assert!(slots.pending.is_empty());
+        assert_eq!(slots.samplers.insert(SamplerInfo::default(), 1), Some(0));
+    }
+}
@@ -1,16 +1,19 @@
 //! Buffer resource types
 
 use {
-    super::{DriverError, device::Device, vk_sync::AccessType},
+    super::{
+        DriverError, HostAccess, allocation_params,
+        device::{Device, allocation_key},
+        vk_sync::AccessType,
+    },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
-    gpu_allocator::{
-        MemoryLocation,
-        vulkan::{Allocation, AllocationCreateDesc, AllocationScheme},
-    },
+    gpu_allocator::vulkan::{Allocation, AllocationCreateDesc, AllocationScheme},
     log::trace,
     log::warn,
+    ordered_float::OrderedFloat,
     std::{
+        collections::{HashMap, hash_map::Entry},
         fmt::{Debug, Formatter},
         mem::ManuallyDrop,
         ops::{Deref, DerefMut, Range},
@@ -57,12 +60,17 @@ pub struct Buffer {
     accesses: Mutex<BufferAccess>,
     allocation: ManuallyDrop<Allocation>,
     buffer: vk::Buffer,
+    buffer_view_cache: Mutex<HashMap<BufferViewInfo, BufferView>>,
+    capacity: vk::DeviceSize,
     device: Arc<Device>,
 
     /// Information used to create this object.
     pub info: BufferInfo,
 
     /// A name for debugging purposes.
+    ///
+    /// The name is also used to identify the memory of this buffer in memory reports, see
+    /// [`MemoryReport::name_buffers`](super::device::MemoryReport::name_buffers).
     pub name: Option<String>,
 }
 
@@ -110,13 +118,34 @@ impl Buffer {
                 DriverError::Unsupported
             })?
         };
-        let mut requirements = unsafe { device.get_buffer_memory_requirements(buffer) };
+        let mut dedicated_requirements = vk::MemoryDedicatedRequirements::default();
+        let mut requirements =
+            vk::MemoryRequirements2::default().push_next(&mut dedicated_requirements);
+        unsafe {
+            device.get_buffer_memory_requirements2(
+                &vk::BufferMemoryRequirementsInfo2::default().buffer(buffer),
+                &mut requirements,
+            );
+        }
+        let mut requirements = requirements.memory_requirements;
         requirements.alignment = requirements.alignment.max(info.alignment);
 
-        let memory_location = if info.mappable {
-            MemoryLocation::CpuToGpu
+        let (dedicated, memory_location, memory_priority) = allocation_params(
+            &device,
+            info.dedicated,
+            &dedicated_requirements,
+            info.memory_priority,
+            info.mappable,
+            info.host_access,
+            &mut requirements,
+        )
+        .inspect_err(|_| unsafe {
+            device.destroy_buffer(buffer, None);
+        })?;
+        let allocation_scheme = if dedicated {
+            AllocationScheme::DedicatedBuffer(buffer)
         } else {
-            MemoryLocation::GpuOnly
+            AllocationScheme::GpuAllocatorManaged
         };
         let allocation = {
             profiling::scope!("allocate");
@@ -133,7 +162,7 @@ impl Buffer {
                     requirements,
                     location: memory_location,
                     linear: true, // Buffers are always linear
-                    allocation_scheme: AllocationScheme::GpuAllocatorManaged,
+                    allocation_scheme,
                 })
                 .map_err(|err| {
                     warn!("{err}");
@@ -142,6 +171,10 @@ impl Buffer {
                 })
         }?;
 
+        if let Some(memory_priority) = memory_priority {
+            Device::set_memory_priority(&device, unsafe { allocation.memory() }, memory_priority);
+        }
+
         // Bind memory to the buffer
         unsafe {
             device
@@ -153,10 +186,14 @@ impl Buffer {
                 })?
         };
 
+        Device::track_allocation(&device, &allocation, "buffer");
+
         Ok(Self {
             accesses: Mutex::new(BufferAccess::new(info.size)),
             allocation: ManuallyDrop::new(allocation),
             buffer,
+            buffer_view_cache: Default::default(),
+            capacity: info.size,
             device,
             info,
             name: None,
@@ -269,6 +306,12 @@ impl Buffer {
         BufferAccessIter::new(accesses, access, access_range)
     }
 
+    /// Returns the size in bytes of the buffer as created, which may be larger than `info.size` for
+    /// buffers leased from a pool using a [`SizeClass`](crate::pool::SizeClass).
+    pub fn capacity(this: &Self) -> vk::DeviceSize {
+        this.capacity
+    }
+
     /// Updates a mappable buffer starting at `offset` with the data in `slice`.
     ///
     /// # Panics
@@ -416,6 +459,34 @@ impl Buffer {
 
         &mut this.allocation.mapped_slice_mut().unwrap()[0..this.info.size as usize]
     }
+
+    #[profiling::function]
+    pub(crate) fn view(this: &Self, info: BufferViewInfo) -> Result<vk::BufferView, DriverError> {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut buffer_view_cache = this.buffer_view_cache.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut buffer_view_cache = buffer_view_cache.unwrap();
+
+        Ok(match buffer_view_cache.entry(info) {
+            Entry::Occupied(entry) => entry.get().buffer_view,
+            Entry::Vacant(entry) => {
+                entry
+                    .insert(BufferView::create(&this.device, info, this)?)
+                    .buffer_view
+            }
+        })
+    }
+
+    pub(super) fn allocation_key(this: &Self) -> (vk::DeviceMemory, vk::DeviceSize) {
+        allocation_key(&this.allocation)
+    }
+
+    /// Sets the debugging name assigned to this buffer.
+    pub fn with_name(mut this: Self, name: impl Into<String>) -> Self {
+        this.name = Some(name.into());
+        this
+    }
 }
 
 impl Debug for Buffer {
@@ -443,9 +514,23 @@ impl Drop for Buffer {
             return;
         }
 
+        {
+            profiling::scope!("views");
+
+            #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+            let mut buffer_view_cache = self.buffer_view_cache.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let mut buffer_view_cache = buffer_view_cache.unwrap();
+
+            buffer_view_cache.clear();
+        }
+
         {
             profiling::scope!("deallocate");
 
+            Device::untrack_allocation(&self.device, &self.allocation);
+
             #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
             let mut allocator = self.device.allocator.lock();
 
@@ -695,10 +780,36 @@ pub struct BufferInfo {
     #[builder(default = "1")]
     pub alignment: vk::DeviceSize,
 
+    /// Specifies a buffer which uses a dedicated memory allocation instead of sharing a larger
+    /// block of memory with other resources.
+    ///
+    /// Dedicated allocations are always used when the driver prefers or requires them, or when
+    /// `memory_priority` is specified and supported by the device.
+    #[builder(default)]
+    pub dedicated: bool,
+
+    /// Specifies how the host accesses the memory of a mappable buffer.
+    ///
+    /// Has no effect unless `mappable` is `true`.
+    #[builder(default)]
+    pub host_access: HostAccess,
+
     /// Specifies a buffer whose memory is host visible and may be mapped.
     #[builder(default)]
     pub mappable: bool,
 
+    // Note: Using setter(into) so caller does not need our version of OrderedFloat
+    /// Specifies the priority, between `0.0` and `1.0`, of the memory of this buffer relative to
+    /// other memory allocations.
+    ///
+    /// Higher priority memory is more likely to stay in device-local memory when the system is
+    /// under memory pressure. Requires
+    /// [`MemoryPriorityFeatures::pageable_device_local_memory`](super::physical_device::MemoryPriorityFeatures::pageable_device_local_memory)
+    /// and is otherwise ignored, with a warning logged once, without affecting how memory is
+    /// allocated.
+    #[builder(default, setter(into, strip_option))]
+    pub memory_priority: Option<OrderedFloat<f32>>,
+
     /// Size in bytes of the buffer to be created.
     pub size: vk::DeviceSize,
 
@@ -715,7 +826,10 @@ impl BufferInfo {
     pub const fn device_mem(size: vk::DeviceSize, usage: vk::BufferUsageFlags) -> BufferInfo {
         BufferInfo {
             alignment: 1,
+            dedicated: false,
+            host_access: HostAccess::Write,
             mappable: false,
+            memory_priority: None,
             size,
             usage,
         }
@@ -739,7 +853,64 @@ impl BufferInfo {
 
         BufferInfo {
             alignment: 1,
+            dedicated: false,
+            host_access: HostAccess::Write,
+            mappable: true,
+            memory_priority: None,
+            size,
+            usage,
+        }
+    }
+
+    /// Specifies a mappable buffer with the given `size` and `usage` values which is suitable for
+    /// reading data written by the device.
+    ///
+    /// Host-cached memory (located in CPU-accesible RAM) is used.
+    ///
+    /// # Note
+    ///
+    /// For convenience the given usage value will be bitwise OR'd with `TRANSFER_DST`.
+    #[inline(always)]
+    pub const fn readback_mem(size: vk::DeviceSize, usage: vk::BufferUsageFlags) -> BufferInfo {
+        let usage = vk::BufferUsageFlags::from_raw(
+            usage.as_raw() | vk::BufferUsageFlags::TRANSFER_DST.as_raw(),
+        );
+
+        BufferInfo {
+            alignment: 1,
+            dedicated: false,
+            host_access: HostAccess::Read,
+            mappable: true,
+            memory_priority: None,
+            size,
+            usage,
+        }
+    }
+
+    /// Specifies a mappable buffer with the given `size` and `usage` values which is located in
+    /// device-local memory.
+    ///
+    /// Host-visible device-local memory (resizable BAR) is used and buffer creation fails if the
+    /// device does not support it.
+    ///
+    /// # Note
+    ///
+    /// For convenience the given usage value will be bitwise OR'd with
+    /// `TRANSFER_DST | TRANSFER_SRC`.
+    #[inline(always)]
+    pub const fn device_host_mem(size: vk::DeviceSize, usage: vk::BufferUsageFlags) -> BufferInfo {
+        let usage = vk::BufferUsageFlags::from_raw(
+            usage.as_raw()
+                | vk::BufferUsageFlags::TRANSFER_DST.as_raw()
+                | vk::BufferUsageFlags::TRANSFER_SRC.as_raw(),
+        );
+
+        BufferInfo {
+            alignment: 1,
+            dedicated: false,
+            host_access: HostAccess::DeviceLocalWrite,
             mappable: true,
+            memory_priority: None,
             size,
             usage,
         }
@@ -770,7 +941,10 @@ impl BufferInfo {
     pub fn to_builder(self) -> BufferInfoBuilder {
         BufferInfoBuilder {
             alignment: Some(self.alignment),
+            dedicated: Some(self.dedicated),
+            host_access: Some(self.host_access),
             mappable: Some(self.mappable),
+            memory_priority: Some(self.memory_priority),
             size: Some(self.size),
             usage: Some(self.usage),
         }
@@ -819,8 +993,119 @@ impl From<UninitializedFieldError> for BufferInfoBuilderError {
     }
 }
 
+struct BufferView {
+    buffer_view: vk::BufferView,
+    device: Arc<Device>,
+}
+
+impl BufferView {
+    #[profiling::function]
+    fn create(
+        device: &Arc<Device>,
+        info: BufferViewInfo,
+        buffer: &Buffer,
+    ) -> Result<Self, DriverError> {
+        debug_assert_ne!(
+            info.fmt,
+            vk::Format::UNDEFINED,
+            "Texel buffer views require a format"
+        );
+        debug_assert!(
+            buffer.info.usage.intersects(
+                vk::BufferUsageFlags::UNIFORM_TEXEL_BUFFER
+                    | vk::BufferUsageFlags::STORAGE_TEXEL_BUFFER
+            ),
+            "Texel buffer views require texel buffer usage"
+        );
+
+        let device = Arc::clone(device);
+        let create_info = vk::BufferViewCreateInfo::default()
+            .buffer(buffer.buffer)
+            .format(info.fmt)
+            .offset(info.range.start)
+            .range(info.range.end - info.range.start);
+
+        let buffer_view =
+            unsafe { device.create_buffer_view(&create_info, None) }.map_err(|err| {
+                warn!("{err}");
+
+                DriverError::Unsupported
+            })?;
+
+        Ok(Self {
+            buffer_view,
+            device,
+        })
+    }
+}
+
+impl Drop for BufferView {
+    #[profiling::function]
+    fn drop(&mut self) {
+        if panicking() {
+            return;
+        }
+
+        unsafe {
+            self.device.destroy_buffer_view(self.buffer_view, None);
+        }
+    }
+}
+
+/// Information used to reinterpret a range of an existing [`Buffer`] instance.
+///
+/// Buffers bound to uniform or storage texel buffer descriptors must be viewed using a format
+/// and must have been created with the matching texel buffer usage; all other descriptor types
+/// only use the range.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BufferViewInfo {
+    /// The format of the texel buffer elements, or `vk::Format::UNDEFINED` if the buffer is not
+    /// viewed as a texel buffer.
+    pub fmt: vk::Format,
+
+    /// The range of the buffer which is viewed.
+    pub range: BufferSubresourceRange,
+}
+
+impl BufferViewInfo {
+    /// Specifies a texel buffer view of `range` using the given format.
+    pub fn new(fmt: vk::Format, range: impl Into<BufferSubresourceRange>) -> Self {
+        Self {
+            fmt,
+            range: range.into(),
+        }
+    }
+}
+
+impl From<BufferInfo> for BufferViewInfo {
+    fn from(info: BufferInfo) -> Self {
+        BufferSubresourceRange::from(info).into()
+    }
+}
+
+impl From<BufferSubresourceRange> for BufferViewInfo {
+    fn from(range: BufferSubresourceRange) -> Self {
+        Self {
+            fmt: vk::Format::UNDEFINED,
+            range,
+        }
+    }
+}
+
+impl From<Range<vk::DeviceSize>> for BufferViewInfo {
+    fn from(range: Range<vk::DeviceSize>) -> Self {
+        BufferSubresourceRange::from(range).into()
+    }
+}
+
+impl From<BufferViewInfo> for BufferSubresourceRange {
+    fn from(info: BufferViewInfo) -> Self {
+        info.range
+    }
+}
+
 /// Specifies a range of buffer data.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct BufferSubresourceRange {
     /// The start of range.
     pub start: vk::DeviceSize,
@@ -1312,6 +1597,34 @@ mod tests {
         assert_eq!(info, builder);
     }
 
+    #[test]
+    pub fn buffer_info_memory_priority() {
+        let info = Builder::default()
+            .size(42)
+            .dedicated(true)
+            .memory_priority(1.0)
+            .build();
+
+        assert!(info.dedicated);
+        assert_eq!(info.memory_priority, Some(OrderedFloat(1.0)));
+        assert_eq!(info, info.to_builder().build());
+        assert_ne!(info, Info::device_mem(42, vk::BufferUsageFlags::empty()));
+    }
+
+    #[test]
+    pub fn buffer_info_readback_mem() {
+        let info = Info::readback_mem(42, vk::BufferUsageFlags::empty());
+        let builder = Builder::default()
+            .size(42)
+            .mappable(true)
+            .host_access(HostAccess::Read)
+            .usage(vk::BufferUsageFlags::TRANSFER_DST)
+            .build();
+
+        assert_eq!(info, builder);
+        assert_eq!(info, info.to_builder().build());
+    }
+
     #[test]
     #[should_panic(expected = "Field not initialized: size")]
     pub fn buffer_info_builder_uninit_size() {
@@ -1324,6 +1637,22 @@ mod tests {
         BufferSubresourceRange { start, end }
     }
 
+    #[test]
+    pub fn buffer_view_info() {
+        let info = BufferViewInfo::from(Info::device_mem(42, vk::BufferUsageFlags::empty()));
+
+        assert_eq!(info.fmt, vk::Format::UNDEFINED);
+        assert_eq!(info.range, buffer_subresource_range(0..42));
+
+        let info = BufferViewInfo::new(vk::Format::R32_UINT, 4..8);
+
+        assert_eq!(info.fmt, vk::Format::R32_UINT);
+        assert_eq!(
+            BufferSubresourceRange::from(info),
+            buffer_subresource_range(4..8)
+        );
+    }
+
     #[test]
     pub fn buffer_subresource_range_intersects() {
         use BufferSubresourceRange as B;
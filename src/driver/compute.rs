@@ -3,8 +3,9 @@
 use {
     super::{
         DriverError,
+        bindless::BindlessHeapInfo,
         device::Device,
-        shader::{DescriptorBindingMap, PipelineDescriptorInfo, Shader, align_spriv},
+        shader::{DescriptorBindingMap, PipelineDescriptorInfo, SamplerTable, Shader, align_spriv},
     },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
@@ -73,6 +74,23 @@ impl ComputePipeline {
         device: &Arc<Device>,
         info: impl Into<ComputePipelineInfo>,
         shader: impl Into<Shader>,
+    ) -> Result<Self, DriverError> {
+        Self::create_with_samplers(device, info, shader, &SamplerTable::default())
+    }
+
+    /// Creates a new compute pipeline on the given device using explicitly assigned samplers.
+    ///
+    /// See [`SamplerTable`] for details.
+    ///
+    /// # Panics
+    ///
+    /// If shader code is not a multiple of four bytes.
+    #[profiling::function]
+    pub fn create_with_samplers(
+        device: &Arc<Device>,
+        info: impl Into<ComputePipelineInfo>,
+        shader: impl Into<Shader>,
+        samplers: &SamplerTable,
     ) -> Result<Self, DriverError> {
         use std::slice::from_ref;
 
@@ -83,14 +101,19 @@ impl ComputePipeline {
         let shader = shader.into();
 
         // Use SPIR-V reflection to get the types and counts of all descriptors
-        let mut descriptor_bindings = shader.descriptor_bindings();
+        let mut descriptor_bindings = shader.descriptor_bindings(samplers)?;
         for (descriptor_info, _) in descriptor_bindings.values_mut() {
             if descriptor_info.binding_count() == 0 {
                 descriptor_info.set_binding_count(info.bindless_descriptor_count);
             }
         }
 
-        let descriptor_info = PipelineDescriptorInfo::create(&device, &descriptor_bindings)?;
+        let descriptor_info = PipelineDescriptorInfo::create(
+            &device,
+            &descriptor_bindings,
+            None,
+            info.bindless_heap,
+        )?;
         let descriptor_set_layouts = descriptor_info
             .layouts
             .values()
@@ -234,6 +257,20 @@ pub struct ComputePipelineInfo {
     /// ```
     #[builder(default = "8192")]
     pub bindless_descriptor_count: u32,
+
+    /// The descriptor set index and information of a [`BindlessHeap`] which is accessed by this
+    /// pipeline.
+    ///
+    /// The descriptor set layout is created from the heap information instead of shader
+    /// reflection, so any heap created using the same information may be bound to passes which use
+    /// this pipeline using [`PipelinePassRef::bind_bindless_heap`].
+    ///
+    /// The default is `None`.
+    ///
+    /// [`BindlessHeap`]: super::bindless::BindlessHeap
+    /// [`PipelinePassRef::bind_bindless_heap`]: crate::graph::pass_ref::PipelinePassRef::bind_bindless_heap
+    #[builder(default, setter(custom))]
+    pub bindless_heap: Option<(u32, BindlessHeapInfo)>,
 }
 
 impl ComputePipelineInfo {
@@ -242,6 +279,7 @@ impl ComputePipelineInfo {
     pub fn to_builder(self) -> ComputePipelineInfoBuilder {
         ComputePipelineInfoBuilder {
             bindless_descriptor_count: Some(self.bindless_descriptor_count),
+            bindless_heap: Some(self.bindless_heap),
         }
     }
 }
@@ -250,6 +288,7 @@ impl Default for ComputePipelineInfo {
     fn default() -> Self {
         Self {
             bindless_descriptor_count: 8192,
+            bindless_heap: None,
         }
     }
 }
@@ -261,6 +300,19 @@ impl From<ComputePipelineInfoBuilder> for ComputePipelineInfo {
 }
 
 impl ComputePipelineInfoBuilder {
+    /// Sets the descriptor set index of a [`BindlessHeap`] created using `info` which is accessed
+    /// by this pipeline.
+    ///
+    /// [`BindlessHeap`]: super::bindless::BindlessHeap
+    pub fn bindless_heap(
+        mut self,
+        descriptor_set_idx: u32,
+        info: impl Into<BindlessHeapInfo>,
+    ) -> Self {
+        self.bindless_heap = Some(Some((descriptor_set_idx, info.into())));
+        self
+    }
+
     /// Builds a new `ComputePipelineInfo`.
     #[inline(always)]
     pub fn build(self) -> ComputePipelineInfo {
@@ -1,18 +1,25 @@
 //! Logical device resource types
 
 use {
-    super::{DriverError, Instance, physical_device::PhysicalDevice},
+    super::{
+        DriverError, Instance,
+        buffer::Buffer,
+        image::Image,
+        physical_device::PhysicalDevice,
+        shader::{Sampler, SamplerInfo},
+    },
     ash::{ext, khr, vk},
     ash_window::enumerate_required_extensions,
     derive_builder::{Builder, UninitializedFieldError},
     gpu_allocator::{
         AllocatorDebugSettings,
-        vulkan::{Allocator, AllocatorCreateDesc},
+        vulkan::{Allocation, Allocator, AllocatorCreateDesc},
     },
     log::{error, info, trace, warn},
     raw_window_handle::HasDisplayHandle,
     std::{
-        cmp::Ordering,
+        cmp::{Ordering, Reverse},
+        collections::HashMap,
         ffi::CStr,
         fmt::{Debug, Formatter},
         iter::{empty, repeat_n},
@@ -29,6 +36,9 @@ use parking_lot::Mutex;
 #[cfg(not(feature = "parking_lot"))]
 use std::sync::Mutex;
 
+/// Function type for notification of device memory heap usage exceeding a threshold.
+pub type MemoryBudgetFn = dyn FnMut(&[MemoryHeapBudget]) + Send;
+
 /// Function type for selection of physical devices.
 pub type SelectPhysicalDeviceFn = dyn FnOnce(&[PhysicalDevice]) -> usize;
 
@@ -38,21 +48,37 @@ pub struct Device {
 
     pub(super) allocator: ManuallyDrop<Mutex<Allocator>>,
 
+    allocations: Mutex<HashMap<(vk::DeviceMemory, vk::DeviceSize), MemoryAllocationReport>>,
+
     device: ash::Device,
 
+    extended_dynamic_state_ext: Option<ext::extended_dynamic_state::Device>,
+
+    pub(super) hdr_metadata_ext: Option<ext::hdr_metadata::Device>,
+
     /// Vulkan instance pointer, which includes useful functions.
     instance: Instance,
 
+    memory_budget_callback: Mutex<Option<MemoryBudgetCallback>>,
+
+    pageable_device_local_memory_ext: Option<ext::pageable_device_local_memory::Device>,
+
     pipeline_cache: vk::PipelineCache,
 
+    pub(super) present_wait_ext: Option<khr::present_wait::Device>,
+
     /// The physical device, which contains useful data about features, properties, and limits.
     pub physical_device: PhysicalDevice,
 
+    push_descriptor_ext: Option<khr::push_descriptor::Device>,
+
     /// The physical execution queues which all work will be submitted to.
     pub(crate) queues: Vec<Vec<vk::Queue>>,
 
     pub(crate) ray_trace_ext: Option<khr::ray_tracing_pipeline::Device>,
 
+    samplers: Mutex<HashMap<SamplerInfo, vk::Sampler>>,
+
     pub(super) surface_ext: Option<khr::surface::Instance>,
     pub(super) swapchain_ext: Option<khr::swapchain::Device>,
 }
@@ -75,10 +101,19 @@ impl Device {
     where
         F: FnOnce(vk::DeviceCreateInfo) -> ash::prelude::VkResult<ash::Device>,
     {
-        let mut enabled_ext_names = Vec::with_capacity(6);
+        let mut enabled_ext_names = Vec::with_capacity(12);
 
         if display_window {
             enabled_ext_names.push(khr::swapchain::NAME.as_ptr());
+
+            if physical_device.supports_hdr_metadata {
+                enabled_ext_names.push(ext::hdr_metadata::NAME.as_ptr());
+            }
+
+            if physical_device.supports_present_wait {
+                enabled_ext_names.push(khr::present_id::NAME.as_ptr());
+                enabled_ext_names.push(khr::present_wait::NAME.as_ptr());
+            }
         }
 
         if physical_device.accel_struct_properties.is_some() {
@@ -86,6 +121,10 @@ impl Device {
             enabled_ext_names.push(khr::deferred_host_operations::NAME.as_ptr());
         }
 
+        if physical_device.push_descriptor_properties.is_some() {
+            enabled_ext_names.push(khr::push_descriptor::NAME.as_ptr());
+        }
+
         if physical_device.ray_query_features.ray_query {
             enabled_ext_names.push(khr::ray_query::NAME.as_ptr());
         }
@@ -94,10 +133,39 @@ impl Device {
             enabled_ext_names.push(khr::ray_tracing_pipeline::NAME.as_ptr());
         }
 
+        if physical_device
+            .conservative_rasterization_properties
+            .is_some()
+        {
+            enabled_ext_names.push(ext::conservative_rasterization::NAME.as_ptr());
+        }
+
+        if physical_device
+            .extended_dynamic_state_features
+            .extended_dynamic_state
+        {
+            enabled_ext_names.push(ext::extended_dynamic_state::NAME.as_ptr());
+        }
+
         if physical_device.index_type_uint8_features.index_type_uint8 {
             enabled_ext_names.push(ext::index_type_uint8::NAME.as_ptr());
         }
 
+        if physical_device.supports_memory_budget {
+            enabled_ext_names.push(ext::memory_budget::NAME.as_ptr());
+        }
+
+        if physical_device.memory_priority_features.memory_priority {
+            enabled_ext_names.push(ext::memory_priority::NAME.as_ptr());
+        }
+
+        if physical_device
+            .memory_priority_features
+            .pageable_device_local_memory
+        {
+            enabled_ext_names.push(ext::pageable_device_local_memory::NAME.as_ptr());
+        }
+
         let priorities = repeat_n(
             1.0,
             physical_device
@@ -131,7 +199,14 @@ impl Device {
         let mut features_v1_2 = vk::PhysicalDeviceVulkan12Features::default();
         let mut acceleration_structure_features =
             vk::PhysicalDeviceAccelerationStructureFeaturesKHR::default();
+        let mut extended_dynamic_state_features =
+            vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT::default();
         let mut index_type_uint8_features = vk::PhysicalDeviceIndexTypeUint8FeaturesEXT::default();
+        let mut memory_priority_features = vk::PhysicalDeviceMemoryPriorityFeaturesEXT::default();
+        let mut pageable_device_local_memory_features =
+            vk::PhysicalDevicePageableDeviceLocalMemoryFeaturesEXT::default();
+        let mut present_id_features = vk::PhysicalDevicePresentIdFeaturesKHR::default();
+        let mut present_wait_features = vk::PhysicalDevicePresentWaitFeaturesKHR::default();
         let mut ray_query_features = vk::PhysicalDeviceRayQueryFeaturesKHR::default();
         let mut ray_trace_features = vk::PhysicalDeviceRayTracingPipelineFeaturesKHR::default();
         let mut features = vk::PhysicalDeviceFeatures2::default()
@@ -150,10 +225,34 @@ impl Device {
             features = features.push_next(&mut ray_trace_features);
         }
 
+        if physical_device
+            .extended_dynamic_state_features
+            .extended_dynamic_state
+        {
+            features = features.push_next(&mut extended_dynamic_state_features);
+        }
+
         if physical_device.index_type_uint8_features.index_type_uint8 {
             features = features.push_next(&mut index_type_uint8_features);
         }
 
+        if physical_device.memory_priority_features.memory_priority {
+            features = features.push_next(&mut memory_priority_features);
+        }
+
+        if physical_device
+            .memory_priority_features
+            .pageable_device_local_memory
+        {
+            features = features.push_next(&mut pageable_device_local_memory_features);
+        }
+
+        if display_window && physical_device.supports_present_wait {
+            features = features
+                .push_next(&mut present_id_features)
+                .push_next(&mut present_wait_features);
+        }
+
         unsafe { get_physical_device_features2(**physical_device, &mut features) };
 
         let device_create_info = vk::DeviceCreateInfo::default()
@@ -249,6 +348,26 @@ impl Device {
         Self::create(instance, select_physical_device, true)
     }
 
+    /// Constructs a new device which supports presentation to headless surfaces.
+    ///
+    /// Requires `VK_EXT_headless_surface` support, which is provided by software implementations
+    /// such as lavapipe. Surfaces may then be created without a window using
+    /// [`Surface::create_headless`](super::surface::Surface::create_headless), which allows
+    /// swapchain and display logic to run on machines without a windowing system.
+    #[profiling::function]
+    pub fn create_headless_display(info: impl Into<DeviceInfo>) -> Result<Self, DriverError> {
+        let DeviceInfo {
+            debug,
+            select_physical_device,
+        } = info.into();
+        let instance = Instance::create(
+            debug,
+            [khr::surface::NAME, ext::headless_surface::NAME].into_iter(),
+        )?;
+
+        Self::create(instance, select_physical_device, true)
+    }
+
     pub(crate) fn create_fence(this: &Self, signaled: bool) -> Result<vk::Fence, DriverError> {
         let mut flags = vk::FenceCreateFlags::empty();
 
@@ -277,6 +396,51 @@ impl Device {
         })
     }
 
+    /// Calls the memory budget callback, if any, when heap usage first exceeds the threshold.
+    #[profiling::function]
+    fn check_memory_budget(this: &Self) {
+        if !this.physical_device.supports_memory_budget {
+            return;
+        }
+
+        let callback = {
+            #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+            let mut memory_budget_callback = this.memory_budget_callback.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let mut memory_budget_callback = memory_budget_callback.unwrap();
+
+            // The callback is taken so that it may create or drop resources without deadlock
+            memory_budget_callback.take()
+        };
+        let Some(mut callback) = callback else {
+            return;
+        };
+
+        if let Ok(heaps) = Self::memory_budget(this) {
+            let exceeded = heaps
+                .iter()
+                .any(|heap| heap.usage as f64 > heap.budget as f64 * callback.threshold as f64);
+
+            if exceeded && !callback.exceeded {
+                (callback.callback)(&heaps);
+            }
+
+            callback.exceeded = exceeded;
+        }
+
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut memory_budget_callback = this.memory_budget_callback.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut memory_budget_callback = memory_budget_callback.unwrap();
+
+        // The callback may have been replaced or removed while we were not holding the lock
+        if memory_budget_callback.is_none() {
+            *memory_budget_callback = Some(callback);
+        }
+    }
+
     /// Helper for times when you already know that the device supports the acceleration
     /// structure extension.
     ///
@@ -289,6 +453,32 @@ impl Device {
             .expect("VK_KHR_acceleration_structure")
     }
 
+    /// Helper for times when you already know that the device supports the extended dynamic state
+    /// extension.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [Self.physical_device.extended_dynamic_state_features] is not enabled.
+    pub(crate) fn expect_extended_dynamic_state_ext(
+        this: &Self,
+    ) -> &ext::extended_dynamic_state::Device {
+        this.extended_dynamic_state_ext
+            .as_ref()
+            .expect("VK_EXT_extended_dynamic_state")
+    }
+
+    /// Helper for times when you already know that the device supports the push descriptor
+    /// extension.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [Self.physical_device.push_descriptor_properties] is `None`.
+    pub(crate) fn expect_push_descriptor_ext(this: &Self) -> &khr::push_descriptor::Device {
+        this.push_descriptor_ext
+            .as_ref()
+            .expect("VK_KHR_push_descriptor")
+    }
+
     /// Helper for times when you already know that the instance supports the surface extension.
     ///
     /// # Panics
@@ -351,6 +541,10 @@ impl Device {
         let surface_ext = display_window
             .then(|| khr::surface::Instance::new(Instance::entry(&instance), &instance));
         let swapchain_ext = display_window.then(|| khr::swapchain::Device::new(&instance, &device));
+        let hdr_metadata_ext = (display_window && physical_device.supports_hdr_metadata)
+            .then(|| ext::hdr_metadata::Device::new(&instance, &device));
+        let present_wait_ext = (display_window && physical_device.supports_present_wait)
+            .then(|| khr::present_wait::Device::new(&instance, &device));
         let accel_struct_ext = physical_device
             .accel_struct_properties
             .is_some()
@@ -359,6 +553,18 @@ impl Device {
             .ray_trace_features
             .ray_tracing_pipeline
             .then(|| khr::ray_tracing_pipeline::Device::new(&instance, &device));
+        let push_descriptor_ext = physical_device
+            .push_descriptor_properties
+            .is_some()
+            .then(|| khr::push_descriptor::Device::new(&instance, &device));
+        let extended_dynamic_state_ext = physical_device
+            .extended_dynamic_state_features
+            .extended_dynamic_state
+            .then(|| ext::extended_dynamic_state::Device::new(&instance, &device));
+        let pageable_device_local_memory_ext = physical_device
+            .memory_priority_features
+            .pageable_device_local_memory
+            .then(|| ext::pageable_device_local_memory::Device::new(&instance, &device));
 
         let pipeline_cache =
             unsafe { device.create_pipeline_cache(&vk::PipelineCacheCreateInfo::default(), None) }
@@ -371,12 +577,20 @@ impl Device {
         Ok(Self {
             accel_struct_ext,
             allocator: ManuallyDrop::new(Mutex::new(allocator)),
+            allocations: Default::default(),
             device,
+            extended_dynamic_state_ext,
+            hdr_metadata_ext,
             instance,
+            memory_budget_callback: Default::default(),
+            pageable_device_local_memory_ext,
             pipeline_cache,
             physical_device,
+            present_wait_ext,
+            push_descriptor_ext,
             queues,
             ray_trace_ext,
+            samplers: Default::default(),
             surface_ext,
             swapchain_ext,
         })
@@ -430,10 +644,218 @@ impl Device {
         &this.instance
     }
 
+    /// Returns the current budget and usage of each device memory heap.
+    ///
+    /// The budget is an estimate of how much memory this process can use before allocations may
+    /// fail or cause performance degradation, and it may change over time as other applications
+    /// use memory.
+    ///
+    /// Returns [`DriverError::Unsupported`] if the device does not support `VK_EXT_memory_budget`.
+    #[profiling::function]
+    pub fn memory_budget(this: &Self) -> Result<Vec<MemoryHeapBudget>, DriverError> {
+        if !this.physical_device.supports_memory_budget {
+            warn!("unsupported memory budget");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        let mut budget_properties = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let mut properties =
+            vk::PhysicalDeviceMemoryProperties2::default().push_next(&mut budget_properties);
+
+        unsafe {
+            this.instance
+                .get_physical_device_memory_properties2(*this.physical_device, &mut properties);
+        }
+
+        let memory_properties = properties.memory_properties;
+
+        Ok(memory_properties
+            .memory_heaps_as_slice()
+            .iter()
+            .enumerate()
+            .map(|(idx, heap)| MemoryHeapBudget {
+                budget: budget_properties.heap_budget[idx],
+                flags: heap.flags,
+                size: heap.size,
+                usage: budget_properties.heap_usage[idx],
+            })
+            .collect())
+    }
+
+    /// Returns a report of the memory allocations and memory blocks owned by this device.
+    ///
+    /// Allocations are named `buffer` or `image` by default. Use
+    /// [`MemoryReport::name_buffers`] and [`MemoryReport::name_images`] to read the names of
+    /// resources when the report is built.
+    #[profiling::function]
+    pub fn memory_report(this: &Self) -> MemoryReport {
+        let report = {
+            let allocator = this.allocator.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let allocator = allocator.unwrap();
+
+            allocator.generate_report()
+        };
+
+        let mut allocations = {
+            let allocations = this.allocations.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let allocations = allocations.unwrap();
+
+            allocations.values().cloned().collect::<Vec<_>>()
+        };
+        allocations.sort_unstable_by_key(|allocation| Reverse(allocation.size));
+
+        let blocks = report
+            .blocks
+            .iter()
+            .map(|block| MemoryBlockReport {
+                allocated_bytes: report.allocations[block.allocations.clone()]
+                    .iter()
+                    .map(|allocation| allocation.size)
+                    .sum(),
+                allocation_count: block.allocations.len(),
+                size: block.size,
+            })
+            .collect();
+
+        MemoryReport {
+            allocations,
+            blocks,
+            total_allocated_bytes: report.total_allocated_bytes,
+            total_reserved_bytes: report.total_reserved_bytes,
+        }
+    }
+
     pub(crate) fn pipeline_cache(this: &Self) -> vk::PipelineCache {
         this.pipeline_cache
     }
 
+    /// Removes the function set using [`Device::set_memory_budget_callback`].
+    pub fn remove_memory_budget_callback(this: &Self) {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut memory_budget_callback = this.memory_budget_callback.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut memory_budget_callback = memory_budget_callback.unwrap();
+
+        *memory_budget_callback = None;
+    }
+
+    /// Returns a sampler which is owned by this device, creating it if required.
+    ///
+    /// Used for mutable samplers which are bound to descriptors while resolving a render graph.
+    #[profiling::function]
+    pub(crate) fn sampler(this: &Self, info: SamplerInfo) -> Result<vk::Sampler, DriverError> {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut samplers = this.samplers.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut samplers = samplers.unwrap();
+
+        if let Some(&sampler) = samplers.get(&info) {
+            return Ok(sampler);
+        }
+
+        let sampler = Sampler::create_handle(this, info)?;
+        samplers.insert(info, sampler);
+
+        Ok(sampler)
+    }
+
+    /// Sets a function which is called when the usage of any device memory heap exceeds the given
+    /// fraction of its budget.
+    ///
+    /// Usage is checked each time a resource is created and the function is called from the thread
+    /// which creates the resource causing usage to exceed the threshold. It is not called again
+    /// until a resource is created while the usage of all heaps is below the threshold. Requires `VK_EXT_memory_budget` support, see [`Device::memory_budget`].
+    ///
+    /// Any previously set function is replaced.
+    pub fn set_memory_budget_callback(
+        this: &Self,
+        threshold: f32,
+        callback: impl FnMut(&[MemoryHeapBudget]) + Send + 'static,
+    ) {
+        debug_assert!(threshold > 0.0, "Threshold must be positive");
+
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut memory_budget_callback = this.memory_budget_callback.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut memory_budget_callback = memory_budget_callback.unwrap();
+
+        *memory_budget_callback = Some(MemoryBudgetCallback {
+            callback: Box::new(callback),
+            exceeded: false,
+            threshold,
+        });
+    }
+
+    /// Sets the priority of a dedicated device memory allocation.
+    ///
+    /// Must only be called when [`Self::supports_memory_priority`] returns `true`.
+    pub(crate) fn set_memory_priority(this: &Self, memory: vk::DeviceMemory, priority: f32) {
+        debug_assert!(
+            (0.0..=1.0).contains(&priority),
+            "Memory priority must be between 0.0 and 1.0"
+        );
+
+        let ext = this
+            .pageable_device_local_memory_ext
+            .as_ref()
+            .expect("VK_EXT_pageable_device_local_memory");
+
+        unsafe {
+            (ext.fp().set_device_memory_priority_ext)(ext.device(), memory, priority);
+        }
+    }
+
+    /// Returns `true` if the priority of device memory allocations may be set.
+    ///
+    /// Allocation priorities are set using `vkSetDeviceMemoryPriorityEXT`, which requires
+    /// `VK_EXT_pageable_device_local_memory`.
+    pub(crate) fn supports_memory_priority(this: &Self) -> bool {
+        this.pageable_device_local_memory_ext.is_some()
+    }
+
+    /// Adds a memory allocation to memory reports and checks the memory budget.
+    pub(crate) fn track_allocation(this: &Self, allocation: &Allocation, name: &str) {
+        {
+            #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+            let mut allocations = this.allocations.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let mut allocations = allocations.unwrap();
+
+            allocations.insert(
+                allocation_key(allocation),
+                MemoryAllocationReport {
+                    allocation: allocation_key(allocation),
+                    dedicated: allocation.is_dedicated(),
+                    memory_properties: allocation.memory_properties(),
+                    name: name.to_owned(),
+                    size: allocation.size(),
+                },
+            );
+        }
+
+        Self::check_memory_budget(this);
+    }
+
+    /// Removes a memory allocation from memory reports.
+    pub(crate) fn untrack_allocation(this: &Self, allocation: &Allocation) {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut allocations = this.allocations.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut allocations = allocations.unwrap();
+
+        allocations.remove(&allocation_key(allocation));
+    }
+
     #[profiling::function]
     pub(crate) fn wait_for_fence(this: &Self, fence: &vk::Fence) -> Result<(), DriverError> {
         use std::slice::from_ref;
@@ -514,6 +936,16 @@ impl Drop for Device {
         }
 
         unsafe {
+            #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+            let mut samplers = self.samplers.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let mut samplers = samplers.unwrap();
+
+            for (_, sampler) in samplers.drain() {
+                self.device.destroy_sampler(sampler, None);
+            }
+
             self.device
                 .destroy_pipeline_cache(self.pipeline_cache, None);
 
@@ -526,6 +958,128 @@ impl Drop for Device {
     }
 }
 
+/// Returns the key which identifies an allocation in memory reports.
+pub(super) fn allocation_key(allocation: &Allocation) -> (vk::DeviceMemory, vk::DeviceSize) {
+    (unsafe { allocation.memory() }, allocation.offset())
+}
+
+/// Describes a memory allocation of a resource in a [`MemoryReport`].
+#[derive(Clone, Debug)]
+pub struct MemoryAllocationReport {
+    allocation: (vk::DeviceMemory, vk::DeviceSize),
+
+    /// `true` if the allocation uses device memory which is not shared with other resources.
+    pub dedicated: bool,
+
+    /// The properties of the memory type used by the allocation.
+    pub memory_properties: vk::MemoryPropertyFlags,
+
+    /// The name of the resource which owns the allocation.
+    pub name: String,
+
+    /// The size in bytes of the allocation.
+    pub size: vk::DeviceSize,
+}
+
+/// Describes a block of device memory in a [`MemoryReport`].
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryBlockReport {
+    /// The sum of the sizes of all allocations in this block, in bytes.
+    pub allocated_bytes: vk::DeviceSize,
+
+    /// The number of allocations in this block.
+    pub allocation_count: usize,
+
+    /// The size in bytes of this block.
+    pub size: vk::DeviceSize,
+}
+
+struct MemoryBudgetCallback {
+    callback: Box<MemoryBudgetFn>,
+    exceeded: bool,
+    threshold: f32,
+}
+
+/// Describes the budget and usage of a device memory heap.
+///
+/// See
+/// [`VkPhysicalDeviceMemoryBudgetPropertiesEXT`](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkPhysicalDeviceMemoryBudgetPropertiesEXT.html)
+/// manual page.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryHeapBudget {
+    /// An estimate of how much memory, in bytes, this process can use from this heap.
+    pub budget: vk::DeviceSize,
+
+    /// Flags describing the heap, such as [`vk::MemoryHeapFlags::DEVICE_LOCAL`].
+    pub flags: vk::MemoryHeapFlags,
+
+    /// The total size in bytes of this heap.
+    pub size: vk::DeviceSize,
+
+    /// An estimate of how much memory, in bytes, this process currently uses from this heap.
+    pub usage: vk::DeviceSize,
+}
+
+/// Describes the memory allocations and memory blocks owned by a [`Device`].
+///
+/// See [`Device::memory_report`].
+#[derive(Clone, Debug)]
+pub struct MemoryReport {
+    /// All live resource allocations, sorted by size in descending order.
+    pub allocations: Vec<MemoryAllocationReport>,
+
+    /// All blocks of device memory, which contain one or more allocations.
+    pub blocks: Vec<MemoryBlockReport>,
+
+    /// Sum of the memory used by all allocations, in bytes.
+    pub total_allocated_bytes: vk::DeviceSize,
+
+    /// Sum of the memory reserved by all memory blocks including unallocated regions, in bytes.
+    pub total_reserved_bytes: vk::DeviceSize,
+}
+
+impl MemoryReport {
+    fn name_allocations<'a>(
+        &mut self,
+        names: impl Iterator<Item = ((vk::DeviceMemory, vk::DeviceSize), &'a str)>,
+    ) {
+        let names = names.collect::<HashMap<_, _>>();
+
+        for allocation in &mut self.allocations {
+            if let Some(name) = names.get(&allocation.allocation) {
+                allocation.name = (*name).to_owned();
+            }
+        }
+    }
+
+    /// Names the allocations of the given buffers using the current value of each
+    /// [`Buffer::name`](super::buffer::Buffer::name) field.
+    ///
+    /// Buffers without a name are ignored.
+    #[profiling::function]
+    pub fn name_buffers<'a>(&mut self, buffers: impl IntoIterator<Item = &'a Buffer>) {
+        self.name_allocations(buffers.into_iter().filter_map(|buffer| {
+            buffer
+                .name
+                .as_deref()
+                .map(|name| (Buffer::allocation_key(buffer), name))
+        }));
+    }
+
+    /// Names the allocations of the given images using the current value of each
+    /// [`Image::name`](super::image::Image::name) field.
+    ///
+    /// Images without a name or without an allocation, such as swapchain images, are ignored.
+    #[profiling::function]
+    pub fn name_images<'a>(&mut self, images: impl IntoIterator<Item = &'a Image>) {
+        self.name_allocations(
+            images
+                .into_iter()
+                .filter_map(|image| Image::allocation_key(image).zip(image.name.as_deref())),
+        );
+    }
+}
+
 /// Information used to create a [`Device`] instance.
 #[derive(Builder)]
 #[builder(
@@ -722,4 +1276,39 @@ mod tests {
     pub fn device_info_builder() {
         Builder::default().build();
     }
+
+    #[test]
+    pub fn memory_report_name_allocations() {
+        use ash::vk::Handle;
+
+        let allocation = |memory, offset| MemoryAllocationReport {
+            allocation: (vk::DeviceMemory::from_raw(memory), offset),
+            dedicated: false,
+            memory_properties: vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            name: "buffer".to_owned(),
+            size: 256,
+        };
+        let mut report = MemoryReport {
+            allocations: vec![allocation(1, 0), allocation(1, 256), allocation(2, 0)],
+            blocks: vec![],
+            total_allocated_bytes: 768,
+            total_reserved_bytes: 768,
+        };
+
+        report.name_allocations(
+            [
+                ((vk::DeviceMemory::from_raw(1), 256), "vertices"),
+                ((vk::DeviceMemory::from_raw(3), 0), "unknown"),
+            ]
+            .into_iter(),
+        );
+
+        let names = report
+            .allocations
+            .iter()
+            .map(|allocation| allocation.name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, ["buffer", "vertices", "buffer"]);
+    }
 }
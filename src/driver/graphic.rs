@@ -3,11 +3,13 @@
 use {
     super::{
         DriverError,
+        bindless::BindlessHeapInfo,
         device::Device,
         image::SampleCount,
         merge_push_constant_ranges,
         shader::{
-            DescriptorBindingMap, PipelineDescriptorInfo, Shader, SpecializationInfo, align_spriv,
+            DescriptorBindingMap, PipelineDescriptorInfo, SamplerTable, Shader, SpecializationInfo,
+            align_spriv,
         },
     },
     ash::vk,
@@ -17,6 +19,8 @@ use {
     std::{collections::HashSet, ffi::CString, sync::Arc, thread::panicking},
 };
 
+const MAX_BLEND_ATTACHMENTS: usize = 8;
+
 const RGBA_COLOR_COMPONENTS: vk::ColorComponentFlags = vk::ColorComponentFlags::from_raw(
     vk::ColorComponentFlags::R.as_raw()
         | vk::ColorComponentFlags::G.as_raw()
@@ -162,6 +166,37 @@ impl From<UninitializedFieldError> for BlendModeBuilderError {
     }
 }
 
+/// Specifies the [depth bias] applied to fragment depth values during rasterization.
+///
+/// Depth bias is commonly used to reduce self-shadowing artifacts ("shadow acne") when rendering
+/// shadow maps.
+///
+/// [depth bias]: https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#primsrast-depthbias
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DepthBias {
+    /// A scalar factor controlling the constant depth value added to each fragment.
+    pub constant_factor: OrderedFloat<f32>,
+
+    /// The maximum (or minimum) depth bias of a fragment.
+    ///
+    /// Values other than zero require the `depth_bias_clamp` device feature.
+    pub clamp: OrderedFloat<f32>,
+
+    /// A scalar factor applied to a fragment’s slope in depth bias calculations.
+    pub slope_factor: OrderedFloat<f32>,
+}
+
+impl DepthBias {
+    /// Specifies a depth bias with the given constant factor, clamp, and slope factor.
+    pub const fn new(constant_factor: f32, clamp: f32, slope_factor: f32) -> Self {
+        Self {
+            constant_factor: OrderedFloat(constant_factor),
+            clamp: OrderedFloat(clamp),
+            slope_factor: OrderedFloat(slope_factor),
+        }
+    }
+}
+
 /// Specifies the [depth bounds tests], [stencil test], and [depth test] pipeline state.
 ///
 /// [depth bounds tests]: https://registry.khronos.org/vulkan/specs/1.3-extensions/html/vkspec.html#fragops-dbt
@@ -344,6 +379,113 @@ impl From<UninitializedFieldError> for DepthStencilModeBuilderError {
     }
 }
 
+/// Specifies which pipeline states are set dynamically while recording commands instead of being
+/// baked into the pipeline.
+///
+/// Each enabled state must be set using the matching function of [`Draw`] before drawing, for
+/// example [`Draw::set_cull_mode`]. Dynamic states allow a single [`GraphicPipeline`] to be used
+/// with many different settings without compiling additional pipelines.
+///
+/// Requires the `VK_EXT_extended_dynamic_state` device extension, see
+/// [`PhysicalDevice::extended_dynamic_state_features`].
+///
+/// See
+/// [VK_EXT_extended_dynamic_state](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VK_EXT_extended_dynamic_state.html).
+///
+/// [`Draw`]: crate::graph::pass_ref::Draw
+/// [`Draw::set_cull_mode`]: crate::graph::pass_ref::Draw::set_cull_mode
+/// [`PhysicalDevice::extended_dynamic_state_features`]: super::physical_device::PhysicalDevice::extended_dynamic_state_features
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ExtendedDynamicState {
+    /// Cull mode is set using `Draw::set_cull_mode`.
+    pub cull_mode: bool,
+
+    /// Depth bounds test enable is set using `Draw::set_depth_bounds_test`.
+    pub depth_bounds_test: bool,
+
+    /// Depth compare op is set using `Draw::set_depth_compare_op`.
+    pub depth_compare_op: bool,
+
+    /// Depth test enable is set using `Draw::set_depth_test`.
+    pub depth_test: bool,
+
+    /// Depth write enable is set using `Draw::set_depth_write`.
+    pub depth_write: bool,
+
+    /// Front face is set using `Draw::set_front_face`.
+    pub front_face: bool,
+
+    /// Primitive topology is set using `Draw::set_primitive_topology`.
+    ///
+    /// The topology must be of the same class (point, line, or triangle) as
+    /// [`GraphicPipelineInfo::topology`].
+    pub primitive_topology: bool,
+
+    /// Stencil ops are set using `Draw::set_stencil_op`.
+    pub stencil_op: bool,
+
+    /// Stencil test enable is set using `Draw::set_stencil_test`.
+    pub stencil_test: bool,
+}
+
+impl ExtendedDynamicState {
+    /// Specifies that all extended dynamic states are set while recording commands.
+    pub const ALL: Self = Self {
+        cull_mode: true,
+        depth_bounds_test: true,
+        depth_compare_op: true,
+        depth_test: true,
+        depth_write: true,
+        front_face: true,
+        primitive_topology: true,
+        stencil_op: true,
+        stencil_test: true,
+    };
+
+    /// Specifies that no extended dynamic states are used.
+    pub const NONE: Self = Self {
+        cull_mode: false,
+        depth_bounds_test: false,
+        depth_compare_op: false,
+        depth_test: false,
+        depth_write: false,
+        front_face: false,
+        primitive_topology: false,
+        stencil_op: false,
+        stencil_test: false,
+    };
+
+    /// Returns `true` if any extended dynamic state is used.
+    pub fn is_enabled(self) -> bool {
+        self != Self::NONE
+    }
+
+    pub(super) fn dynamic_states(self) -> impl Iterator<Item = vk::DynamicState> {
+        [
+            (self.cull_mode, vk::DynamicState::CULL_MODE_EXT),
+            (
+                self.depth_bounds_test,
+                vk::DynamicState::DEPTH_BOUNDS_TEST_ENABLE_EXT,
+            ),
+            (
+                self.depth_compare_op,
+                vk::DynamicState::DEPTH_COMPARE_OP_EXT,
+            ),
+            (self.depth_test, vk::DynamicState::DEPTH_TEST_ENABLE_EXT),
+            (self.depth_write, vk::DynamicState::DEPTH_WRITE_ENABLE_EXT),
+            (self.front_face, vk::DynamicState::FRONT_FACE_EXT),
+            (
+                self.primitive_topology,
+                vk::DynamicState::PRIMITIVE_TOPOLOGY_EXT,
+            ),
+            (self.stencil_op, vk::DynamicState::STENCIL_OP_EXT),
+            (self.stencil_test, vk::DynamicState::STENCIL_TEST_ENABLE_EXT),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, state)| enabled.then_some(state))
+    }
+}
+
 /// Opaque representation of a [pipeline] object.
 ///
 /// Also contains information about the object.
@@ -409,6 +551,26 @@ impl GraphicPipeline {
         info: impl Into<GraphicPipelineInfo>,
         shaders: impl IntoIterator<Item = S>,
     ) -> Result<Self, DriverError>
+    where
+        S: Into<Shader>,
+    {
+        Self::create_with_samplers(device, info, shaders, &SamplerTable::default())
+    }
+
+    /// Creates a new graphic pipeline on the given device using explicitly assigned samplers.
+    ///
+    /// See [`SamplerTable`] for details.
+    ///
+    /// # Panics
+    ///
+    /// If shader code is not a multiple of four bytes.
+    #[profiling::function]
+    pub fn create_with_samplers<S>(
+        device: &Arc<Device>,
+        info: impl Into<GraphicPipelineInfo>,
+        shaders: impl IntoIterator<Item = S>,
+        samplers: &SamplerTable,
+    ) -> Result<Self, DriverError>
     where
         S: Into<Shader>,
     {
@@ -449,8 +611,74 @@ impl GraphicPipeline {
             "invalid shader stage combination"
         );
 
+        if info.conservative_rasterization.is_some()
+            && device
+                .physical_device
+                .conservative_rasterization_properties
+                .is_none()
+        {
+            warn!("unsupported conservative rasterization extension");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if info.depth_clamp && !device.physical_device.features_v1_0.depth_clamp {
+            warn!("unsupported depth clamp feature");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if info
+            .depth_bias
+            .is_some_and(|depth_bias| depth_bias.clamp.0 != 0.0)
+            && !device.physical_device.features_v1_0.depth_bias_clamp
+        {
+            warn!("unsupported depth bias clamp feature");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if info.extended_dynamic_state.is_enabled()
+            && !device
+                .physical_device
+                .extended_dynamic_state_features
+                .extended_dynamic_state
+        {
+            warn!("unsupported extended dynamic state extension");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if info.line_width.0 != 1.0 && !device.physical_device.features_v1_0.wide_lines {
+            warn!("unsupported wide lines feature");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if info.logic_op.is_some() && !device.physical_device.features_v1_0.logic_op {
+            warn!("unsupported logic op feature");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        if !device.physical_device.features_v1_0.independent_blend {
+            let color_attachments = shaders
+                .iter()
+                .filter(|shader| shader.stage == vk::ShaderStageFlags::FRAGMENT)
+                .flat_map(|shader| shader.attachments().1);
+
+            if info.has_independent_blend(color_attachments) {
+                warn!("unsupported independent blend feature");
+
+                return Err(DriverError::Unsupported);
+            }
+        }
+
         let mut descriptor_bindings = Shader::merge_descriptor_bindings(
-            shaders.iter().map(|shader| shader.descriptor_bindings()),
+            shaders
+                .iter()
+                .map(|shader| shader.descriptor_bindings(samplers))
+                .collect::<Result<Vec<_>, _>>()?,
         );
         for (descriptor_info, _) in descriptor_bindings.values_mut() {
             if descriptor_info.binding_count() == 0 {
@@ -458,7 +686,43 @@ impl GraphicPipeline {
             }
         }
 
-        let descriptor_info = PipelineDescriptorInfo::create(&device, &descriptor_bindings)?;
+        if let Some(push_descriptor_set) = info.push_descriptor_set {
+            let Some(push_descriptor_properties) =
+                device.physical_device.push_descriptor_properties.as_ref()
+            else {
+                warn!("unsupported push descriptor extension");
+
+                return Err(DriverError::Unsupported);
+            };
+
+            let last_descriptor_set = descriptor_bindings
+                .keys()
+                .map(|descriptor| descriptor.set)
+                .max();
+            if last_descriptor_set != Some(push_descriptor_set) {
+                warn!("push descriptor set {push_descriptor_set} must be the last descriptor set");
+
+                return Err(DriverError::InvalidData);
+            }
+
+            let push_descriptor_count = descriptor_bindings
+                .iter()
+                .filter(|(descriptor, _)| descriptor.set == push_descriptor_set)
+                .map(|(_, (descriptor_info, _))| descriptor_info.binding_count())
+                .sum::<u32>();
+            if push_descriptor_count > push_descriptor_properties.max_push_descriptors {
+                warn!("unsupported push descriptor count {push_descriptor_count}");
+
+                return Err(DriverError::Unsupported);
+            }
+        }
+
+        let descriptor_info = PipelineDescriptorInfo::create(
+            &device,
+            &descriptor_bindings,
+            info.push_descriptor_set,
+            info.bindless_heap,
+        )?;
         let descriptor_sets_layouts = descriptor_info
             .layouts
             .values()
@@ -666,6 +930,20 @@ pub struct GraphicPipelineInfo {
     #[builder(default = "8192")]
     pub bindless_descriptor_count: u32,
 
+    /// The descriptor set index and information of a [`BindlessHeap`] which is accessed by this
+    /// pipeline.
+    ///
+    /// The descriptor set layout is created from the heap information instead of shader
+    /// reflection, so any heap created using the same information may be bound to passes which use
+    /// this pipeline using [`PipelinePassRef::bind_bindless_heap`].
+    ///
+    /// The default is `None`.
+    ///
+    /// [`BindlessHeap`]: super::bindless::BindlessHeap
+    /// [`PipelinePassRef::bind_bindless_heap`]: crate::graph::pass_ref::PipelinePassRef::bind_bindless_heap
+    #[builder(default, setter(custom))]
+    pub bindless_heap: Option<(u32, BindlessHeapInfo)>,
+
     /// Specifies color blend state used when rasterization is enabled for any color attachments
     /// accessed during rendering.
     ///
@@ -673,18 +951,89 @@ pub struct GraphicPipelineInfo {
     #[builder(default)]
     pub blend: BlendMode,
 
+    /// Per-attachment color blend state which overrides [`Self::blend`] for the given color
+    /// attachment indices.
+    ///
+    /// Use [`GraphicPipelineInfoBuilder::attachment_blend`] to set these values and
+    /// [`Self::attachment_blend`] to read the effective blend state of an attachment.
+    ///
+    /// Differing effective blend states of the color attachments written by the fragment shader
+    /// require the `independent_blend` device feature.
+    #[builder(default, setter(custom))]
+    pub blend_attachments: [Option<BlendMode>; MAX_BLEND_ATTACHMENTS],
+
+    /// Constant RGBA color used by blend factors such as `vk::BlendFactor::CONSTANT_COLOR`.
+    ///
+    /// The default value is `[0.0, 0.0, 0.0, 0.0]`.
+    #[builder(default, setter(custom))]
+    pub blend_constants: [OrderedFloat<f32>; 4],
+
+    /// Enables conservative rasterization using the given mode.
+    ///
+    /// Requires the `VK_EXT_conservative_rasterization` device extension, see
+    /// [`PhysicalDevice::conservative_rasterization_properties`].
+    ///
+    /// [`PhysicalDevice::conservative_rasterization_properties`]: super::physical_device::PhysicalDevice::conservative_rasterization_properties
+    #[builder(default, setter(strip_option))]
+    pub conservative_rasterization: Option<vk::ConservativeRasterizationModeEXT>,
+
     /// Bitmask controlling triangle culling.
     ///
     /// The default value is `vk::CullModeFlags::BACK`.
     #[builder(default = "vk::CullModeFlags::BACK")]
     pub cull_mode: vk::CullModeFlags,
 
+    /// Static depth bias applied to fragment depth values.
+    ///
+    /// Has no effect when [`Self::dynamic_depth_bias`] is set.
+    #[builder(default, setter(strip_option))]
+    pub depth_bias: Option<DepthBias>,
+
+    /// Controls whether fragment depth values are clamped to the viewport depth range instead of
+    /// clipping primitives.
+    ///
+    /// Requires the `depth_clamp` device feature.
+    #[builder(default)]
+    pub depth_clamp: bool,
+
+    /// Enables depth bias which is specified while recording using [`Draw::set_depth_bias`].
+    ///
+    /// [`Draw::set_depth_bias`]: crate::graph::pass_ref::Draw::set_depth_bias
+    #[builder(default)]
+    pub dynamic_depth_bias: bool,
+
+    /// Specifies which states are set dynamically using [`Draw`] functions instead of the values
+    /// of this pipeline information.
+    ///
+    /// Requires the `VK_EXT_extended_dynamic_state` device extension.
+    ///
+    /// The default value is [`ExtendedDynamicState::NONE`].
+    ///
+    /// [`Draw`]: crate::graph::pass_ref::Draw
+    #[builder(default)]
+    pub extended_dynamic_state: ExtendedDynamicState,
+
     /// Interpret polygon front-facing orientation.
     ///
     /// The default value is `vk::FrontFace::COUNTER_CLOCKWISE`.
     #[builder(default = "vk::FrontFace::COUNTER_CLOCKWISE")]
     pub front_face: vk::FrontFace,
 
+    /// The width of rasterized line segments.
+    ///
+    /// Values other than `1.0` require the `wide_lines` device feature.
+    ///
+    /// The default value is `1.0`.
+    #[builder(default = "OrderedFloat(1.0)", setter(into))]
+    pub line_width: OrderedFloat<f32>,
+
+    /// Logical operation applied between the fragment output and the existing color attachment
+    /// values, replacing blending for all color attachments.
+    ///
+    /// Requires the `logic_op` device feature.
+    #[builder(default, setter(strip_option))]
+    pub logic_op: Option<vk::LogicOp>,
+
     /// Specify a fraction of the minimum number of unique samples to process for each fragment.
     #[builder(default, setter(into, strip_option))]
     pub min_sample_shading: Option<OrderedFloat<f32>>,
@@ -695,6 +1044,23 @@ pub struct GraphicPipelineInfo {
     #[builder(default = "vk::PolygonMode::FILL")]
     pub polygon_mode: vk::PolygonMode,
 
+    /// Controls whether a special vertex index value (for example `u32::MAX`) restarts the
+    /// assembly of primitives when using indexed draws.
+    #[builder(default)]
+    pub primitive_restart: bool,
+
+    /// Specifies a descriptor set which is updated using [`Draw::push_descriptor`] between draws
+    /// instead of being allocated from a descriptor pool.
+    ///
+    /// The push descriptor set must be the last (highest numbered) descriptor set used by the
+    /// shaders of this pipeline. Requires the `VK_KHR_push_descriptor` device extension, see
+    /// [`PhysicalDevice::push_descriptor_properties`].
+    ///
+    /// [`Draw::push_descriptor`]: crate::graph::pass_ref::Draw::push_descriptor
+    /// [`PhysicalDevice::push_descriptor_properties`]: super::physical_device::PhysicalDevice::push_descriptor_properties
+    #[builder(default, setter(strip_option))]
+    pub push_descriptor_set: Option<u32>,
+
     /// Input primitive topology.
     ///
     /// The default value is `vk::PrimitiveTopology::TRIANGLE_LIST`.
@@ -717,6 +1083,30 @@ impl GraphicPipelineInfo {
         Default::default()
     }
 
+    /// Returns the color blend state used for the given color attachment index.
+    ///
+    /// This is the value set using [`GraphicPipelineInfoBuilder::attachment_blend`], if any, or
+    /// else [`Self::blend`].
+    pub fn attachment_blend(&self, attachment_idx: u32) -> BlendMode {
+        self.blend_attachments
+            .get(attachment_idx as usize)
+            .copied()
+            .flatten()
+            .unwrap_or(self.blend)
+    }
+
+    /// Returns `true` if the effective blend states of the given color attachments differ from each
+    /// other, which requires the `independent_blend` device feature.
+    fn has_independent_blend(&self, color_attachments: impl IntoIterator<Item = u32>) -> bool {
+        let mut blends = color_attachments
+            .into_iter()
+            .map(|attachment_idx| self.attachment_blend(attachment_idx));
+
+        blends
+            .next()
+            .is_some_and(|first| blends.any(|blend| blend != first))
+    }
+
     /// Converts a `GraphicPipelineInfo` into a `GraphicPipelineInfoBuilder`.
     #[inline(always)]
     pub fn to_builder(self) -> GraphicPipelineInfoBuilder {
@@ -724,11 +1114,23 @@ impl GraphicPipelineInfo {
             alpha_to_coverage: Some(self.alpha_to_coverage),
             alpha_to_one: Some(self.alpha_to_one),
             bindless_descriptor_count: Some(self.bindless_descriptor_count),
+            bindless_heap: Some(self.bindless_heap),
             blend: Some(self.blend),
+            blend_attachments: Some(self.blend_attachments),
+            blend_constants: Some(self.blend_constants),
+            conservative_rasterization: Some(self.conservative_rasterization),
             cull_mode: Some(self.cull_mode),
+            depth_bias: Some(self.depth_bias),
+            depth_clamp: Some(self.depth_clamp),
+            dynamic_depth_bias: Some(self.dynamic_depth_bias),
+            extended_dynamic_state: Some(self.extended_dynamic_state),
             front_face: Some(self.front_face),
+            line_width: Some(self.line_width),
+            logic_op: Some(self.logic_op),
             min_sample_shading: Some(self.min_sample_shading),
             polygon_mode: Some(self.polygon_mode),
+            primitive_restart: Some(self.primitive_restart),
+            push_descriptor_set: Some(self.push_descriptor_set),
             topology: Some(self.topology),
             samples: Some(self.samples),
         }
@@ -741,11 +1143,23 @@ impl Default for GraphicPipelineInfo {
             alpha_to_coverage: false,
             alpha_to_one: false,
             bindless_descriptor_count: 8192,
+            bindless_heap: None,
             blend: BlendMode::REPLACE,
+            blend_attachments: Default::default(),
+            blend_constants: Default::default(),
+            conservative_rasterization: None,
             cull_mode: vk::CullModeFlags::BACK,
+            depth_bias: None,
+            depth_clamp: false,
+            dynamic_depth_bias: false,
+            extended_dynamic_state: ExtendedDynamicState::NONE,
             front_face: vk::FrontFace::COUNTER_CLOCKWISE,
+            line_width: OrderedFloat(1.0),
+            logic_op: None,
             min_sample_shading: None,
             polygon_mode: vk::PolygonMode::FILL,
+            primitive_restart: false,
+            push_descriptor_set: None,
             topology: vk::PrimitiveTopology::TRIANGLE_LIST,
             samples: SampleCount::Type1,
         }
@@ -759,6 +1173,42 @@ impl From<GraphicPipelineInfoBuilder> for GraphicPipelineInfo {
 }
 
 impl GraphicPipelineInfoBuilder {
+    /// Sets the descriptor set index of a [`BindlessHeap`] created using `info` which is accessed
+    /// by this pipeline.
+    ///
+    /// [`BindlessHeap`]: super::bindless::BindlessHeap
+    pub fn bindless_heap(
+        mut self,
+        descriptor_set_idx: u32,
+        info: impl Into<BindlessHeapInfo>,
+    ) -> Self {
+        self.bindless_heap = Some(Some((descriptor_set_idx, info.into())));
+        self
+    }
+
+    /// Sets the color blend state of a single color attachment, overriding the value of
+    /// [`GraphicPipelineInfo::blend`] for that attachment.
+    ///
+    /// # Panics
+    ///
+    /// If `attachment_idx` is eight or greater.
+    pub fn attachment_blend(mut self, attachment_idx: u32, blend: impl Into<BlendMode>) -> Self {
+        assert!(
+            (attachment_idx as usize) < MAX_BLEND_ATTACHMENTS,
+            "unsupported blend attachment index"
+        );
+
+        self.blend_attachments.get_or_insert_with(Default::default)[attachment_idx as usize] =
+            Some(blend.into());
+        self
+    }
+
+    /// Sets the constant RGBA color used by constant blend factors.
+    pub fn blend_constants(mut self, blend_constants: [f32; 4]) -> Self {
+        self.blend_constants = Some(blend_constants.map(OrderedFloat));
+        self
+    }
+
     /// Builds a new `GraphicPipelineInfo`.
     #[inline(always)]
     pub fn build(self) -> GraphicPipelineInfo {
@@ -898,4 +1348,63 @@ mod tests {
 
         assert_eq!(info, builder);
     }
+
+    #[test]
+    pub fn extended_dynamic_state() {
+        assert!(!ExtendedDynamicState::default().is_enabled());
+        assert_eq!(ExtendedDynamicState::NONE.dynamic_states().count(), 0);
+        assert_eq!(ExtendedDynamicState::ALL.dynamic_states().count(), 9);
+
+        let state = ExtendedDynamicState {
+            cull_mode: true,
+            ..Default::default()
+        };
+
+        assert!(state.is_enabled());
+        assert_eq!(
+            state.dynamic_states().collect::<Vec<_>>(),
+            [vk::DynamicState::CULL_MODE_EXT]
+        );
+    }
+
+    #[test]
+    pub fn graphic_pipeline_info_attachment_blend() {
+        let info = Builder::default()
+            .blend(BlendMode::ALPHA)
+            .attachment_blend(2, BlendMode::REPLACE)
+            .blend_constants([1.0, 0.5, 0.25, 0.0])
+            .build();
+
+        assert_eq!(info.attachment_blend(0), BlendMode::ALPHA);
+        assert_eq!(info.attachment_blend(2), BlendMode::REPLACE);
+        assert_eq!(info.attachment_blend(8), BlendMode::ALPHA);
+        assert_eq!(info.blend_constants[1], OrderedFloat(0.5));
+        assert_eq!(info, info.to_builder().build());
+    }
+
+    #[test]
+    pub fn graphic_pipeline_info_independent_blend() {
+        let single = Builder::default()
+            .attachment_blend(0, BlendMode::ALPHA)
+            .build();
+
+        assert!(!single.has_independent_blend([0]));
+
+        let same = Builder::default()
+            .attachment_blend(0, BlendMode::ALPHA)
+            .attachment_blend(1, BlendMode::ALPHA)
+            .build();
+
+        assert!(!same.has_independent_blend([0, 1]));
+        assert!(same.has_independent_blend([0, 1, 2]));
+
+        let differing = Builder::default()
+            .blend(BlendMode::ALPHA)
+            .attachment_blend(1, BlendMode::REPLACE)
+            .build();
+
+        assert!(!differing.has_independent_blend([]));
+        assert!(!differing.has_independent_blend([0, 2]));
+        assert!(differing.has_independent_blend([0, 1]));
+    }
 }
@@ -1,14 +1,17 @@
 //! Image resource types
 
 use {
-    super::{DriverError, device::Device, format_aspect_mask, vk_sync::AccessType},
+    super::{
+        DriverError, HostAccess, allocation_params,
+        device::{Device, allocation_key},
+        format_aspect_mask,
+        vk_sync::AccessType,
+    },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
-    gpu_allocator::{
-        MemoryLocation,
-        vulkan::{Allocation, AllocationCreateDesc, AllocationScheme},
-    },
+    gpu_allocator::vulkan::{Allocation, AllocationCreateDesc, AllocationScheme},
     log::{trace, warn},
+    ordered_float::OrderedFloat,
     std::{
         collections::{HashMap, hash_map::Entry},
         fmt::{Debug, Formatter},
@@ -103,6 +106,9 @@ pub struct Image {
     pub info: ImageInfo,
 
     /// A name for debugging purposes.
+    ///
+    /// The name is also used to identify the memory of this image in memory reports, see
+    /// [`MemoryReport::name_images`](super::device::MemoryReport::name_images).
     pub name: Option<String>,
 }
 
@@ -141,6 +147,10 @@ impl Image {
             "Unspecified image usage {:?}",
             info.usage
         );
+        debug_assert!(
+            !info.mappable || info.tiling == vk::ImageTiling::LINEAR,
+            "Mappable images require linear tiling"
+        );
 
         let accesses = Mutex::new(ImageAccess::new(info, AccessType::Nothing));
 
@@ -155,7 +165,34 @@ impl Image {
                 DriverError::Unsupported
             })?
         };
-        let requirements = unsafe { device.get_image_memory_requirements(image) };
+        let mut dedicated_requirements = vk::MemoryDedicatedRequirements::default();
+        let mut requirements =
+            vk::MemoryRequirements2::default().push_next(&mut dedicated_requirements);
+        unsafe {
+            device.get_image_memory_requirements2(
+                &vk::ImageMemoryRequirementsInfo2::default().image(image),
+                &mut requirements,
+            );
+        }
+        let mut requirements = requirements.memory_requirements;
+
+        let (dedicated, memory_location, memory_priority) = allocation_params(
+            &device,
+            info.dedicated,
+            &dedicated_requirements,
+            info.memory_priority,
+            info.mappable,
+            info.host_access,
+            &mut requirements,
+        )
+        .inspect_err(|_| unsafe {
+            device.destroy_image(image, None);
+        })?;
+        let allocation_scheme = if dedicated {
+            AllocationScheme::DedicatedImage(image)
+        } else {
+            AllocationScheme::GpuAllocatorManaged
+        };
         let allocation = {
             profiling::scope!("allocate");
 
@@ -169,9 +206,9 @@ impl Image {
                 .allocate(&AllocationCreateDesc {
                     name: "image",
                     requirements,
-                    location: MemoryLocation::GpuOnly,
-                    linear: false,
-                    allocation_scheme: AllocationScheme::GpuAllocatorManaged,
+                    location: memory_location,
+                    linear: info.tiling == vk::ImageTiling::LINEAR,
+                    allocation_scheme,
                 })
                 .map_err(|err| {
                     warn!("{err}");
@@ -180,6 +217,10 @@ impl Image {
                 })
         }?;
 
+        if let Some(memory_priority) = memory_priority {
+            Device::set_memory_priority(&device, unsafe { allocation.memory() }, memory_priority);
+        }
+
         unsafe {
             device
                 .bind_image_memory(image, allocation.memory(), allocation.offset())
@@ -190,6 +231,8 @@ impl Image {
                 })?;
         }
 
+        Device::track_allocation(&device, &allocation, "image");
+
         Ok(Self {
             accesses,
             allocation: Some(allocation),
@@ -313,6 +356,113 @@ impl Image {
         }
     }
 
+    /// Returns the size in bytes of the memory allocated for this image, or zero if the image is
+    /// not owned by this instance.
+    pub(crate) fn memory_size(this: &Self) -> vk::DeviceSize {
+        this.allocation
+            .as_ref()
+            .map(Allocation::size)
+            .unwrap_or_default()
+    }
+
+    /// Returns a mapped slice of the memory of this image.
+    ///
+    /// The data is arranged according to the subresource layouts returned by
+    /// [`Image::subresource_layout`]. Host access requires the image to be in the general layout,
+    /// for example by accessing it using [`AccessType::HostRead`] once device writes have completed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image was not created with the `mappable` flag set to `true`.
+    #[profiling::function]
+    pub fn mapped_slice(this: &Self) -> &[u8] {
+        debug_assert!(
+            this.info.mappable,
+            "Image is not mappable - create using mappable flag"
+        );
+
+        this.allocation
+            .as_ref()
+            .and_then(Allocation::mapped_slice)
+            .unwrap()
+    }
+
+    /// Returns a mapped mutable slice of the memory of this image.
+    ///
+    /// See [`Image::mapped_slice`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image was not created with the `mappable` flag set to `true`.
+    #[profiling::function]
+    pub fn mapped_slice_mut(this: &mut Self) -> &mut [u8] {
+        debug_assert!(
+            this.info.mappable,
+            "Image is not mappable - create using mappable flag"
+        );
+
+        this.allocation
+            .as_mut()
+            .and_then(Allocation::mapped_slice_mut)
+            .unwrap()
+    }
+
+    /// Returns the memory layout of a subresource of a linear-tiled image.
+    ///
+    /// The returned offset is relative to the start of the slice returned by
+    /// [`Image::mapped_slice`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::image::{Image, ImageInfo};
+    /// # use screen_13::driver::HostAccess;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// let info = ImageInfo::image_2d(32, 32, vk::Format::R8G8B8A8_UNORM, vk::ImageUsageFlags::TRANSFER_DST)
+    ///     .to_builder()
+    ///     .tiling(vk::ImageTiling::LINEAR)
+    ///     .mappable(true)
+    ///     .host_access(HostAccess::Read);
+    /// let image = Image::create(&device, info)?;
+    /// let layout = Image::subresource_layout(&image, vk::ImageAspectFlags::COLOR, 0, 0);
+    ///
+    /// assert!(layout.row_pitch >= 32 * 4);
+    /// # Ok(()) }
+    /// ```
+    #[profiling::function]
+    pub fn subresource_layout(
+        this: &Self,
+        aspect_mask: vk::ImageAspectFlags,
+        mip_level: u32,
+        array_layer: u32,
+    ) -> vk::SubresourceLayout {
+        debug_assert_eq!(
+            this.info.tiling,
+            vk::ImageTiling::LINEAR,
+            "Subresource layouts require linear tiling"
+        );
+        debug_assert!(mip_level < this.info.mip_level_count);
+        debug_assert!(array_layer < this.info.array_layer_count);
+
+        unsafe {
+            this.device.get_image_subresource_layout(
+                this.image,
+                vk::ImageSubresource {
+                    aspect_mask,
+                    mip_level,
+                    array_layer,
+                },
+            )
+        }
+    }
+
     #[profiling::function]
     fn drop_allocation(this: &Self, allocation: Allocation) {
         {
@@ -334,6 +484,8 @@ impl Image {
         {
             profiling::scope!("deallocate");
 
+            Device::untrack_allocation(&this.device, &allocation);
+
             #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
             let mut allocator = this.device.allocator.lock();
 
@@ -388,6 +540,16 @@ impl Image {
             }
         })
     }
+
+    pub(super) fn allocation_key(this: &Self) -> Option<(vk::DeviceMemory, vk::DeviceSize)> {
+        this.allocation.as_ref().map(allocation_key)
+    }
+
+    /// Sets the debugging name assigned to this image.
+    pub fn with_name(mut this: Self, name: impl Into<String>) -> Self {
+        this.name = Some(name.into());
+        this
+    }
 }
 
 impl Debug for Image {
@@ -694,6 +856,14 @@ pub struct ImageInfo {
     #[builder(setter(strip_option))]
     pub depth: u32,
 
+    /// Specifies an image which uses a dedicated memory allocation instead of sharing a larger
+    /// block of memory with other resources.
+    ///
+    /// Dedicated allocations are always used when the driver prefers or requires them, or when
+    /// `memory_priority` is specified and supported by the device.
+    #[builder(default, setter(strip_option))]
+    pub dedicated: bool,
+
     /// A bitmask of describing additional parameters of the image.
     #[builder(default, setter(strip_option))]
     pub flags: vk::ImageCreateFlags,
@@ -706,6 +876,30 @@ pub struct ImageInfo {
     #[builder(setter(strip_option))]
     pub height: u32,
 
+    /// Specifies how the host accesses the memory of a mappable image.
+    ///
+    /// Has no effect unless `mappable` is `true`.
+    #[builder(default, setter(strip_option))]
+    pub host_access: HostAccess,
+
+    /// Specifies an image whose memory is host visible and may be mapped.
+    ///
+    /// Mappable images must use [`vk::ImageTiling::LINEAR`] tiling.
+    #[builder(default, setter(strip_option))]
+    pub mappable: bool,
+
+    // Note: Using setter(into) so caller does not need our version of OrderedFloat
+    /// Specifies the priority, between `0.0` and `1.0`, of the memory of this image relative to
+    /// other memory allocations.
+    ///
+    /// Higher priority memory is more likely to stay in device-local memory when the system is
+    /// under memory pressure, which is useful for large render targets. Requires
+    /// [`MemoryPriorityFeatures::pageable_device_local_memory`](super::physical_device::MemoryPriorityFeatures::pageable_device_local_memory)
+    /// and is otherwise ignored, with a warning logged once, without affecting how memory is
+    /// allocated.
+    #[builder(default, setter(into, strip_option))]
+    pub memory_priority: Option<OrderedFloat<f32>>,
+
     /// The number of levels of detail available for minified sampling of the image.
     #[builder(default = "1", setter(strip_option))]
     pub mip_level_count: u32,
@@ -811,7 +1005,11 @@ impl ImageInfo {
             array_layer_count,
             fmt,
             usage,
+            dedicated: false,
             flags: vk::ImageCreateFlags::empty(),
+            host_access: HostAccess::Write,
+            mappable: false,
+            memory_priority: None,
             tiling: vk::ImageTiling::OPTIMAL,
             mip_level_count: 1,
             sample_count: SampleCount::Type1,
@@ -966,9 +1164,13 @@ impl ImageInfo {
         ImageInfoBuilder {
             array_layer_count: Some(self.array_layer_count),
             depth: Some(self.depth),
+            dedicated: Some(self.dedicated),
             flags: Some(self.flags),
             fmt: Some(self.fmt),
             height: Some(self.height),
+            host_access: Some(self.host_access),
+            mappable: Some(self.mappable),
+            memory_priority: Some(self.memory_priority),
             mip_level_count: Some(self.mip_level_count),
             sample_count: Some(self.sample_count),
             tiling: Some(self.tiling),
@@ -1091,12 +1293,7 @@ impl ImageView {
         let create_info = vk::ImageViewCreateInfo::default()
             .view_type(info.ty.into_vk())
             .format(info.fmt)
-            .components(vk::ComponentMapping {
-                r: vk::ComponentSwizzle::R,
-                g: vk::ComponentSwizzle::G,
-                b: vk::ComponentSwizzle::B,
-                a: vk::ComponentSwizzle::A,
-            })
+            .components(info.swizzle.into())
             .image(image)
             .subresource_range(vk::ImageSubresourceRange {
                 aspect_mask: info.aspect_mask,
@@ -1157,6 +1354,9 @@ pub struct ImageViewInfo {
     pub base_mip_level: u32,
 
     /// The format and type of the texel blocks that will be contained in the view.
+    ///
+    /// Views using a different format than the image must use a compatible format and the image
+    /// must have been created with `vk::ImageCreateFlags::MUTABLE_FORMAT`.
     pub fmt: vk::Format,
 
     /// The number of mip levels that will be contained in the view.
@@ -1165,6 +1365,18 @@ pub struct ImageViewInfo {
     #[builder(default = "vk::REMAINING_MIP_LEVELS")]
     pub mip_level_count: u32,
 
+    /// Specifies a remapping of color components (or of depth or stencil components after they
+    /// have been converted into color components) when the view is sampled or read.
+    ///
+    /// For example, a single-channel `R8_UNORM` image may be sampled as grayscale by mapping the
+    /// `g` and `b` components to `vk::ComponentSwizzle::R`.
+    ///
+    /// Views used as framebuffer attachments or storage images must use the identity mapping.
+    ///
+    /// The default value is [`Swizzle::IDENTITY`].
+    #[builder(default, setter(into))]
+    pub swizzle: Swizzle,
+
     /// The basic dimensionality of the view.
     pub ty: ImageType,
 }
@@ -1184,6 +1396,7 @@ impl ImageViewInfo {
             base_mip_level: 0,
             fmt,
             mip_level_count: vk::REMAINING_MIP_LEVELS,
+            swizzle: Swizzle::IDENTITY,
             ty,
         }
     }
@@ -1198,10 +1411,17 @@ impl ImageViewInfo {
             base_mip_level: Some(self.base_mip_level),
             fmt: Some(self.fmt),
             mip_level_count: Some(self.mip_level_count),
+            swizzle: Some(self.swizzle),
             ty: Some(self.ty),
         }
     }
 
+    /// Takes this instance and returns it with a newly specified component mapping.
+    pub fn with_swizzle(mut self, swizzle: impl Into<Swizzle>) -> Self {
+        self.swizzle = swizzle.into();
+        self
+    }
+
     /// Takes this instance and returns it with a newly specified `ImageType`.
     pub fn with_ty(mut self, ty: ImageType) -> Self {
         self.ty = ty;
@@ -1218,6 +1438,7 @@ impl From<ImageInfo> for ImageViewInfo {
             base_mip_level: 0,
             fmt: info.fmt,
             mip_level_count: info.mip_level_count,
+            swizzle: Swizzle::IDENTITY,
             ty: info.ty,
         }
     }
@@ -1264,6 +1485,81 @@ impl From<UninitializedFieldError> for ImageViewInfoBuilderError {
     }
 }
 
+/// Specifies a remapping of the color components of an image view.
+///
+/// See
+/// [VkComponentMapping](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkComponentMapping.html)
+/// for more information.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Swizzle {
+    /// Specifies the component value placed in the R component of the output vector.
+    pub r: vk::ComponentSwizzle,
+
+    /// Specifies the component value placed in the G component of the output vector.
+    pub g: vk::ComponentSwizzle,
+
+    /// Specifies the component value placed in the B component of the output vector.
+    pub b: vk::ComponentSwizzle,
+
+    /// Specifies the component value placed in the A component of the output vector.
+    pub a: vk::ComponentSwizzle,
+}
+
+impl Swizzle {
+    /// Each component is mapped to itself.
+    pub const IDENTITY: Self = Self::new(
+        vk::ComponentSwizzle::IDENTITY,
+        vk::ComponentSwizzle::IDENTITY,
+        vk::ComponentSwizzle::IDENTITY,
+        vk::ComponentSwizzle::IDENTITY,
+    );
+
+    /// The R component is replicated to the R, G and B components and A is one, which samples a
+    /// single-channel image as opaque grayscale.
+    pub const GRAYSCALE: Self = Self::new(
+        vk::ComponentSwizzle::R,
+        vk::ComponentSwizzle::R,
+        vk::ComponentSwizzle::R,
+        vk::ComponentSwizzle::ONE,
+    );
+
+    /// The R component is moved to A and the R, G and B components are one, which samples a
+    /// single-channel image as white with alpha.
+    pub const ALPHA: Self = Self::new(
+        vk::ComponentSwizzle::ONE,
+        vk::ComponentSwizzle::ONE,
+        vk::ComponentSwizzle::ONE,
+        vk::ComponentSwizzle::R,
+    );
+
+    /// Specifies a component mapping using the given values.
+    pub const fn new(
+        r: vk::ComponentSwizzle,
+        g: vk::ComponentSwizzle,
+        b: vk::ComponentSwizzle,
+        a: vk::ComponentSwizzle,
+    ) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl From<vk::ComponentMapping> for Swizzle {
+    fn from(mapping: vk::ComponentMapping) -> Self {
+        Self::new(mapping.r, mapping.g, mapping.b, mapping.a)
+    }
+}
+
+impl From<Swizzle> for vk::ComponentMapping {
+    fn from(swizzle: Swizzle) -> Self {
+        Self {
+            r: swizzle.r,
+            g: swizzle.g,
+            b: swizzle.b,
+            a: swizzle.a,
+        }
+    }
+}
+
 /// Specifies sample counts supported for an image used for storage operation.
 ///
 /// Values must not exceed the device limits specified by [Device.physical_device.props.limits].
@@ -2286,6 +2582,27 @@ mod tests {
         assert_eq!(info, builder);
     }
 
+    #[test]
+    pub fn image_info_mappable() {
+        let info = ImageInfo::image_2d(42, 84, vk::Format::R8_UNORM, vk::ImageUsageFlags::empty())
+            .to_builder()
+            .tiling(vk::ImageTiling::LINEAR)
+            .mappable(true)
+            .host_access(HostAccess::Read)
+            .build();
+
+        assert!(info.mappable);
+        assert_eq!(info.host_access, HostAccess::Read);
+        assert_eq!(info, info.to_builder().build());
+        assert_ne!(
+            info,
+            ImageInfo::image_2d(42, 84, vk::Format::R8_UNORM, vk::ImageUsageFlags::empty())
+                .to_builder()
+                .tiling(vk::ImageTiling::LINEAR)
+                .build()
+        );
+    }
+
     #[test]
     #[should_panic(expected = "Field not initialized: depth")]
     pub fn image_info_builder_uninit_depth() {
@@ -2425,6 +2742,24 @@ mod tests {
         assert_eq!(info, builder);
     }
 
+    #[test]
+    pub fn image_view_info_swizzle() {
+        let info = ImageViewInfo::new(vk::Format::R8_UNORM, ImageType::Texture2D);
+
+        assert_eq!(info.swizzle, Swizzle::IDENTITY);
+        assert_eq!(info.swizzle, Swizzle::default());
+
+        let info = info.with_swizzle(Swizzle::GRAYSCALE);
+        let builder = info.to_builder().build();
+
+        assert_eq!(builder.swizzle, Swizzle::GRAYSCALE);
+        assert_eq!(info, builder);
+
+        let mapping = vk::ComponentMapping::from(Swizzle::ALPHA);
+
+        assert_eq!(Swizzle::from(mapping), Swizzle::ALPHA);
+    }
+
     #[test]
     #[should_panic(expected = "Field not initialized: aspect_mask")]
     pub fn image_view_info_builder_uninit_aspect_mask() {
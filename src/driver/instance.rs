@@ -1,6 +1,6 @@
 use {
     super::{DriverError, physical_device::PhysicalDevice},
-    ash::{Entry, ext, vk},
+    ash::{Entry, ext, khr, vk},
     log::{debug, error, trace, warn},
     std::{
         ffi::{CStr, CString},
@@ -120,6 +120,7 @@ pub struct Instance {
     _debug_loader: Option<ext::debug_report::Instance>,
     debug_utils: Option<ext::debug_utils::Instance>,
     entry: Entry,
+    headless_surface: Option<ext::headless_surface::Instance>,
     instance: ash::Instance,
 }
 
@@ -149,10 +150,15 @@ impl Instance {
         let entry = ash_molten::load();
 
         let required_extensions = required_extensions.collect::<Vec<_>>();
+        let headless_surface = required_extensions.contains(&ext::headless_surface::NAME);
         let instance_extensions = required_extensions
             .iter()
             .map(|ext| ext.as_ptr())
             .chain(unsafe { Self::extension_names(debug).into_iter() })
+            .chain(Self::swapchain_colorspace_extension_name(
+                &entry,
+                &required_extensions,
+            ))
             .collect::<Box<[_]>>();
         let layer_names = Self::layer_names(debug);
         let layer_names: Vec<*const i8> = layer_names
@@ -217,11 +223,15 @@ impl Instance {
             (None, None, None)
         };
 
+        let headless_surface =
+            headless_surface.then(|| ext::headless_surface::Instance::new(&entry, &instance));
+
         Ok(Self {
             _debug_callback: debug_callback,
             _debug_loader: debug_loader,
             debug_utils,
             entry,
+            headless_surface,
             instance,
         })
     }
@@ -243,6 +253,7 @@ impl Instance {
             _debug_loader: None,
             debug_utils: None,
             entry,
+            headless_surface: None,
             instance,
         })
     }
@@ -252,6 +263,12 @@ impl Instance {
         &this.entry
     }
 
+    /// Returns the `VK_EXT_headless_surface` functions if this instance was created with the
+    /// extension enabled.
+    pub(crate) fn headless_surface_ext(this: &Self) -> Option<&ext::headless_surface::Instance> {
+        this.headless_surface.as_ref()
+    }
+
     unsafe fn extension_names(
         #[cfg_attr(target_os = "macos", allow(unused_variables))] debug: bool,
     ) -> Vec<*const c_char> {
@@ -268,6 +285,28 @@ impl Instance {
         res
     }
 
+    /// Returns the swapchain color space extension name if surfaces are required and the extension
+    /// is available, which allows HDR surface formats to be reported.
+    fn swapchain_colorspace_extension_name(
+        entry: &Entry,
+        required_extensions: &[&CStr],
+    ) -> Option<*const c_char> {
+        if !required_extensions.contains(&khr::surface::NAME) {
+            return None;
+        }
+
+        let extensions = unsafe { entry.enumerate_instance_extension_properties(None) }
+            .inspect_err(|err| warn!("unable to enumerate instance extensions: {err}"))
+            .ok()?;
+
+        extensions
+            .iter()
+            .any(|property| {
+                property.extension_name_as_c_str() == Ok(ext::swapchain_colorspace::NAME)
+            })
+            .then(|| ext::swapchain_colorspace::NAME.as_ptr())
+    }
+
     /// Returns `true` if this instance was created with debug layers enabled.
     pub fn is_debug(this: &Self) -> bool {
         this.debug_utils.is_some()
@@ -27,6 +27,7 @@
 //! - [`RayTracePipeline`](ray_trace::RayTracePipeline)
 
 pub mod accel_struct;
+pub mod bindless;
 pub mod buffer;
 pub mod compute;
 pub mod device;
@@ -74,18 +75,46 @@ pub(crate) use self::{
 use {
     self::{
         buffer::{Buffer, BufferInfo},
+        device::Device,
         graphic::{DepthStencilMode, GraphicPipeline, VertexInputState},
         image::SampleCount,
     },
     ash::vk,
+    gpu_allocator::MemoryLocation,
+    log::warn,
+    ordered_float::OrderedFloat,
     std::{
         cmp::Ordering,
         error::Error,
         fmt::{Display, Formatter},
+        ops::Range,
+        sync::Once,
     },
     vk_sync::ImageLayout,
 };
 
+/// Returns the image layout used when an image view is written to a descriptor of the given type.
+pub(super) fn descriptor_image_layout(
+    descriptor_type: vk::DescriptorType,
+    aspect_mask: vk::ImageAspectFlags,
+) -> vk::ImageLayout {
+    match descriptor_type {
+        vk::DescriptorType::COMBINED_IMAGE_SAMPLER | vk::DescriptorType::SAMPLED_IMAGE => {
+            if aspect_mask.contains(vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL) {
+                vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL
+            } else if aspect_mask.contains(vk::ImageAspectFlags::DEPTH) {
+                vk::ImageLayout::DEPTH_READ_ONLY_OPTIMAL
+            } else if aspect_mask.contains(vk::ImageAspectFlags::STENCIL) {
+                vk::ImageLayout::STENCIL_READ_ONLY_OPTIMAL
+            } else {
+                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL
+            }
+        }
+        vk::DescriptorType::STORAGE_IMAGE => vk::ImageLayout::GENERAL,
+        _ => unimplemented!("{descriptor_type:?}"),
+    }
+}
+
 pub(super) const fn format_aspect_mask(fmt: vk::Format) -> vk::ImageAspectFlags {
     match fmt {
         vk::Format::D16_UNORM | vk::Format::D32_SFLOAT | vk::Format::X8_D24_UNORM_PACK32 => {
@@ -329,7 +358,8 @@ pub(super) const fn is_write_access(ty: AccessType) -> bool {
         | RayTracingShaderReadDepthStencilInputAttachment
         | RayTracingShaderReadAccelerationStructure
         | RayTracingShaderReadOther
-        | AccelerationStructureBuildRead => false,
+        | AccelerationStructureBuildRead
+        | AccelerationStructureBufferRead => false,
         CommandBufferWriteNVX
         | VertexShaderWrite
         | TessellationControlShaderWrite
@@ -454,6 +484,104 @@ fn merge_push_constant_ranges(pcr: &[vk::PushConstantRange]) -> Vec<vk::PushCons
     }
 }
 
+/// Returns `Ok` if `size` bytes at `offset` are entirely declared by the push constant ranges of a
+/// pipeline, otherwise returns each shader stage (one stage flag per item) which declares some, but
+/// not all, of the data along with the range of bytes the stage declares.
+///
+/// Stages which declare none of the data are not mismatched.
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+fn push_constant_mismatches(
+    pcr: &[vk::PushConstantRange],
+    offset: u32,
+    size: u32,
+) -> Result<(), Vec<(vk::ShaderStageFlags, Range<u32>)>> {
+    let end = offset + size;
+
+    // Walk the ranges in order to find the first byte not declared by any stage
+    let mut ranges = pcr.to_vec();
+    ranges.sort_unstable_by_key(|range| range.offset);
+
+    let mut declared_end = offset;
+    for range in ranges {
+        if range.offset > declared_end {
+            break;
+        }
+
+        declared_end = declared_end.max(range.offset + range.size);
+    }
+
+    if declared_end >= end {
+        return Ok(());
+    }
+
+    let stage_flags = pcr
+        .iter()
+        .fold(vk::ShaderStageFlags::empty(), |stage_flags, range| {
+            stage_flags | range.stage_flags
+        });
+
+    Err((0..u32::BITS)
+        .map(|bit| vk::ShaderStageFlags::from_raw(1 << bit))
+        .filter(|stage| stage_flags.contains(*stage))
+        .filter(|stage| {
+            // Any stage which declares part of the data cannot declare all of it, because the data
+            // was not entirely declared above
+            pcr.iter().any(|range| {
+                range.stage_flags.contains(*stage)
+                    && range.offset < end
+                    && offset < range.offset + range.size
+            })
+        })
+        .map(|stage| {
+            let (start, end) = pcr
+                .iter()
+                .filter(|range| range.stage_flags.contains(stage))
+                .fold((u32::MAX, 0), |(start, end), range| {
+                    (start.min(range.offset), end.max(range.offset + range.size))
+                });
+
+            (stage, start..end)
+        })
+        .collect())
+}
+
+/// Checks that `size` bytes of push constant data at `offset` are valid for a pipeline which uses
+/// the given (merged) push constant ranges.
+///
+/// # Panics
+///
+/// Panics if `offset` or `size` are not multiples of four or if any of the data is not declared
+/// by the shader stages of the pipeline. Each mismatched stage is logged before panicking.
+#[cfg(debug_assertions)]
+pub(crate) fn validate_push_constants(pcr: &[vk::PushConstantRange], offset: u32, size: u32) {
+    assert_eq!(
+        offset % 4,
+        0,
+        "Push constant offset must be a multiple of 4"
+    );
+    assert_eq!(size % 4, 0, "Push constant size must be a multiple of 4");
+
+    let Err(mismatches) = push_constant_mismatches(pcr, offset, size) else {
+        return;
+    };
+
+    for (stage, range) in mismatches {
+        log::warn!(
+            "{stage:?} stage declares push constants {}..{} (data is {}..{})",
+            range.start,
+            range.end,
+            offset,
+            offset + size
+        );
+    }
+
+    panic!(
+        "Push constant data {}..{} is not declared by the pipeline",
+        offset,
+        offset + size
+    );
+}
+
 pub(super) const fn pipeline_stage_access_flags(
     access_type: AccessType,
 ) -> (vk::PipelineStageFlags, vk::AccessFlags) {
@@ -639,7 +767,123 @@ pub(super) const fn pipeline_stage_access_flags(
             stage::ACCELERATION_STRUCTURE_BUILD_KHR,
             access::TRANSFER_WRITE,
         ),
+        ty::AccelerationStructureBufferRead => (
+            stage::ACCELERATION_STRUCTURE_BUILD_KHR,
+            access::TRANSFER_READ,
+        ),
+    }
+}
+
+/// Describes how the host accesses the memory of a mappable resource.
+///
+/// This value has no effect on resources which are not mappable.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum HostAccess {
+    /// Memory is written by the host and read by the device.
+    ///
+    /// Write-combined memory is preferred, which is fast to write sequentially but very slow for
+    /// the host to read.
+    #[default]
+    Write,
+
+    /// Memory is written by the device and read by the host.
+    ///
+    /// Host-cached memory is preferred, which is suitable for readback of results.
+    Read,
+
+    /// Memory is written by the host and read by the device and must be located in device-local
+    /// memory.
+    ///
+    /// This requires a device which exposes host-visible device-local memory, such as those with
+    /// resizable BAR ("ReBAR") or Smart Access Memory enabled, otherwise resource creation will
+    /// fail with [`DriverError::Unsupported`].
+    DeviceLocalWrite,
+}
+
+/// Returns whether a resource is allocated dedicated memory, the memory location of the allocation
+/// and the memory priority to set on it, restricting `requirements` to the memory types which
+/// satisfy the given host access.
+pub(super) fn allocation_params(
+    device: &Device,
+    dedicated: bool,
+    dedicated_requirements: &vk::MemoryDedicatedRequirements,
+    memory_priority: Option<OrderedFloat<f32>>,
+    mappable: bool,
+    host_access: HostAccess,
+    requirements: &mut vk::MemoryRequirements,
+) -> Result<(bool, MemoryLocation, Option<f32>), DriverError> {
+    static MEMORY_PRIORITY_IGNORED: Once = Once::new();
+
+    let memory_priority = memory_priority.and_then(|OrderedFloat(memory_priority)| {
+        if Device::supports_memory_priority(device) {
+            Some(memory_priority)
+        } else {
+            MEMORY_PRIORITY_IGNORED.call_once(|| {
+                warn!("memory priority ignored: VK_EXT_pageable_device_local_memory not supported");
+            });
+
+            None
+        }
+    });
+
+    // Priorities are set per allocation, so prioritized resources are allocated dedicated memory
+    let dedicated = dedicated
+        || memory_priority.is_some()
+        || dedicated_requirements.prefers_dedicated_allocation == vk::TRUE
+        || dedicated_requirements.requires_dedicated_allocation == vk::TRUE;
+    let memory_location = memory_location(
+        &device.physical_device.memory_properties,
+        mappable,
+        host_access,
+        requirements,
+    )?;
+
+    Ok((dedicated, memory_location, memory_priority))
+}
+
+/// Returns the memory location used to allocate a resource and restricts `requirements` to the
+/// memory types which satisfy the given host access.
+fn memory_location(
+    memory_properties: &vk::PhysicalDeviceMemoryProperties,
+    mappable: bool,
+    host_access: HostAccess,
+    requirements: &mut vk::MemoryRequirements,
+) -> Result<MemoryLocation, DriverError> {
+    if !mappable {
+        return Ok(MemoryLocation::GpuOnly);
     }
+
+    Ok(match host_access {
+        HostAccess::Write => MemoryLocation::CpuToGpu,
+        HostAccess::Read => MemoryLocation::GpuToCpu,
+        HostAccess::DeviceLocalWrite => {
+            let flags = vk::MemoryPropertyFlags::DEVICE_LOCAL
+                | vk::MemoryPropertyFlags::HOST_VISIBLE
+                | vk::MemoryPropertyFlags::HOST_COHERENT;
+            let memory_type_bits = memory_properties
+                .memory_types_as_slice()
+                .iter()
+                .enumerate()
+                .fold(0, |bits, (idx, memory_type)| {
+                    if memory_type.property_flags.contains(flags) {
+                        bits | (1 << idx)
+                    } else {
+                        bits
+                    }
+                })
+                & requirements.memory_type_bits;
+
+            if memory_type_bits == 0 {
+                warn!("unsupported host-visible device-local memory");
+
+                return Err(DriverError::Unsupported);
+            }
+
+            requirements.memory_type_bits = memory_type_bits;
+
+            MemoryLocation::CpuToGpu
+        }
+    })
 }
 
 /// Describes the general category of all graphics driver failure cases.
@@ -651,7 +895,7 @@ pub(super) const fn pipeline_stage_access_flags(
 ///
 /// Feel free to open an issue on GitHub, [here](https://github.com/attackgoat/screen-13/issues) for
 /// help debugging the issue.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum DriverError {
     /// The input data, or referenced data, is not valid for the current state.
     InvalidData,
@@ -675,7 +919,10 @@ impl Error for DriverError {}
 
 #[cfg(test)]
 mod tests {
-    use {super::merge_push_constant_ranges, ash::vk};
+    use {
+        super::{merge_push_constant_ranges, push_constant_mismatches},
+        ash::vk,
+    };
 
     macro_rules! assert_pcr_eq {
         ($lhs: expr, $rhs: expr,) => {
@@ -988,4 +1235,80 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    pub fn push_constant_mismatches_declared() {
+        let pcr = merge_push_constant_ranges(&[
+            vk::PushConstantRange {
+                stage_flags: vk::ShaderStageFlags::VERTEX,
+                offset: 0,
+                size: 16,
+            },
+            vk::PushConstantRange {
+                stage_flags: vk::ShaderStageFlags::FRAGMENT,
+                offset: 8,
+                size: 24,
+            },
+        ]);
+
+        assert!(push_constant_mismatches(&pcr, 0, 32).is_ok());
+        assert!(push_constant_mismatches(&pcr, 4, 8).is_ok());
+        assert!(push_constant_mismatches(&pcr, 16, 16).is_ok());
+        assert!(push_constant_mismatches(&pcr, 0, 0).is_ok());
+    }
+
+    #[test]
+    pub fn push_constant_mismatches_undeclared() {
+        let pcr = [
+            vk::PushConstantRange {
+                stage_flags: vk::ShaderStageFlags::VERTEX,
+                offset: 0,
+                size: 8,
+            },
+            vk::PushConstantRange {
+                stage_flags: vk::ShaderStageFlags::FRAGMENT,
+                offset: 16,
+                size: 8,
+            },
+        ];
+
+        assert_eq!(
+            push_constant_mismatches(&pcr, 0, 24),
+            Err(vec![
+                (vk::ShaderStageFlags::VERTEX, 0..8),
+                (vk::ShaderStageFlags::FRAGMENT, 16..24)
+            ])
+        );
+        assert_eq!(
+            push_constant_mismatches(&pcr, 16, 12),
+            Err(vec![(vk::ShaderStageFlags::FRAGMENT, 16..24)])
+        );
+        assert_eq!(push_constant_mismatches(&pcr, 32, 4), Err(vec![]));
+        assert_eq!(push_constant_mismatches(&[], 0, 4), Err(vec![]));
+    }
+
+    #[test]
+    pub fn push_constant_mismatches_single_stage() {
+        let pcr = [
+            vk::PushConstantRange {
+                stage_flags: vk::ShaderStageFlags::VERTEX,
+                offset: 0,
+                size: 8,
+            },
+            vk::PushConstantRange {
+                stage_flags: vk::ShaderStageFlags::FRAGMENT,
+                offset: 16,
+                size: 8,
+            },
+        ];
+
+        assert_eq!(
+            push_constant_mismatches(&pcr, 4, 8),
+            Err(vec![(vk::ShaderStageFlags::VERTEX, 0..8)])
+        );
+        assert_eq!(
+            push_constant_mismatches(&pcr, 12, 8),
+            Err(vec![(vk::ShaderStageFlags::FRAGMENT, 16..24)])
+        );
+    }
 }
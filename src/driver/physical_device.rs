@@ -118,6 +118,90 @@ impl From<vk::PhysicalDeviceDepthStencilResolveProperties<'_>> for DepthStencilR
     }
 }
 
+/// Properties of the physical device for conservative rasterization.
+///
+/// See
+/// [`VkPhysicalDeviceConservativeRasterizationPropertiesEXT`](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkPhysicalDeviceConservativeRasterizationPropertiesEXT.html)
+/// manual page.
+#[derive(Debug)]
+pub struct ConservativeRasterizationProperties {
+    /// The size in pixels the generating primitive is increased at each of its edges during
+    /// conservative rasterization overestimation mode.
+    pub primitive_overestimation_size: f32,
+
+    /// The maximum size in pixels of extra overestimation the implementation supports in the
+    /// pipeline state.
+    pub max_extra_primitive_overestimation_size: f32,
+
+    /// The granularity of extra overestimation that can be specified in the pipeline state.
+    pub extra_primitive_overestimation_size_granularity: f32,
+
+    /// Indicates support for conservative rasterization underestimation mode.
+    pub primitive_underestimation: bool,
+
+    /// Indicates that conservative rasterization is applied to points and lines as well as
+    /// triangles.
+    pub conservative_point_and_line_rasterization: bool,
+
+    /// Indicates that degenerate triangles are rasterized when using overestimation mode.
+    pub degenerate_triangles_rasterized: bool,
+
+    /// Indicates that degenerate lines are rasterized when using overestimation mode.
+    pub degenerate_lines_rasterized: bool,
+
+    /// Indicates that the `FullyCoveredEXT` SPIR-V builtin is supported.
+    pub fully_covered_fragment_shader_input_variable: bool,
+
+    /// Indicates that the implementation supports the `SPV_NV_inner_coverage` extension with
+    /// underestimation mode.
+    pub conservative_rasterization_post_depth_coverage: bool,
+}
+
+impl From<vk::PhysicalDeviceConservativeRasterizationPropertiesEXT<'_>>
+    for ConservativeRasterizationProperties
+{
+    fn from(props: vk::PhysicalDeviceConservativeRasterizationPropertiesEXT<'_>) -> Self {
+        Self {
+            primitive_overestimation_size: props.primitive_overestimation_size,
+            max_extra_primitive_overestimation_size: props.max_extra_primitive_overestimation_size,
+            extra_primitive_overestimation_size_granularity: props
+                .extra_primitive_overestimation_size_granularity,
+            primitive_underestimation: props.primitive_underestimation == vk::TRUE,
+            conservative_point_and_line_rasterization: props
+                .conservative_point_and_line_rasterization
+                == vk::TRUE,
+            degenerate_triangles_rasterized: props.degenerate_triangles_rasterized == vk::TRUE,
+            degenerate_lines_rasterized: props.degenerate_lines_rasterized == vk::TRUE,
+            fully_covered_fragment_shader_input_variable: props
+                .fully_covered_fragment_shader_input_variable
+                == vk::TRUE,
+            conservative_rasterization_post_depth_coverage: props
+                .conservative_rasterization_post_depth_coverage
+                == vk::TRUE,
+        }
+    }
+}
+
+/// Features of the physical device for extended dynamic state.
+///
+/// See
+/// [`VkPhysicalDeviceExtendedDynamicStateFeaturesEXT`](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkPhysicalDeviceExtendedDynamicStateFeaturesEXT.html)
+/// manual page.
+#[derive(Debug, Default)]
+pub struct ExtendedDynamicStateFeatures {
+    /// Indicates that the implementation supports the cull mode, front face, primitive topology,
+    /// depth and stencil states being set dynamically while recording commands.
+    pub extended_dynamic_state: bool,
+}
+
+impl From<vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT<'_>> for ExtendedDynamicStateFeatures {
+    fn from(features: vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT<'_>) -> Self {
+        Self {
+            extended_dynamic_state: features.extended_dynamic_state == vk::TRUE,
+        }
+    }
+}
+
 /// Features of the physical device for vertex indexing.
 ///
 /// See
@@ -138,15 +222,43 @@ impl From<vk::PhysicalDeviceIndexTypeUint8FeaturesEXT<'_>> for IndexTypeUint8Fea
     }
 }
 
+/// Features of the physical device for memory priority.
+///
+/// See
+/// [`VkPhysicalDeviceMemoryPriorityFeaturesEXT`](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkPhysicalDeviceMemoryPriorityFeaturesEXT.html)
+/// and
+/// [`VkPhysicalDevicePageableDeviceLocalMemoryFeaturesEXT`](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkPhysicalDevicePageableDeviceLocalMemoryFeaturesEXT.html)
+/// manual pages.
+#[derive(Debug, Default)]
+pub struct MemoryPriorityFeatures {
+    /// Indicates that the implementation supports memory priorities specified at memory
+    /// allocation time.
+    pub memory_priority: bool,
+
+    /// Indicates that the implementation supports pageable device-local memory and may
+    /// transparently move device-local memory allocations to host-local memory to better share
+    /// device-local memory with other applications.
+    ///
+    /// When supported, memory priorities may be changed after allocation.
+    pub pageable_device_local_memory: bool,
+}
+
 /// Structure which holds data about the physical hardware selected by the current device.
 pub struct PhysicalDevice {
     /// Describes the properties of the device which relate to acceleration structures, if
     /// available.
     pub accel_struct_properties: Option<AccelerationStructureProperties>,
 
+    /// Describes the properties of the device which relate to conservative rasterization, if
+    /// available.
+    pub conservative_rasterization_properties: Option<ConservativeRasterizationProperties>,
+
     /// Describes the properties of the device which relate to depth/stencil resolve operations.
     pub depth_stencil_resolve_properties: DepthStencilResolveProperties,
 
+    /// Describes the features of the device which relate to extended dynamic state, if available.
+    pub extended_dynamic_state_features: ExtendedDynamicStateFeatures,
+
     /// Describes the features of the physical device which are part of the Vulkan 1.0 base feature set.
     pub features_v1_0: Vulkan10Features,
 
@@ -159,6 +271,9 @@ pub struct PhysicalDevice {
     /// Describes the features of the physical device which relate to vertex indexing.
     pub index_type_uint8_features: IndexTypeUint8Features,
 
+    /// Describes the features of the physical device which relate to memory priority.
+    pub memory_priority_features: MemoryPriorityFeatures,
+
     /// Memory properties of the physical device.
     pub memory_properties: vk::PhysicalDeviceMemoryProperties,
 
@@ -178,6 +293,9 @@ pub struct PhysicalDevice {
     /// Describes the queues offered by this physical device.
     pub queue_families: Box<[vk::QueueFamilyProperties]>,
 
+    /// Describes the properties of the device which relate to push descriptors, if available.
+    pub push_descriptor_properties: Option<PushDescriptorProperties>,
+
     pub(crate) queue_family_indices: Box<[u32]>,
 
     /// Describes the features of the device which relate to ray query, if available.
@@ -191,6 +309,10 @@ pub struct PhysicalDevice {
 
     /// Describes the properties of the device which relate to min/max sampler filtering.
     pub sampler_filter_minmax_properties: SamplerFilterMinmaxProperties,
+
+    pub(crate) supports_hdr_metadata: bool,
+    pub(crate) supports_memory_budget: bool,
+    pub(crate) supports_present_wait: bool,
 }
 
 impl PhysicalDevice {
@@ -230,14 +352,26 @@ impl PhysicalDevice {
         let mut features_v1_2 = vk::PhysicalDeviceVulkan12Features::default();
         let mut acceleration_structure_features =
             vk::PhysicalDeviceAccelerationStructureFeaturesKHR::default();
+        let mut extended_dynamic_state_features =
+            vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT::default();
         let mut index_type_u8_features = vk::PhysicalDeviceIndexTypeUint8FeaturesEXT::default();
+        let mut memory_priority_features = vk::PhysicalDeviceMemoryPriorityFeaturesEXT::default();
+        let mut pageable_device_local_memory_features =
+            vk::PhysicalDevicePageableDeviceLocalMemoryFeaturesEXT::default();
+        let mut present_id_features = vk::PhysicalDevicePresentIdFeaturesKHR::default();
+        let mut present_wait_features = vk::PhysicalDevicePresentWaitFeaturesKHR::default();
         let mut ray_query_features = vk::PhysicalDeviceRayQueryFeaturesKHR::default();
         let mut ray_trace_features = vk::PhysicalDeviceRayTracingPipelineFeaturesKHR::default();
         let mut features = vk::PhysicalDeviceFeatures2::default()
             .push_next(&mut features_v1_1)
             .push_next(&mut features_v1_2)
             .push_next(&mut acceleration_structure_features)
+            .push_next(&mut extended_dynamic_state_features)
             .push_next(&mut index_type_u8_features)
+            .push_next(&mut memory_priority_features)
+            .push_next(&mut pageable_device_local_memory_features)
+            .push_next(&mut present_id_features)
+            .push_next(&mut present_wait_features)
             .push_next(&mut ray_query_features)
             .push_next(&mut ray_trace_features);
         unsafe {
@@ -252,8 +386,12 @@ impl PhysicalDevice {
         let mut properties_v1_2 = vk::PhysicalDeviceVulkan12Properties::default();
         let mut accel_struct_properties =
             vk::PhysicalDeviceAccelerationStructurePropertiesKHR::default();
+        let mut conservative_rasterization_properties =
+            vk::PhysicalDeviceConservativeRasterizationPropertiesEXT::default();
         let mut depth_stencil_resolve_properties =
             vk::PhysicalDeviceDepthStencilResolveProperties::default();
+        let mut push_descriptor_properties =
+            vk::PhysicalDevicePushDescriptorPropertiesKHR::default();
         let mut ray_trace_properties = vk::PhysicalDeviceRayTracingPipelinePropertiesKHR::default();
         let mut sampler_filter_minmax_properties =
             vk::PhysicalDeviceSamplerFilterMinmaxProperties::default();
@@ -261,7 +399,9 @@ impl PhysicalDevice {
             .push_next(&mut properties_v1_1)
             .push_next(&mut properties_v1_2)
             .push_next(&mut accel_struct_properties)
+            .push_next(&mut conservative_rasterization_properties)
             .push_next(&mut depth_stencil_resolve_properties)
+            .push_next(&mut push_descriptor_properties)
             .push_next(&mut ray_trace_properties)
             .push_next(&mut sampler_filter_minmax_properties);
         unsafe {
@@ -306,14 +446,40 @@ impl PhysicalDevice {
             .collect::<HashSet<_>>();
         let supports_accel_struct = extensions.contains(khr::acceleration_structure::NAME)
             && extensions.contains(khr::deferred_host_operations::NAME);
+        let supports_conservative_rasterization =
+            extensions.contains(ext::conservative_rasterization::NAME);
+        let supports_extended_dynamic_state =
+            extensions.contains(ext::extended_dynamic_state::NAME);
+        let supports_hdr_metadata = extensions.contains(ext::hdr_metadata::NAME);
         let supports_index_type_uint8 = extensions.contains(ext::index_type_uint8::NAME);
+        let supports_memory_budget = extensions.contains(ext::memory_budget::NAME);
+        let supports_memory_priority = extensions.contains(ext::memory_priority::NAME);
+        let supports_pageable_device_local_memory = supports_memory_priority
+            && extensions.contains(ext::pageable_device_local_memory::NAME);
+        let supports_present_wait = extensions.contains(khr::present_id::NAME)
+            && extensions.contains(khr::present_wait::NAME)
+            && present_id_features.present_id == vk::TRUE
+            && present_wait_features.present_wait == vk::TRUE;
+        let supports_push_descriptor = extensions.contains(khr::push_descriptor::NAME);
         let supports_ray_query = extensions.contains(khr::ray_query::NAME);
         let supports_ray_trace = extensions.contains(khr::ray_tracing_pipeline::NAME);
 
         // Gather optional features and properties of the physical device
+        let extended_dynamic_state_features = if supports_extended_dynamic_state {
+            extended_dynamic_state_features.into()
+        } else {
+            Default::default()
+        };
         let index_type_uint8_features = supports_index_type_uint8
             .then(|| index_type_u8_features.into())
             .unwrap_or_default();
+        let memory_priority_features = MemoryPriorityFeatures {
+            memory_priority: supports_memory_priority
+                && memory_priority_features.memory_priority == vk::TRUE,
+            pageable_device_local_memory: supports_pageable_device_local_memory
+                && memory_priority_features.memory_priority == vk::TRUE
+                && pageable_device_local_memory_features.pageable_device_local_memory == vk::TRUE,
+        };
         let ray_query_features = supports_ray_query
             .then(|| ray_query_features.into())
             .unwrap_or_default();
@@ -321,26 +487,37 @@ impl PhysicalDevice {
             .then(|| ray_trace_features.into())
             .unwrap_or_default();
         let accel_struct_properties = supports_accel_struct.then(|| accel_struct_properties.into());
+        let conservative_rasterization_properties = supports_conservative_rasterization
+            .then(|| conservative_rasterization_properties.into());
+        let push_descriptor_properties =
+            supports_push_descriptor.then(|| push_descriptor_properties.into());
         let ray_trace_properties = supports_ray_trace.then(|| ray_trace_properties.into());
 
         Ok(Self {
             accel_struct_properties,
+            conservative_rasterization_properties,
             depth_stencil_resolve_properties,
+            extended_dynamic_state_features,
             features_v1_0,
             features_v1_1,
             features_v1_2,
             index_type_uint8_features,
+            memory_priority_features,
             memory_properties,
             physical_device,
             properties_v1_0,
             properties_v1_1,
             properties_v1_2,
+            push_descriptor_properties,
             queue_families,
             queue_family_indices,
             ray_query_features,
             ray_trace_features,
             ray_trace_properties,
             sampler_filter_minmax_properties,
+            supports_hdr_metadata,
+            supports_memory_budget,
+            supports_present_wait,
         })
     }
 }
@@ -363,6 +540,26 @@ impl Deref for PhysicalDevice {
     }
 }
 
+/// Properties of the physical device for push descriptors.
+///
+/// See
+/// [`VkPhysicalDevicePushDescriptorPropertiesKHR`](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkPhysicalDevicePushDescriptorPropertiesKHR.html)
+/// manual page.
+#[derive(Debug)]
+pub struct PushDescriptorProperties {
+    /// The maximum number of descriptors that can be used in a descriptor set layout created with
+    /// `VK_DESCRIPTOR_SET_LAYOUT_CREATE_PUSH_DESCRIPTOR_BIT_KHR` set.
+    pub max_push_descriptors: u32,
+}
+
+impl From<vk::PhysicalDevicePushDescriptorPropertiesKHR<'_>> for PushDescriptorProperties {
+    fn from(props: vk::PhysicalDevicePushDescriptorPropertiesKHR<'_>) -> Self {
+        Self {
+            max_push_descriptors: props.max_push_descriptors,
+        }
+    }
+}
+
 /// Features of the physical device for ray query.
 ///
 /// See
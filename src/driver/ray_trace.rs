@@ -3,15 +3,17 @@
 use {
     super::{
         DriverError,
+        bindless::BindlessHeapInfo,
+        buffer::{Buffer, BufferInfo},
         device::Device,
         merge_push_constant_ranges,
         physical_device::RayTraceProperties,
-        shader::{DescriptorBindingMap, PipelineDescriptorInfo, Shader, align_spriv},
+        shader::{DescriptorBindingMap, PipelineDescriptorInfo, SamplerTable, Shader, align_spriv},
     },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
     log::warn,
-    std::{ffi::CString, ops::Deref, sync::Arc, thread::panicking},
+    std::{ffi::CString, ops::Deref, slice, sync::Arc, thread::panicking},
 };
 
 /// Smart pointer handle to a [pipeline] object.
@@ -108,6 +110,33 @@ impl RayTracePipeline {
         shaders: impl IntoIterator<Item = S>,
         shader_groups: impl IntoIterator<Item = RayTraceShaderGroup>,
     ) -> Result<Self, DriverError>
+    where
+        S: Into<Shader>,
+    {
+        Self::create_with_samplers(
+            device,
+            info,
+            shaders,
+            shader_groups,
+            &SamplerTable::default(),
+        )
+    }
+
+    /// Creates a new ray trace pipeline on the given device using explicitly assigned samplers.
+    ///
+    /// See [`SamplerTable`] for details.
+    ///
+    /// # Panics
+    ///
+    /// If shader code is not a multiple of four bytes.
+    #[profiling::function]
+    pub fn create_with_samplers<S>(
+        device: &Arc<Device>,
+        info: impl Into<RayTracePipelineInfo>,
+        shaders: impl IntoIterator<Item = S>,
+        shader_groups: impl IntoIterator<Item = RayTraceShaderGroup>,
+        samplers: &SamplerTable,
+    ) -> Result<Self, DriverError>
     where
         S: Into<Shader>,
     {
@@ -130,7 +159,10 @@ impl RayTracePipeline {
 
         // Use SPIR-V reflection to get the types and counts of all descriptors
         let mut descriptor_bindings = Shader::merge_descriptor_bindings(
-            shaders.iter().map(|shader| shader.descriptor_bindings()),
+            shaders
+                .iter()
+                .map(|shader| shader.descriptor_bindings(samplers))
+                .collect::<Result<Vec<_>, _>>()?,
         );
         for (descriptor_info, _) in descriptor_bindings.values_mut() {
             if descriptor_info.binding_count() == 0 {
@@ -138,7 +170,8 @@ impl RayTracePipeline {
             }
         }
 
-        let descriptor_info = PipelineDescriptorInfo::create(device, &descriptor_bindings)?;
+        let descriptor_info =
+            PipelineDescriptorInfo::create(device, &descriptor_bindings, None, info.bindless_heap)?;
         let descriptor_set_layout_handles = descriptor_info
             .layouts
             .values()
@@ -425,6 +458,20 @@ pub struct RayTracePipelineInfo {
     #[builder(default = "8192")]
     pub bindless_descriptor_count: u32,
 
+    /// The descriptor set index and information of a [`BindlessHeap`] which is accessed by this
+    /// pipeline.
+    ///
+    /// The descriptor set layout is created from the heap information instead of shader
+    /// reflection, so any heap created using the same information may be bound to passes which use
+    /// this pipeline using [`PipelinePassRef::bind_bindless_heap`].
+    ///
+    /// The default is `None`.
+    ///
+    /// [`BindlessHeap`]: super::bindless::BindlessHeap
+    /// [`PipelinePassRef::bind_bindless_heap`]: crate::graph::pass_ref::PipelinePassRef::bind_bindless_heap
+    #[builder(default, setter(custom))]
+    pub bindless_heap: Option<(u32, BindlessHeapInfo)>,
+
     /// Allow [setting the stack size dynamically] for a ray trace pipeline.
     ///
     /// When set, you must manually set the stack size during ray trace passes using
@@ -454,6 +501,7 @@ impl RayTracePipelineInfo {
     pub fn to_builder(self) -> RayTracePipelineInfoBuilder {
         RayTracePipelineInfoBuilder {
             bindless_descriptor_count: Some(self.bindless_descriptor_count),
+            bindless_heap: Some(self.bindless_heap),
             dynamic_stack_size: Some(self.dynamic_stack_size),
             max_ray_recursion_depth: Some(self.max_ray_recursion_depth),
         }
@@ -464,6 +512,7 @@ impl Default for RayTracePipelineInfo {
     fn default() -> Self {
         Self {
             bindless_descriptor_count: 8192,
+            bindless_heap: None,
             dynamic_stack_size: false,
             max_ray_recursion_depth: 16,
         }
@@ -477,6 +526,19 @@ impl From<RayTracePipelineInfoBuilder> for RayTracePipelineInfo {
 }
 
 impl RayTracePipelineInfoBuilder {
+    /// Sets the descriptor set index of a [`BindlessHeap`] created using `info` which is accessed
+    /// by this pipeline.
+    ///
+    /// [`BindlessHeap`]: super::bindless::BindlessHeap
+    pub fn bindless_heap(
+        mut self,
+        descriptor_set_idx: u32,
+        info: impl Into<BindlessHeapInfo>,
+    ) -> Self {
+        self.bindless_heap = Some(Some((descriptor_set_idx, info.into())));
+        self
+    }
+
     /// Builds a new `RayTracePipelineInfo`.
     #[inline(always)]
     pub fn build(self) -> RayTracePipelineInfo {
@@ -639,6 +701,290 @@ impl From<RayTraceShaderGroupType> for vk::RayTracingShaderGroupTypeKHR {
     }
 }
 
+/// A buffer of shader group handles and optional user data which is used to select the shaders
+/// run by [`RayTrace::trace_rays`](crate::graph::pass_ref::RayTrace::trace_rays).
+///
+/// Records are aligned according to the [`RayTraceProperties`] of the device and each region
+/// uses the stride of its largest record.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use screen_13::driver::DriverError;
+/// # use screen_13::driver::device::{Device, DeviceInfo};
+/// # use screen_13::driver::ray_trace::{RayTracePipeline, RayTracePipelineInfo, RayTraceShaderGroup, ShaderBindingTable, ShaderBindingTableInfo, ShaderBindingTableRecord};
+/// # use screen_13::driver::shader::Shader;
+/// # fn main() -> Result<(), DriverError> {
+/// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+/// # let my_rgen_code = [0u8; 1];
+/// # let my_rchit_code = [0u8; 1];
+/// # let my_rmiss_code = [0u8; 1];
+/// # let my_pipeline = RayTracePipeline::create(
+/// #     &device,
+/// #     RayTracePipelineInfo::default(),
+/// #     [
+/// #         Shader::new_ray_gen(my_rgen_code.as_slice()),
+/// #         Shader::new_closest_hit(my_rchit_code.as_slice()),
+/// #         Shader::new_miss(my_rmiss_code.as_slice()),
+/// #     ],
+/// #     [
+/// #         RayTraceShaderGroup::new_general(0),
+/// #         RayTraceShaderGroup::new_triangles(1, None),
+/// #         RayTraceShaderGroup::new_general(2),
+/// #     ],
+/// # )?;
+/// let info = ShaderBindingTableInfo::new(0)
+///     .hit(ShaderBindingTableRecord::new(1).data([0xff; 16]))
+///     .miss(2);
+/// let sbt = ShaderBindingTable::create(&device, &my_pipeline, &info)?;
+///
+/// assert_eq!(sbt.ray_gen.size, sbt.ray_gen.stride);
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct ShaderBindingTable {
+    /// The buffer which stores the records of this table.
+    ///
+    /// The buffer must be bound to any render graph which traces rays using this table.
+    pub buffer: Arc<Buffer>,
+
+    /// The region of callable shader records, which is empty if no callable records were provided.
+    pub callable: vk::StridedDeviceAddressRegionKHR,
+
+    /// The region of hit group records, which is empty if no hit records were provided.
+    pub hit: vk::StridedDeviceAddressRegionKHR,
+
+    /// The region of miss shader records, which is empty if no miss records were provided.
+    pub miss: vk::StridedDeviceAddressRegionKHR,
+
+    /// The region of the ray generation shader record.
+    pub ray_gen: vk::StridedDeviceAddressRegionKHR,
+}
+
+impl ShaderBindingTable {
+    /// Creates a new shader binding table for the shader groups of the given pipeline.
+    ///
+    /// Returns [`DriverError::InvalidData`] if a record refers to a shader group which does not
+    /// exist or a record exceeds `max_shader_group_stride` of [`RayTraceProperties`].
+    #[profiling::function]
+    pub fn create(
+        device: &Arc<Device>,
+        pipeline: &RayTracePipeline,
+        info: &ShaderBindingTableInfo,
+    ) -> Result<Self, DriverError> {
+        let &RayTraceProperties {
+            max_shader_group_stride,
+            shader_group_base_alignment,
+            shader_group_handle_alignment,
+            shader_group_handle_size,
+            ..
+        } = device
+            .physical_device
+            .ray_trace_properties
+            .as_ref()
+            .ok_or(DriverError::Unsupported)?;
+
+        let group_count = pipeline.shader_group_handles.len() / shader_group_handle_size as usize;
+        let records = [
+            slice::from_ref(&info.ray_gen),
+            info.miss.as_slice(),
+            info.hit.as_slice(),
+            info.callable.as_slice(),
+        ];
+
+        for record in records.iter().copied().flatten() {
+            if record.group as usize >= group_count {
+                warn!("invalid shader group: {}", record.group);
+
+                return Err(DriverError::InvalidData);
+            }
+        }
+
+        let mut offset = 0;
+        let regions = records.map(|records| {
+            let region = shader_binding_table_region(
+                offset,
+                records,
+                shader_group_handle_size,
+                shader_group_handle_alignment,
+                shader_group_base_alignment,
+            );
+            offset =
+                (region.offset + region.size).next_multiple_of(shader_group_base_alignment as _);
+
+            region
+        });
+
+        if regions
+            .iter()
+            .any(|region| region.stride > max_shader_group_stride as vk::DeviceSize)
+        {
+            warn!("shader binding table record exceeds max_shader_group_stride");
+
+            return Err(DriverError::InvalidData);
+        }
+
+        let mut buffer = Buffer::create(
+            device,
+            BufferInfo::host_mem(
+                offset,
+                vk::BufferUsageFlags::SHADER_BINDING_TABLE_KHR
+                    | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS,
+            )
+            .to_builder()
+            .alignment(shader_group_base_alignment as _),
+        )?;
+
+        {
+            let data = Buffer::mapped_slice_mut(&mut buffer);
+
+            for (region, records) in regions.iter().zip(records) {
+                for (idx, record) in records.iter().enumerate() {
+                    let start = (region.offset + idx as vk::DeviceSize * region.stride) as usize;
+                    let handle = RayTracePipeline::group_handle(pipeline, record.group as _)?;
+                    let data = &mut data[start..start + region.stride as usize];
+                    let (handle_data, user_data) = data.split_at_mut(handle.len());
+                    handle_data.copy_from_slice(handle);
+                    user_data[..record.data.len()].copy_from_slice(&record.data);
+                }
+            }
+        }
+
+        let device_address = Buffer::device_address(&buffer);
+        let [ray_gen, miss, hit, callable] = regions.map(|region| {
+            if region.size == 0 {
+                return vk::StridedDeviceAddressRegionKHR::default();
+            }
+
+            vk::StridedDeviceAddressRegionKHR {
+                device_address: device_address + region.offset,
+                stride: region.stride,
+                size: region.size,
+            }
+        });
+
+        Ok(Self {
+            buffer: Arc::new(buffer),
+            callable,
+            hit,
+            miss,
+            ray_gen,
+        })
+    }
+}
+
+/// Information used to create a [`ShaderBindingTable`] instance.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ShaderBindingTableInfo {
+    /// The callable shader records.
+    pub callable: Vec<ShaderBindingTableRecord>,
+
+    /// The hit group records.
+    pub hit: Vec<ShaderBindingTableRecord>,
+
+    /// The miss shader records.
+    pub miss: Vec<ShaderBindingTableRecord>,
+
+    /// The ray generation shader record.
+    pub ray_gen: ShaderBindingTableRecord,
+}
+
+impl ShaderBindingTableInfo {
+    /// Specifies a shader binding table with the given ray generation shader record.
+    pub fn new(ray_gen: impl Into<ShaderBindingTableRecord>) -> Self {
+        Self {
+            ray_gen: ray_gen.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Appends a callable shader record.
+    pub fn callable(mut self, record: impl Into<ShaderBindingTableRecord>) -> Self {
+        self.callable.push(record.into());
+        self
+    }
+
+    /// Appends a hit group record.
+    pub fn hit(mut self, record: impl Into<ShaderBindingTableRecord>) -> Self {
+        self.hit.push(record.into());
+        self
+    }
+
+    /// Appends a miss shader record.
+    pub fn miss(mut self, record: impl Into<ShaderBindingTableRecord>) -> Self {
+        self.miss.push(record.into());
+        self
+    }
+}
+
+/// A shader group handle and optional user data stored in a [`ShaderBindingTable`].
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ShaderBindingTableRecord {
+    /// User data which follows the shader group handle and may be read by shaders as a
+    /// `shaderRecordEXT` buffer block.
+    pub data: Vec<u8>,
+
+    /// The index of the shader group in the pipeline.
+    pub group: u32,
+}
+
+impl ShaderBindingTableRecord {
+    /// Specifies a record of the given shader group without user data.
+    pub fn new(group: u32) -> Self {
+        Self {
+            data: vec![],
+            group,
+        }
+    }
+
+    /// Sets the user data of this record.
+    pub fn data(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.data = data.into();
+        self
+    }
+}
+
+impl From<u32> for ShaderBindingTableRecord {
+    fn from(group: u32) -> Self {
+        Self::new(group)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct ShaderBindingTableRegion {
+    offset: vk::DeviceSize,
+    size: vk::DeviceSize,
+    stride: vk::DeviceSize,
+}
+
+/// Returns the layout of a region of records which begins at the given aligned offset.
+fn shader_binding_table_region(
+    offset: vk::DeviceSize,
+    records: &[ShaderBindingTableRecord],
+    handle_size: u32,
+    handle_alignment: u32,
+    base_alignment: u32,
+) -> ShaderBindingTableRegion {
+    let offset = offset.next_multiple_of(base_alignment as _);
+    let data_len = records
+        .iter()
+        .map(|record| record.data.len())
+        .max()
+        .unwrap_or_default();
+    let stride = (handle_size as vk::DeviceSize + data_len as vk::DeviceSize)
+        .next_multiple_of(handle_alignment as _);
+    let size = records.len() as vk::DeviceSize * stride;
+
+    ShaderBindingTableRegion {
+        offset,
+        size,
+        stride,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -661,4 +1007,26 @@ mod tests {
 
         assert_eq!(info, builder);
     }
+
+    #[test]
+    pub fn shader_binding_table_region() {
+        let records = [
+            ShaderBindingTableRecord::new(0),
+            ShaderBindingTableRecord::new(1).data([0; 12]),
+        ];
+        let region = super::shader_binding_table_region(40, &records, 32, 16, 64);
+
+        assert_eq!(
+            region,
+            ShaderBindingTableRegion {
+                offset: 64,
+                size: 96,
+                stride: 48,
+            }
+        );
+
+        let region = super::shader_binding_table_region(0, &[], 32, 16, 64);
+
+        assert_eq!(region.size, 0);
+    }
 }
@@ -339,11 +339,27 @@ impl RenderPass {
         let color_blend_attachment_states = this.info.subpasses[subpass_idx as usize]
             .color_attachments
             .iter()
-            .map(|_| pipeline.info.blend.into_vk())
+            .enumerate()
+            .map(|(attachment_idx, _)| {
+                pipeline
+                    .info
+                    .attachment_blend(attachment_idx as u32)
+                    .into_vk()
+            })
             .collect::<Box<[_]>>();
         let color_blend_state = vk::PipelineColorBlendStateCreateInfo::default()
-            .attachments(&color_blend_attachment_states);
-        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+            .attachments(&color_blend_attachment_states)
+            .blend_constants(pipeline.info.blend_constants.map(|constant| constant.0))
+            .logic_op_enable(pipeline.info.logic_op.is_some())
+            .logic_op(pipeline.info.logic_op.unwrap_or_default());
+        let mut dynamic_states = vec![vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+
+        if pipeline.info.dynamic_depth_bias {
+            dynamic_states.push(vk::DynamicState::DEPTH_BIAS);
+        }
+
+        dynamic_states.extend(pipeline.info.extended_dynamic_state.dynamic_states());
+
         let dynamic_state =
             vk::PipelineDynamicStateCreateInfo::default().dynamic_states(&dynamic_states);
         let multisample_state = vk::PipelineMultisampleStateCreateInfo::default()
@@ -396,19 +412,37 @@ impl RenderPass {
             .viewport_count(1)
             .scissor_count(1);
         let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo {
+            primitive_restart_enable: pipeline.info.primitive_restart as _,
             topology: pipeline.info.topology,
             ..Default::default()
         };
         let depth_stencil = depth_stencil
             .map(|depth_stencil| depth_stencil.into_vk())
             .unwrap_or_default();
-        let rasterization_state = vk::PipelineRasterizationStateCreateInfo {
+        let depth_bias = pipeline.info.depth_bias.unwrap_or_default();
+        let mut rasterization_state = vk::PipelineRasterizationStateCreateInfo {
             front_face: pipeline.info.front_face,
-            line_width: 1.0,
+            line_width: pipeline.info.line_width.0,
             polygon_mode: pipeline.info.polygon_mode,
             cull_mode: pipeline.info.cull_mode,
+            depth_bias_enable: (pipeline.info.depth_bias.is_some()
+                || pipeline.info.dynamic_depth_bias) as _,
+            depth_bias_constant_factor: depth_bias.constant_factor.0,
+            depth_bias_clamp: depth_bias.clamp.0,
+            depth_bias_slope_factor: depth_bias.slope_factor.0,
+            depth_clamp_enable: pipeline.info.depth_clamp as _,
             ..Default::default()
         };
+        let mut conservative_rasterization_state =
+            vk::PipelineRasterizationConservativeStateCreateInfoEXT::default();
+
+        if let Some(mode) = pipeline.info.conservative_rasterization {
+            conservative_rasterization_state =
+                conservative_rasterization_state.conservative_rasterization_mode(mode);
+            rasterization_state =
+                rasterization_state.push_next(&mut conservative_rasterization_state);
+        }
+
         let graphic_pipeline_info = vk::GraphicsPipelineCreateInfo::default()
             .color_blend_state(&color_blend_state)
             .depth_stencil_state(&depth_stencil)
@@ -1,7 +1,11 @@
 //! Shader resource types
 
 use {
-    super::{DescriptorSetLayout, DriverError, VertexInputState, device::Device},
+    super::{
+        DescriptorSetLayout, DriverError, VertexInputState,
+        bindless::{self, BindlessHeap, BindlessHeapInfo},
+        device::Device,
+    },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
     log::{debug, error, trace, warn},
@@ -38,41 +42,50 @@ pub(crate) fn align_spriv(code: &[u8]) -> Result<&[u32], DriverError> {
 }
 
 #[profiling::function]
-fn guess_immutable_sampler(binding_name: &str) -> SamplerInfo {
-    const INVALID_ERR: &str = "Invalid sampler specification";
-
-    let (texel_filter, mipmap_mode, address_modes) = if binding_name.contains("_sampler_") {
-        let spec = &binding_name[binding_name.len() - 3..];
-        let texel_filter = match &spec[0..1] {
-            "n" => vk::Filter::NEAREST,
-            "l" => vk::Filter::LINEAR,
-            _ => panic!("{INVALID_ERR}: {}", &spec[0..1]),
-        };
+fn guess_immutable_sampler(binding_name: &str) -> Result<SamplerInfo, DriverError> {
+    let (texel_filter, mipmap_mode, address_modes) =
+        if let Some(idx) = binding_name.rfind("_sampler_") {
+            let spec = &binding_name.as_bytes()[idx + "_sampler_".len()..];
+            let invalid_spec = || {
+                warn!("Invalid sampler specification: {binding_name}");
 
-        let mipmap_mode = match &spec[1..2] {
-            "n" => vk::SamplerMipmapMode::NEAREST,
-            "l" => vk::SamplerMipmapMode::LINEAR,
-            _ => panic!("{INVALID_ERR}: {}", &spec[1..2]),
-        };
+                DriverError::InvalidData
+            };
 
-        let address_modes = match &spec[2..3] {
-            "b" => vk::SamplerAddressMode::CLAMP_TO_BORDER,
-            "e" => vk::SamplerAddressMode::CLAMP_TO_EDGE,
-            "m" => vk::SamplerAddressMode::MIRRORED_REPEAT,
-            "r" => vk::SamplerAddressMode::REPEAT,
-            _ => panic!("{INVALID_ERR}: {}", &spec[2..3]),
-        };
+            let &[texel_filter, mipmap_mode, address_modes] = spec else {
+                return Err(invalid_spec());
+            };
 
-        (texel_filter, mipmap_mode, address_modes)
-    } else {
-        debug!("image binding {binding_name} using default sampler");
+            let texel_filter = match texel_filter {
+                b'n' => vk::Filter::NEAREST,
+                b'l' => vk::Filter::LINEAR,
+                _ => return Err(invalid_spec()),
+            };
 
-        (
-            vk::Filter::LINEAR,
-            vk::SamplerMipmapMode::LINEAR,
-            vk::SamplerAddressMode::REPEAT,
-        )
-    };
+            let mipmap_mode = match mipmap_mode {
+                b'n' => vk::SamplerMipmapMode::NEAREST,
+                b'l' => vk::SamplerMipmapMode::LINEAR,
+                _ => return Err(invalid_spec()),
+            };
+
+            let address_modes = match address_modes {
+                b'b' => vk::SamplerAddressMode::CLAMP_TO_BORDER,
+                b'e' => vk::SamplerAddressMode::CLAMP_TO_EDGE,
+                b'm' => vk::SamplerAddressMode::MIRRORED_REPEAT,
+                b'r' => vk::SamplerAddressMode::REPEAT,
+                _ => return Err(invalid_spec()),
+            };
+
+            (texel_filter, mipmap_mode, address_modes)
+        } else {
+            debug!("image binding {binding_name} using default sampler");
+
+            (
+                vk::Filter::LINEAR,
+                vk::SamplerMipmapMode::LINEAR,
+                vk::SamplerAddressMode::REPEAT,
+            )
+        };
     let anisotropy_enable = texel_filter == vk::Filter::LINEAR;
     let mut info = SamplerInfoBuilder::default()
         .mag_filter(texel_filter)
@@ -88,7 +101,7 @@ fn guess_immutable_sampler(binding_name: &str) -> SamplerInfo {
         info = info.max_anisotropy(16.0);
     }
 
-    info.build()
+    Ok(info.build())
 }
 
 /// Tuple of descriptor set index and binding index.
@@ -119,10 +132,10 @@ impl From<(u32, u32)> for Descriptor {
 #[derive(Clone, Copy, Debug)]
 pub(crate) enum DescriptorInfo {
     AccelerationStructure(u32),
-    CombinedImageSampler(u32, SamplerInfo, bool), //count, sampler, is-manually-defined?
-    InputAttachment(u32, u32),                    //count, input index,
+    CombinedImageSampler(u32, SamplerBinding, bool), //count, sampler, is-manually-defined?
+    InputAttachment(u32, u32),                       //count, input index,
     SampledImage(u32),
-    Sampler(u32, SamplerInfo, bool), //count, sampler, is-manually-defined?
+    Sampler(u32, SamplerBinding, bool), //count, sampler, is-manually-defined?
     StorageBuffer(u32),
     StorageImage(u32),
     StorageTexelBuffer(u32),
@@ -161,11 +174,20 @@ impl DescriptorInfo {
         }
     }
 
+    /// Returns `true` if this is a sampler or combined image sampler descriptor which is not
+    /// backed by an immutable sampler.
+    pub fn is_mutable_sampler(self) -> bool {
+        matches!(
+            self,
+            Self::CombinedImageSampler(_, SamplerBinding::Mutable, _)
+                | Self::Sampler(_, SamplerBinding::Mutable, _)
+        )
+    }
+
     fn sampler_info(self) -> Option<SamplerInfo> {
         match self {
-            Self::CombinedImageSampler(_, sampler_info, _) | Self::Sampler(_, sampler_info, _) => {
-                Some(sampler_info)
-            }
+            Self::CombinedImageSampler(_, SamplerBinding::Immutable(sampler_info), _)
+            | Self::Sampler(_, SamplerBinding::Immutable(sampler_info), _) => Some(sampler_info),
             _ => None,
         }
     }
@@ -190,6 +212,8 @@ impl DescriptorInfo {
 pub(crate) struct PipelineDescriptorInfo {
     pub layouts: BTreeMap<u32, DescriptorSetLayout>,
     pub pool_sizes: HashMap<u32, HashMap<vk::DescriptorType, u32>>,
+    pub push_descriptor_set: Option<u32>,
+    pub bindless_heap: Option<(u32, BindlessHeapInfo)>,
 
     #[allow(dead_code)]
     samplers: Box<[Sampler]>,
@@ -200,10 +224,22 @@ impl PipelineDescriptorInfo {
     pub fn create(
         device: &Arc<Device>,
         descriptor_bindings: &DescriptorBindingMap,
+        push_descriptor_set: Option<u32>,
+        bindless_heap: Option<(u32, BindlessHeapInfo)>,
     ) -> Result<Self, DriverError> {
+        // Push descriptor sets are bound after all other sets
+        if push_descriptor_set.zip(bindless_heap).is_some_and(
+            |(push_descriptor_set, (bindless_set_idx, _))| bindless_set_idx >= push_descriptor_set,
+        ) {
+            warn!("bindless heap set must be less than push descriptor set");
+
+            return Err(DriverError::InvalidData);
+        }
+
         let descriptor_set_count = descriptor_bindings
             .keys()
             .map(|descriptor| descriptor.set)
+            .chain(bindless_heap.map(|(descriptor_set_idx, _)| descriptor_set_idx))
             .max()
             .map(|set| set + 1)
             .unwrap_or_default();
@@ -254,6 +290,20 @@ impl PipelineDescriptorInfo {
             .collect::<HashMap<_, _>>();
 
         for descriptor_set_idx in 0..descriptor_set_count {
+            // Bindless heap sets are owned by the heap and never allocated from a pool
+            if let Some((_, info)) = bindless_heap
+                .filter(|&(bindless_set_idx, _)| bindless_set_idx == descriptor_set_idx)
+            {
+                Self::validate_bindless_heap(descriptor_bindings, descriptor_set_idx)?;
+
+                layouts.insert(
+                    descriptor_set_idx,
+                    bindless::create_descriptor_set_layout(device, info)?,
+                );
+
+                continue;
+            }
+
             let mut binding_counts = HashMap::<vk::DescriptorType, u32>::new();
             let mut bindings = vec![];
 
@@ -282,18 +332,28 @@ impl PipelineDescriptorInfo {
                 bindings.push(binding);
             }
 
-            let pool_size = pool_sizes
-                .entry(descriptor_set_idx)
-                .or_insert_with(HashMap::new);
+            let is_push_descriptor_set = push_descriptor_set == Some(descriptor_set_idx);
+
+            // Push descriptor sets are never allocated from a pool
+            if !is_push_descriptor_set {
+                let pool_size = pool_sizes
+                    .entry(descriptor_set_idx)
+                    .or_insert_with(HashMap::new);
 
-            for (descriptor_ty, binding_count) in binding_counts.into_iter() {
-                *pool_size.entry(descriptor_ty).or_default() += binding_count;
+                for (descriptor_ty, binding_count) in binding_counts.into_iter() {
+                    *pool_size.entry(descriptor_ty).or_default() += binding_count;
+                }
             }
 
             //trace!("bindings: {:#?}", &bindings);
 
             let mut create_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
 
+            if is_push_descriptor_set {
+                create_info =
+                    create_info.flags(vk::DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR_KHR);
+            }
+
             // The bindless flags have to be created for every descriptor set layout binding.
             // [vulkan spec](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VkDescriptorSetLayoutBindingFlagsCreateInfo.html)
             // Maybe using one vector and updating it would be more efficient.
@@ -331,9 +391,49 @@ impl PipelineDescriptorInfo {
         Ok(Self {
             layouts,
             pool_sizes,
+            push_descriptor_set,
+            bindless_heap,
             samplers,
         })
     }
+
+    fn validate_bindless_heap(
+        descriptor_bindings: &DescriptorBindingMap,
+        descriptor_set_idx: u32,
+    ) -> Result<(), DriverError> {
+        for (descriptor, (descriptor_info, _)) in descriptor_bindings
+            .iter()
+            .filter(|(descriptor, _)| descriptor.set == descriptor_set_idx)
+        {
+            let expected_ty = match descriptor.binding {
+                BindlessHeap::SAMPLED_IMAGE_BINDING => vk::DescriptorType::SAMPLED_IMAGE,
+                BindlessHeap::STORAGE_IMAGE_BINDING => vk::DescriptorType::STORAGE_IMAGE,
+                BindlessHeap::STORAGE_BUFFER_BINDING => vk::DescriptorType::STORAGE_BUFFER,
+                BindlessHeap::SAMPLER_BINDING => vk::DescriptorType::SAMPLER,
+                _ => {
+                    warn!(
+                        "invalid bindless heap binding {}.{}",
+                        descriptor.set, descriptor.binding
+                    );
+
+                    return Err(DriverError::InvalidData);
+                }
+            };
+
+            if descriptor_info.descriptor_type() != expected_ty {
+                warn!(
+                    "invalid bindless heap descriptor type {}.{}: {:?}",
+                    descriptor.set,
+                    descriptor.binding,
+                    descriptor_info.descriptor_type()
+                );
+
+                return Err(DriverError::InvalidData);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 pub(crate) struct Sampler {
@@ -345,9 +445,19 @@ impl Sampler {
     #[profiling::function]
     pub fn create(device: &Arc<Device>, info: impl Into<SamplerInfo>) -> Result<Self, DriverError> {
         let device = Arc::clone(device);
-        let info = info.into();
+        let sampler = Self::create_handle(&device, info.into())?;
 
-        let sampler = unsafe {
+        Ok(Self { device, sampler })
+    }
+
+    /// Creates a sampler handle which is not owned by a `Sampler` instance; the caller is
+    /// responsible for destroying it.
+    #[profiling::function]
+    pub(super) fn create_handle(
+        device: &Device,
+        info: SamplerInfo,
+    ) -> Result<vk::Sampler, DriverError> {
+        unsafe {
             device
                 .create_sampler(
                     &vk::SamplerCreateInfo::default()
@@ -381,10 +491,8 @@ impl Sampler {
                         | vk::Result::ERROR_OUT_OF_DEVICE_MEMORY => DriverError::OutOfMemory,
                         _ => DriverError::Unsupported,
                     }
-                })?
-        };
-
-        Ok(Self { device, sampler })
+                })
+        }
     }
 }
 
@@ -415,6 +523,31 @@ impl Drop for Sampler {
     }
 }
 
+/// Specifies how the sampler of a sampler or combined image sampler descriptor is provided.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SamplerBinding {
+    /// The sampler is created along with the pipeline and is part of the descriptor set layout.
+    Immutable(SamplerInfo),
+
+    /// The sampler is not part of the descriptor set layout and must be bound to each recorded
+    /// execution using [`PipelinePassRef::bind_sampler`].
+    ///
+    /// [`PipelinePassRef::bind_sampler`]: crate::graph::pass_ref::PipelinePassRef::bind_sampler
+    Mutable,
+}
+
+impl From<SamplerInfo> for SamplerBinding {
+    fn from(info: SamplerInfo) -> Self {
+        Self::Immutable(info)
+    }
+}
+
+impl From<SamplerInfoBuilder> for SamplerBinding {
+    fn from(info: SamplerInfoBuilder) -> Self {
+        Self::Immutable(info.build())
+    }
+}
+
 /// Information used to create a [`vk::Sampler`] instance.
 #[derive(Builder, Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[builder(
@@ -670,6 +803,112 @@ impl From<UninitializedFieldError> for SamplerInfoBuilderError {
     }
 }
 
+/// Identifies a sampler or combined image sampler descriptor within a [`SamplerTable`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SamplerKey {
+    /// A descriptor set index and binding index.
+    Descriptor(Descriptor),
+
+    /// The name of the descriptor as declared in shader code.
+    Name(String),
+}
+
+impl From<Descriptor> for SamplerKey {
+    fn from(descriptor: Descriptor) -> Self {
+        Self::Descriptor(descriptor)
+    }
+}
+
+impl From<u32> for SamplerKey {
+    fn from(binding: u32) -> Self {
+        Self::Descriptor(binding.into())
+    }
+}
+
+impl From<(u32, u32)> for SamplerKey {
+    fn from(descriptor: (u32, u32)) -> Self {
+        Self::Descriptor(descriptor.into())
+    }
+}
+
+impl From<&str> for SamplerKey {
+    fn from(name: &str) -> Self {
+        Self::Name(name.to_owned())
+    }
+}
+
+impl From<String> for SamplerKey {
+    fn from(name: String) -> Self {
+        Self::Name(name)
+    }
+}
+
+/// A pipeline-level table which explicitly assigns samplers to sampler and combined image sampler
+/// descriptors.
+///
+/// Samplers specified using [`ShaderBuilder::image_sampler`] take precedence over this table, and
+/// this table takes precedence over the `_sampler_xyz` binding name suffix described in the
+/// [main documentation]. Entries keyed by descriptor take precedence over entries keyed by name.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use ash::vk;
+/// # use screen_13::driver::DriverError;
+/// # use screen_13::driver::device::{Device, DeviceInfo};
+/// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+/// # use screen_13::driver::shader::{SamplerBinding, SamplerInfo, SamplerTable, Shader};
+/// # fn main() -> Result<(), DriverError> {
+/// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+/// # let my_shader_code = [0u8; 1];
+/// let samplers = SamplerTable::default()
+///     .sampler("my_texture", SamplerInfo::NEAREST)
+///     .sampler((1, 2), SamplerBinding::Mutable);
+/// let shader = Shader::new_compute(my_shader_code.as_slice());
+/// let pipeline = ComputePipeline::create_with_samplers(
+///     &device,
+///     ComputePipelineInfo::default(),
+///     shader,
+///     &samplers,
+/// )?;
+/// # Ok(()) }
+/// ```
+///
+/// [main documentation]: crate
+#[derive(Clone, Debug, Default)]
+pub struct SamplerTable {
+    descriptors: HashMap<Descriptor, SamplerBinding>,
+    names: HashMap<String, SamplerBinding>,
+}
+
+impl SamplerTable {
+    /// Assigns a sampler to the descriptor specified by `key`.
+    pub fn sampler(
+        mut self,
+        key: impl Into<SamplerKey>,
+        binding: impl Into<SamplerBinding>,
+    ) -> Self {
+        let binding = binding.into();
+
+        match key.into() {
+            SamplerKey::Descriptor(descriptor) => self.descriptors.insert(descriptor, binding),
+            SamplerKey::Name(name) => self.names.insert(name, binding),
+        };
+
+        self
+    }
+
+    fn get(&self, descriptor: Descriptor, name: &str) -> Option<SamplerBinding> {
+        self.descriptors
+            .get(&descriptor)
+            .or_else(|| self.names.get(name))
+            .copied()
+    }
+}
+
 /// Describes a shader program which runs on some pipeline stage.
 #[allow(missing_docs)]
 #[derive(Builder, Clone)]
@@ -745,7 +984,7 @@ pub struct Shader {
     entry_point: EntryPoint,
 
     #[builder(default, private)]
-    image_samplers: HashMap<Descriptor, SamplerInfo>,
+    image_samplers: HashMap<Descriptor, SamplerBinding>,
 
     #[builder(default, private, setter(strip_option))]
     vertex_input_state: Option<VertexInputState>,
@@ -910,7 +1149,10 @@ impl Shader {
     }
 
     #[profiling::function]
-    pub(super) fn descriptor_bindings(&self) -> DescriptorBindingMap {
+    pub(super) fn descriptor_bindings(
+        &self,
+        samplers: &SamplerTable,
+    ) -> Result<DescriptorBindingMap, DriverError> {
         let mut res = DescriptorBindingMap::default();
 
         for (name, descriptor, desc_ty, binding_count) in
@@ -947,12 +1189,15 @@ impl Shader {
                     DescriptorInfo::AccelerationStructure(binding_count)
                 }
                 DescriptorType::CombinedImageSampler() => {
-                    let (sampler_info, is_manually_defined) =
-                        self.image_sampler(descriptor, name.as_deref().unwrap_or_default());
+                    let (sampler_binding, is_manually_defined) = self.image_sampler(
+                        descriptor,
+                        name.as_deref().unwrap_or_default(),
+                        samplers,
+                    )?;
 
                     DescriptorInfo::CombinedImageSampler(
                         binding_count,
-                        sampler_info,
+                        sampler_binding,
                         is_manually_defined,
                     )
                 }
@@ -961,10 +1206,13 @@ impl Shader {
                 }
                 DescriptorType::SampledImage() => DescriptorInfo::SampledImage(binding_count),
                 DescriptorType::Sampler() => {
-                    let (sampler_info, is_manually_defined) =
-                        self.image_sampler(descriptor, name.as_deref().unwrap_or_default());
+                    let (sampler_binding, is_manually_defined) = self.image_sampler(
+                        descriptor,
+                        name.as_deref().unwrap_or_default(),
+                        samplers,
+                    )?;
 
-                    DescriptorInfo::Sampler(binding_count, sampler_info, is_manually_defined)
+                    DescriptorInfo::Sampler(binding_count, sampler_binding, is_manually_defined)
                 }
                 DescriptorType::StorageBuffer(_access_ty) => {
                     DescriptorInfo::StorageBuffer(binding_count)
@@ -983,15 +1231,27 @@ impl Shader {
             res.insert(descriptor, (descriptor_info, self.stage));
         }
 
-        res
+        Ok(res)
     }
 
-    fn image_sampler(&self, descriptor: Descriptor, name: &str) -> (SamplerInfo, bool) {
-        self.image_samplers
-            .get(&descriptor)
-            .copied()
-            .map(|sampler_info| (sampler_info, true))
-            .unwrap_or_else(|| (guess_immutable_sampler(name), false))
+    fn image_sampler(
+        &self,
+        descriptor: Descriptor,
+        name: &str,
+        samplers: &SamplerTable,
+    ) -> Result<(SamplerBinding, bool), DriverError> {
+        if let Some(sampler_binding) = self.image_samplers.get(&descriptor).copied() {
+            return Ok((sampler_binding, true));
+        }
+
+        // Samplers from the pipeline-level table are identical for every shader stage and so they
+        // are not considered to be manually defined when merging stages
+        if let Some(sampler_binding) = samplers.get(descriptor, name) {
+            return Ok((sampler_binding, false));
+        }
+
+        guess_immutable_sampler(name)
+            .map(|sampler_info| (SamplerBinding::Immutable(sampler_info), false))
     }
 
     #[profiling::function]
@@ -1467,7 +1727,38 @@ impl ShaderBuilder {
         self.image_samplers
             .as_mut()
             .unwrap()
-            .insert(descriptor, info);
+            .insert(descriptor, SamplerBinding::Immutable(info));
+
+        self
+    }
+
+    /// Specifies a sampler or combined image sampler descriptor which does not use an immutable
+    /// sampler.
+    ///
+    /// The sampler must instead be bound to each recorded execution of a pass using
+    /// [`PipelinePassRef::bind_sampler`].
+    ///
+    /// _NOTE:_ As with [`ShaderBuilder::image_sampler`], only call this function on one of the
+    /// shader stages of a pipeline which use the descriptor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two shader stages of the same pipeline define individual samplers for the same
+    /// descriptor.
+    ///
+    /// [`PipelinePassRef::bind_sampler`]: crate::graph::pass_ref::PipelinePassRef::bind_sampler
+    #[profiling::function]
+    pub fn mutable_sampler(mut self, descriptor: impl Into<Descriptor>) -> Self {
+        let descriptor = descriptor.into();
+
+        if self.image_samplers.is_none() {
+            self.image_samplers = Some(Default::default());
+        }
+
+        self.image_samplers
+            .as_mut()
+            .unwrap()
+            .insert(descriptor, SamplerBinding::Mutable);
 
         self
     }
@@ -1579,6 +1870,63 @@ mod tests {
     type Info = SamplerInfo;
     type Builder = SamplerInfoBuilder;
 
+    #[test]
+    pub fn guess_immutable_sampler_default() {
+        let info = guess_immutable_sampler("my_image").unwrap();
+
+        assert_eq!(info.mag_filter, vk::Filter::LINEAR);
+        assert_eq!(info.mipmap_mode, vk::SamplerMipmapMode::LINEAR);
+        assert_eq!(info.address_mode_u, vk::SamplerAddressMode::REPEAT);
+    }
+
+    #[test]
+    pub fn guess_immutable_sampler_invalid() {
+        assert!(guess_immutable_sampler("my_image_sampler_").is_err());
+        assert!(guess_immutable_sampler("my_image_sampler_ll").is_err());
+        assert!(guess_immutable_sampler("my_image_sampler_llrr").is_err());
+        assert!(guess_immutable_sampler("my_image_sampler_xlr").is_err());
+        assert!(guess_immutable_sampler("my_image_sampler_lxr").is_err());
+        assert!(guess_immutable_sampler("my_image_sampler_llx").is_err());
+        assert!(guess_immutable_sampler("my_image_sampler_llé").is_err());
+    }
+
+    #[test]
+    pub fn guess_immutable_sampler_suffix() {
+        let info = guess_immutable_sampler("my_image_sampler_nne").unwrap();
+
+        assert_eq!(info.mag_filter, vk::Filter::NEAREST);
+        assert_eq!(info.min_filter, vk::Filter::NEAREST);
+        assert_eq!(info.mipmap_mode, vk::SamplerMipmapMode::NEAREST);
+        assert_eq!(info.address_mode_u, vk::SamplerAddressMode::CLAMP_TO_EDGE);
+        assert_eq!(info.address_mode_v, vk::SamplerAddressMode::CLAMP_TO_EDGE);
+        assert_eq!(info.address_mode_w, vk::SamplerAddressMode::CLAMP_TO_EDGE);
+        assert!(!info.anisotropy_enable);
+    }
+
+    #[test]
+    pub fn sampler_table() {
+        let linear = SamplerInfo::LINEAR.build();
+        let nearest = SamplerInfo::NEAREST.build();
+        let table = SamplerTable::default()
+            .sampler("by_name", linear)
+            .sampler((1, 2), nearest)
+            .sampler(3, SamplerBinding::Mutable);
+
+        assert_eq!(
+            table.get(Descriptor::from(0), "by_name"),
+            Some(SamplerBinding::Immutable(linear))
+        );
+        assert_eq!(
+            table.get(Descriptor::from((1, 2)), "by_name"),
+            Some(SamplerBinding::Immutable(nearest))
+        );
+        assert_eq!(
+            table.get(Descriptor::from(3), "other"),
+            Some(SamplerBinding::Mutable)
+        );
+        assert_eq!(table.get(Descriptor::from(4), "other"), None);
+    }
+
     #[test]
     pub fn sampler_info() {
         let info = Info::default();
@@ -24,7 +24,7 @@ impl Surface {
     /// Create a surface from a raw window display handle.
     ///
     /// `device` must have been created with platform specific surface extensions enabled, acquired
-    /// through [`Device::create_display_window`].
+    /// through [`Device::create_display`].
     #[profiling::function]
     pub fn create(
         device: &Arc<Device>,
@@ -60,6 +60,84 @@ impl Surface {
         Ok(Self { device, surface })
     }
 
+    /// Create a surface which is not associated with a window.
+    ///
+    /// Presentation to headless surfaces has no visible result, which is useful for testing
+    /// swapchain and display logic on machines without a windowing system, such as when using
+    /// lavapipe on a headless Linux machine.
+    ///
+    /// `device` must have been created with the headless surface extension enabled, acquired
+    /// through [`Device::create_headless_display`], otherwise [`DriverError::Unsupported`] is
+    /// returned. Headless surfaces have no current extent, so swapchains use the width and height of
+    /// their [`SwapchainInfo`].
+    ///
+    /// [`SwapchainInfo`]: super::swapchain::SwapchainInfo
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::surface::Surface;
+    /// # use screen_13::driver::swapchain::{Swapchain, SwapchainInfo};
+    /// # fn main() -> Result<(), DriverError> {
+    /// let device = Arc::new(Device::create_headless_display(DeviceInfo::default())?);
+    /// let surface = Surface::create_headless(&device)?;
+    /// let formats = Surface::formats(&surface)?;
+    /// let format = Surface::linear_or_default(&formats);
+    /// let swapchain = Swapchain::new(&device, surface, SwapchainInfo::new(320, 200, format))?;
+    /// # Ok(()) }
+    /// ```
+    #[profiling::function]
+    pub fn create_headless(device: &Arc<Device>) -> Result<Self, DriverError> {
+        let device = Arc::clone(device);
+        let instance = Device::instance(&device);
+        let headless_surface_ext = Instance::headless_surface_ext(instance).ok_or_else(|| {
+            warn!("headless surfaces require VK_EXT_headless_surface to be enabled");
+
+            DriverError::Unsupported
+        })?;
+        let surface = unsafe {
+            headless_surface_ext
+                .create_headless_surface(&vk::HeadlessSurfaceCreateInfoEXT::default(), None)
+        }
+        .map_err(|err| {
+            warn!("Unable to create headless surface: {err}");
+
+            DriverError::Unsupported
+        })?;
+
+        Ok(Self { device, surface })
+    }
+
+    /// Helper function to automatically select an HDR10 format, if one is available.
+    ///
+    /// HDR10 formats use the `HDR10_ST2084_EXT` color space with 10-bit color components. The
+    /// instance enables `VK_EXT_swapchain_colorspace` when available, which is required for these
+    /// formats to be reported.
+    #[profiling::function]
+    pub fn hdr10(formats: &[vk::SurfaceFormatKHR]) -> Option<vk::SurfaceFormatKHR> {
+        formats
+            .iter()
+            .find(
+                |&&vk::SurfaceFormatKHR {
+                     color_space,
+                     format,
+                 }| {
+                    matches!(color_space, vk::ColorSpaceKHR::HDR10_ST2084_EXT)
+                        && matches!(
+                            format,
+                            vk::Format::A2B10G10R10_UNORM_PACK32
+                                | vk::Format::A2R10G10B10_UNORM_PACK32
+                        )
+                },
+            )
+            .copied()
+    }
+
     /// Lists the supported surface formats.
     #[profiling::function]
     pub fn formats(this: &Self) -> Result<Vec<vk::SurfaceFormatKHR>, DriverError> {
@@ -100,6 +178,27 @@ impl Surface {
         Self::linear(formats).unwrap_or_else(|| formats.first().copied().unwrap_or_default())
     }
 
+    /// Helper function to automatically select an scRGB format, if one is available.
+    ///
+    /// scRGB formats use the `EXTENDED_SRGB_LINEAR_EXT` color space with 16-bit floating point
+    /// color components. The instance enables `VK_EXT_swapchain_colorspace` when available, which
+    /// is required for these formats to be reported.
+    #[profiling::function]
+    pub fn scrgb(formats: &[vk::SurfaceFormatKHR]) -> Option<vk::SurfaceFormatKHR> {
+        formats
+            .iter()
+            .find(
+                |&&vk::SurfaceFormatKHR {
+                     color_space,
+                     format,
+                 }| {
+                    matches!(color_space, vk::ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT)
+                        && matches!(format, vk::Format::R16G16B16A16_SFLOAT)
+                },
+            )
+            .copied()
+    }
+
     /// Helper function to automatically select the best sRGB format, if one is available.
     #[profiling::function]
     pub fn srgb(formats: &[vk::SurfaceFormatKHR]) -> Option<vk::SurfaceFormatKHR> {
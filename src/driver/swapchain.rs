@@ -9,7 +9,7 @@ use {
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
     log::{debug, info, trace, warn},
-    std::{mem::replace, ops::Deref, slice, sync::Arc, thread::panicking},
+    std::{mem::replace, ops::Deref, slice, sync::Arc, thread::panicking, time::Duration},
 };
 
 // TODO: This needs to track completed command buffers and not constantly create semaphores
@@ -17,11 +17,18 @@ use {
 /// Provides the ability to present rendering results to a [`Surface`].
 #[derive(Debug)]
 pub struct Swapchain {
+    composite_alpha: vk::CompositeAlphaFlagsKHR,
     device: Arc<Device>,
+    hdr_metadata: Option<HdrMetadata>,
+    image_usage: vk::ImageUsageFlags,
     images: Box<[SwapchainImage]>,
     info: SwapchainInfo,
     old_swapchain: vk::SwapchainKHR,
+    pre_transform: vk::SurfaceTransformFlagsKHR,
+    present_id: u64,
+    present_mode: vk::PresentModeKHR,
     suboptimal: bool,
+    swapchain_present_id: u64,
     surface: Surface,
     swapchain: vk::SwapchainKHR,
 }
@@ -29,6 +36,10 @@ pub struct Swapchain {
 impl Swapchain {
     /// Prepares a [`vk::SwapchainKHR`] object which is lazily created after calling
     /// [`acquire_next_image`][Self::acquire_next_image].
+    ///
+    /// The surface capabilities are queried immediately in order to check support of
+    /// [`SwapchainInfo::image_usage`]. Returns [`DriverError::Unsupported`] if the surface does not
+    /// support that usage.
     #[profiling::function]
     pub fn new(
         device: &Arc<Device>,
@@ -38,15 +49,46 @@ impl Swapchain {
         let device = Arc::clone(device);
         let info = info.into();
 
-        Ok(Swapchain {
+        let mut swapchain = Swapchain {
+            composite_alpha: vk::CompositeAlphaFlagsKHR::empty(),
             device,
+            hdr_metadata: None,
+            image_usage: vk::ImageUsageFlags::empty(),
             images: Default::default(),
             info,
             old_swapchain: vk::SwapchainKHR::null(),
+            pre_transform: vk::SurfaceTransformFlagsKHR::empty(),
+            present_id: 0,
+            present_mode: vk::PresentModeKHR::FIFO,
             suboptimal: true,
+            swapchain_present_id: 0,
             surface,
             swapchain: vk::SwapchainKHR::null(),
-        })
+        };
+
+        let surface_capabilities = {
+            let surface_ext = Device::expect_surface_ext(&swapchain.device);
+
+            unsafe {
+                surface_ext.get_physical_device_surface_capabilities(
+                    *swapchain.device.physical_device,
+                    *swapchain.surface,
+                )
+            }
+            .map_err(|err| {
+                warn!("unable to get surface capabilities: {err}");
+
+                match err {
+                    vk::Result::ERROR_OUT_OF_DEVICE_MEMORY
+                    | vk::Result::ERROR_OUT_OF_HOST_MEMORY => DriverError::OutOfMemory,
+                    _ => DriverError::Unsupported,
+                }
+            })?
+        };
+
+        swapchain.surface_image_usage(surface_capabilities.supported_usage_flags)?;
+
+        Ok(swapchain)
     }
 
     /// Gets the next available swapchain image which should be rendered to and then presented using
@@ -58,13 +100,7 @@ impl Swapchain {
     ) -> Result<SwapchainImage, SwapchainError> {
         for _ in 0..2 {
             if self.suboptimal {
-                self.recreate_swapchain().map_err(|err| {
-                    if matches!(err, DriverError::Unsupported) {
-                        SwapchainError::Suboptimal
-                    } else {
-                        SwapchainError::SurfaceLost
-                    }
-                })?;
+                self.recreate_swapchain()?;
             }
 
             let swapchain_ext = Device::expect_swapchain_ext(&self.device);
@@ -138,7 +174,7 @@ impl Swapchain {
 
                     warn!("unable to acquire image: {err}");
 
-                    return Err(SwapchainError::SurfaceLost);
+                    return Err(Self::surface_error(err));
                 }
             }
         }
@@ -146,6 +182,77 @@ impl Swapchain {
         Err(SwapchainError::Suboptimal)
     }
 
+    fn choose_composite_alpha(
+        composite_alpha: Option<vk::CompositeAlphaFlagsKHR>,
+        supported_composite_alpha: vk::CompositeAlphaFlagsKHR,
+    ) -> vk::CompositeAlphaFlagsKHR {
+        if let Some(composite_alpha) = composite_alpha {
+            if supported_composite_alpha.contains(composite_alpha) {
+                return composite_alpha;
+            }
+
+            warn!("unsupported composite alpha: {composite_alpha:?}");
+        }
+
+        [
+            vk::CompositeAlphaFlagsKHR::OPAQUE,
+            vk::CompositeAlphaFlagsKHR::INHERIT,
+            vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED,
+            vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED,
+        ]
+        .into_iter()
+        .find(|composite_alpha| supported_composite_alpha.contains(*composite_alpha))
+        .unwrap_or(vk::CompositeAlphaFlagsKHR::OPAQUE)
+    }
+
+    fn choose_pre_transform(
+        pre_transform: Option<vk::SurfaceTransformFlagsKHR>,
+        surface_capabilities: vk::SurfaceCapabilitiesKHR,
+    ) -> vk::SurfaceTransformFlagsKHR {
+        if let Some(pre_transform) = pre_transform {
+            if surface_capabilities
+                .supported_transforms
+                .contains(pre_transform)
+            {
+                return pre_transform;
+            }
+
+            warn!("unsupported pre-transform: {pre_transform:?}");
+        }
+
+        if surface_capabilities
+            .supported_transforms
+            .contains(vk::SurfaceTransformFlagsKHR::IDENTITY)
+        {
+            vk::SurfaceTransformFlagsKHR::IDENTITY
+        } else {
+            surface_capabilities.current_transform
+        }
+    }
+
+    fn choose_present_mode(
+        info: &SwapchainInfo,
+        supported_present_modes: &[vk::PresentModeKHR],
+    ) -> vk::PresentModeKHR {
+        let sync_display_present_modes: &[_] = if info.sync_display {
+            &[vk::PresentModeKHR::FIFO_RELAXED, vk::PresentModeKHR::FIFO]
+        } else {
+            &[vk::PresentModeKHR::MAILBOX, vk::PresentModeKHR::IMMEDIATE]
+        };
+        let mut present_modes = info.present_modes.iter().flatten().copied().peekable();
+
+        // FIFO is the only present mode which is required to be supported
+        if present_modes.peek().is_some() {
+            present_modes.find(|present_mode| supported_present_modes.contains(present_mode))
+        } else {
+            sync_display_present_modes
+                .iter()
+                .copied()
+                .find(|present_mode| supported_present_modes.contains(present_mode))
+        }
+        .unwrap_or(vk::PresentModeKHR::FIFO)
+    }
+
     fn clamp_desired_image_count(
         desired_image_count: u32,
         surface_capabilities: vk::SurfaceCapabilitiesKHR,
@@ -179,11 +286,91 @@ impl Swapchain {
         }
     }
 
+    /// Gets the composite alpha mode chosen when the swapchain was most recently created.
+    pub fn composite_alpha(&self) -> vk::CompositeAlphaFlagsKHR {
+        self.composite_alpha
+    }
+
+    fn apply_hdr_metadata(&self) {
+        let (Some(hdr_metadata), Some(hdr_metadata_ext)) =
+            (self.hdr_metadata, self.device.hdr_metadata_ext.as_ref())
+        else {
+            return;
+        };
+
+        if self.swapchain == vk::SwapchainKHR::null() {
+            return;
+        }
+
+        let xy = |[x, y]: [f32; 2]| vk::XYColorEXT { x, y };
+        let metadata = vk::HdrMetadataEXT::default()
+            .display_primary_red(xy(hdr_metadata.display_primary_red))
+            .display_primary_green(xy(hdr_metadata.display_primary_green))
+            .display_primary_blue(xy(hdr_metadata.display_primary_blue))
+            .white_point(xy(hdr_metadata.white_point))
+            .max_luminance(hdr_metadata.max_luminance)
+            .min_luminance(hdr_metadata.min_luminance)
+            .max_content_light_level(hdr_metadata.max_content_light_level)
+            .max_frame_average_light_level(hdr_metadata.max_frame_average_light_level);
+
+        unsafe {
+            hdr_metadata_ext
+                .set_hdr_metadata(slice::from_ref(&self.swapchain), slice::from_ref(&metadata));
+        }
+    }
+
+    /// Gets the image usage chosen when the swapchain was most recently created.
+    ///
+    /// This includes all usage supported by the surface and format, which is a superset of
+    /// [`SwapchainInfo::image_usage`].
+    pub fn image_usage(&self) -> vk::ImageUsageFlags {
+        self.image_usage
+    }
+
+    /// Returns the image usage supported by the surface and format, which must include
+    /// [`SwapchainInfo::image_usage`].
+    fn surface_image_usage(
+        &mut self,
+        supported_usage_flags: vk::ImageUsageFlags,
+    ) -> Result<vk::ImageUsageFlags, DriverError> {
+        let image_usage = self.supported_surface_usage(supported_usage_flags)?;
+
+        if !image_usage.contains(self.info.image_usage) {
+            warn!(
+                "unsupported swapchain image usage: {:?}",
+                self.info.image_usage & !image_usage
+            );
+
+            return Err(DriverError::Unsupported);
+        }
+
+        Ok(image_usage)
+    }
+
     /// Gets information about this swapchain.
     pub fn info(&self) -> SwapchainInfo {
         self.info
     }
 
+    /// Gets the pre-transform chosen when the swapchain was most recently created.
+    pub fn pre_transform(&self) -> vk::SurfaceTransformFlagsKHR {
+        self.pre_transform
+    }
+
+    /// Gets the identifier of the most recently presented image, if the device supports
+    /// `VK_KHR_present_wait`.
+    ///
+    /// Identifiers increase by one with each presentation and may be used with
+    /// [`wait_for_present`][Self::wait_for_present].
+    pub fn present_id(&self) -> Option<u64> {
+        (self.device.present_wait_ext.is_some() && self.present_id > 0).then_some(self.present_id)
+    }
+
+    /// Gets the present mode chosen when the swapchain was most recently created.
+    pub fn present_mode(&self) -> vk::PresentModeKHR {
+        self.present_mode
+    }
+
     /// Presents an image which has been previously acquired using
     /// [`acquire_next_image`][Self::acquire_next_image].
     #[profiling::function]
@@ -194,60 +381,129 @@ impl Swapchain {
         queue_family_index: u32,
         queue_index: u32,
     ) {
+        Self::present_images(
+            [(self, image)],
+            wait_semaphores,
+            queue_family_index,
+            queue_index,
+        );
+    }
+
+    /// Presents images of multiple swapchains, each previously acquired using
+    /// [`acquire_next_image`][Self::acquire_next_image], using a single presentation request.
+    ///
+    /// All swapchains must have been created using the same device.
+    #[profiling::function]
+    pub fn present_images<'a>(
+        images: impl IntoIterator<Item = (&'a mut Self, SwapchainImage)>,
+        wait_semaphores: &[vk::Semaphore],
+        queue_family_index: u32,
+        queue_index: u32,
+    ) {
+        let images = images.into_iter().collect::<Vec<_>>();
+        let Some((swapchain, _)) = images.first() else {
+            return;
+        };
+
+        let device = Arc::clone(&swapchain.device);
         let queue_family_index = queue_family_index as usize;
         let queue_index = queue_index as usize;
 
         debug_assert!(
-            queue_family_index < self.device.physical_device.queue_families.len(),
+            queue_family_index < device.physical_device.queue_families.len(),
             "Queue family index must be within the range of the available queues created by the device."
         );
         debug_assert!(
             queue_index
-                < self.device.physical_device.queue_families[queue_family_index].queue_count
-                    as usize,
+                < device.physical_device.queue_families[queue_family_index].queue_count as usize,
             "Queue index must be within the range of the available queues created by the device."
         );
+        debug_assert!(
+            images
+                .iter()
+                .all(|(swapchain, _)| Arc::ptr_eq(&swapchain.device, &device)),
+            "Swapchains must have been created using the same device."
+        );
 
-        let present_info = vk::PresentInfoKHR::default()
+        let swapchains = images
+            .iter()
+            .map(|(swapchain, _)| swapchain.swapchain)
+            .collect::<Box<_>>();
+        let image_indices = images
+            .iter()
+            .map(|(_, image)| image.image_idx)
+            .collect::<Box<_>>();
+        let present_ids = images
+            .iter()
+            .map(|(swapchain, _)| swapchain.present_id + 1)
+            .collect::<Box<_>>();
+        let mut results = vec![vk::Result::SUCCESS; images.len()];
+        let mut present_id_info = vk::PresentIdKHR::default().present_ids(&present_ids);
+        let mut present_info = vk::PresentInfoKHR::default()
             .wait_semaphores(wait_semaphores)
-            .swapchains(slice::from_ref(&self.swapchain))
-            .image_indices(slice::from_ref(&image.image_idx));
+            .swapchains(&swapchains)
+            .image_indices(&image_indices)
+            .results(&mut results);
 
-        let swapchain_ext = Device::expect_swapchain_ext(&self.device);
+        if device.present_wait_ext.is_some() {
+            present_info = present_info.push_next(&mut present_id_info);
+        }
 
-        unsafe {
-            match swapchain_ext.queue_present(
-                self.device.queues[queue_family_index][queue_index],
+        let swapchain_ext = Device::expect_swapchain_ext(&device);
+        let presented = match unsafe {
+            swapchain_ext.queue_present(
+                device.queues[queue_family_index][queue_index],
                 &present_info,
-            ) {
-                Ok(_) => {
-                    Self::destroy_swapchain(&self.device, &mut self.old_swapchain);
+            )
+        } {
+            Ok(_) => true,
+            Err(err)
+                if err == vk::Result::ERROR_DEVICE_LOST
+                    || err == vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT
+                    || err == vk::Result::ERROR_OUT_OF_DATE_KHR
+                    || err == vk::Result::ERROR_SURFACE_LOST_KHR
+                    || err == vk::Result::SUBOPTIMAL_KHR =>
+            {
+                // Results of each swapchain are handled below; the result of a single swapchain
+                // is the result of the request
+                if let [result] = results.as_mut_slice() {
+                    *result = err;
                 }
-                Err(err)
-                    if err == vk::Result::ERROR_DEVICE_LOST
-                        || err == vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT
-                        || err == vk::Result::ERROR_OUT_OF_DATE_KHR
-                        || err == vk::Result::ERROR_SURFACE_LOST_KHR
-                        || err == vk::Result::SUBOPTIMAL_KHR =>
-                {
+
+                true
+            }
+            Err(err) => {
+                // Probably:
+                // VK_ERROR_OUT_OF_HOST_MEMORY
+                // VK_ERROR_OUT_OF_DEVICE_MEMORY
+                warn!("{err}");
+
+                false
+            }
+        };
+
+        for (((swapchain, image), result), present_id) in
+            images.into_iter().zip(results).zip(present_ids)
+        {
+            if presented {
+                if result == vk::Result::SUCCESS || result == vk::Result::SUBOPTIMAL_KHR {
+                    Self::destroy_swapchain(&swapchain.device, &mut swapchain.old_swapchain);
+                } else {
                     // Handled in the next frame
-                    self.suboptimal = true;
-                }
-                Err(err) => {
-                    // Probably:
-                    // VK_ERROR_OUT_OF_HOST_MEMORY
-                    // VK_ERROR_OUT_OF_DEVICE_MEMORY
-                    warn!("{err}");
+                    swapchain.suboptimal = true;
                 }
             }
-        }
 
-        let image_idx = image.image_idx as usize;
-        self.images[image_idx] = image;
+            // Identifiers may not be reused even if presentation failed
+            swapchain.present_id = present_id;
+
+            let image_idx = image.image_idx as usize;
+            swapchain.images[image_idx] = image;
+        }
     }
 
     #[profiling::function]
-    fn recreate_swapchain(&mut self) -> Result<(), DriverError> {
+    fn recreate_swapchain(&mut self) -> Result<(), SwapchainError> {
         Self::destroy_swapchain(&self.device, &mut self.old_swapchain);
 
         let (surface_capabilities, present_modes) = {
@@ -258,8 +514,11 @@ impl Swapchain {
                     *self.surface,
                 )
             }
-            .inspect_err(|err| warn!("unable to get surface capabilities: {err}"))
-            .or(Err(DriverError::Unsupported))?;
+            .map_err(|err| {
+                warn!("unable to get surface capabilities: {err}");
+
+                Self::surface_error(err)
+            })?;
 
             let present_modes = unsafe {
                 surface_ext.get_physical_device_surface_present_modes(
@@ -267,8 +526,11 @@ impl Swapchain {
                     *self.surface,
                 )
             }
-            .inspect_err(|err| warn!("unable to get surface present modes: {err}"))
-            .or(Err(DriverError::Unsupported))?;
+            .map_err(|err| {
+                warn!("unable to get surface present modes: {err}");
+
+                Self::surface_error(err)
+            })?;
 
             (surface_capabilities, present_modes)
         };
@@ -276,8 +538,9 @@ impl Swapchain {
         let desired_image_count =
             Self::clamp_desired_image_count(self.info.desired_image_count, surface_capabilities);
 
-        let image_usage =
-            self.supported_surface_usage(surface_capabilities.supported_usage_flags)?;
+        let image_usage = self
+            .surface_image_usage(surface_capabilities.supported_usage_flags)
+            .map_err(SwapchainError::Driver)?;
 
         let (surface_width, surface_height) = match surface_capabilities.current_extent.width {
             std::u32::MAX => (
@@ -298,27 +561,16 @@ impl Swapchain {
         };
 
         if surface_width * surface_height == 0 {
-            return Err(DriverError::Unsupported);
+            return Err(SwapchainError::Suboptimal);
         }
 
-        let present_mode_preference = if self.info.sync_display {
-            vec![vk::PresentModeKHR::FIFO_RELAXED, vk::PresentModeKHR::FIFO]
-        } else {
-            vec![vk::PresentModeKHR::MAILBOX, vk::PresentModeKHR::IMMEDIATE]
-        };
-        let present_mode = present_mode_preference
-            .into_iter()
-            .find(|mode| present_modes.contains(mode))
-            .unwrap_or(vk::PresentModeKHR::FIFO);
-
-        let pre_transform = if surface_capabilities
-            .supported_transforms
-            .contains(vk::SurfaceTransformFlagsKHR::IDENTITY)
-        {
-            vk::SurfaceTransformFlagsKHR::IDENTITY
-        } else {
-            surface_capabilities.current_transform
-        };
+        let present_mode = Self::choose_present_mode(&self.info, &present_modes);
+        let pre_transform =
+            Self::choose_pre_transform(self.info.pre_transform, surface_capabilities);
+        let composite_alpha = Self::choose_composite_alpha(
+            self.info.composite_alpha,
+            surface_capabilities.supported_composite_alpha,
+        );
 
         let swapchain_ext = Device::expect_swapchain_ext(&self.device);
         let swapchain_create_info = vk::SwapchainCreateInfoKHR::default()
@@ -333,7 +585,7 @@ impl Swapchain {
             .image_usage(image_usage)
             .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
             .pre_transform(pre_transform)
-            .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
+            .composite_alpha(composite_alpha)
             .present_mode(present_mode)
             .clipped(true)
             .old_swapchain(self.swapchain)
@@ -342,16 +594,13 @@ impl Swapchain {
             .map_err(|err| {
                 warn!("{err}");
 
-                DriverError::Unsupported
+                Self::surface_error(err)
             })?;
 
         let images =
             unsafe { swapchain_ext.get_swapchain_images(swapchain) }.map_err(|err| match err {
-                vk::Result::INCOMPLETE => DriverError::InvalidData,
-                vk::Result::ERROR_OUT_OF_DEVICE_MEMORY | vk::Result::ERROR_OUT_OF_HOST_MEMORY => {
-                    DriverError::OutOfMemory
-                }
-                _ => DriverError::Unsupported,
+                vk::Result::INCOMPLETE => SwapchainError::Driver(DriverError::InvalidData),
+                err => Self::surface_error(err),
             })?;
         let images = images
             .into_iter()
@@ -371,21 +620,28 @@ impl Swapchain {
                 let image_idx = image_idx as u32;
                 image.name = Some(format!("swapchain{image_idx}"));
 
-                Ok(SwapchainImage {
+                SwapchainImage {
                     exec_idx: 0,
                     image,
                     image_idx,
-                })
+                }
             })
-            .collect::<Result<Box<_>, _>>()?;
+            .collect::<Box<_>>();
 
+        self.composite_alpha = composite_alpha;
+        self.image_usage = image_usage;
         self.info.height = surface_height;
         self.info.width = surface_width;
         self.images = images;
         self.old_swapchain = self.swapchain;
+        self.pre_transform = pre_transform;
+        self.present_mode = present_mode;
+        self.swapchain_present_id = self.present_id;
         self.swapchain = swapchain;
         self.suboptimal = false;
 
+        self.apply_hdr_metadata();
+
         info!(
             "swapchain {}x{} {present_mode:?}x{} {:?} {image_usage:#?}",
             self.info.width,
@@ -397,9 +653,48 @@ impl Swapchain {
         Ok(())
     }
 
+    /// Sets the HDR metadata which describes the mastering display and content light levels of
+    /// presented images.
+    ///
+    /// The metadata is applied to the current swapchain and to any swapchain recreated later.
+    ///
+    /// Returns [`DriverError::Unsupported`] if the device does not support `VK_EXT_hdr_metadata`.
+    #[profiling::function]
+    pub fn set_hdr_metadata(&mut self, hdr_metadata: HdrMetadata) -> Result<(), DriverError> {
+        if self.device.hdr_metadata_ext.is_none() {
+            warn!("HDR metadata is not supported");
+
+            return Err(DriverError::Unsupported);
+        }
+
+        self.hdr_metadata = Some(hdr_metadata);
+        self.apply_hdr_metadata();
+
+        Ok(())
+    }
+
+    /// Replaces the surface of this swapchain, such as after the previous surface was lost.
+    ///
+    /// The swapchain is lazily recreated after calling
+    /// [`acquire_next_image`][Self::acquire_next_image]. Previously acquired swapchain images must
+    /// no longer be in use by the device and should be discarded after calling this function.
+    #[profiling::function]
+    pub fn set_surface(&mut self, surface: Surface) {
+        // The swapchains belong to the previous surface and so they cannot be retired
+        Self::destroy_swapchain(&self.device, &mut self.old_swapchain);
+        Self::destroy_swapchain(&self.device, &mut self.swapchain);
+
+        self.images = Default::default();
+        self.suboptimal = true;
+        self.surface = surface;
+    }
+
     /// Sets information about this swapchain.
     ///
-    /// Previously acquired swapchain images should be discarded after calling this function.
+    /// Previously acquired swapchain images should be discarded after calling this function. If the
+    /// surface does not support [`SwapchainInfo::image_usage`] then
+    /// [`acquire_next_image`][Self::acquire_next_image] returns
+    /// [`SwapchainError::Driver`] with [`DriverError::Unsupported`].
     pub fn set_info(&mut self, info: impl Into<SwapchainInfo>) {
         let info: SwapchainInfo = info.into();
 
@@ -418,6 +713,78 @@ impl Swapchain {
         }
     }
 
+    pub(crate) fn swapchain(this: &Self) -> vk::SwapchainKHR {
+        this.swapchain
+    }
+
+    /// Maps an error of a surface or swapchain function to the condition reported by this
+    /// swapchain.
+    ///
+    /// Only `VK_ERROR_SURFACE_LOST_KHR` is reported as [`SwapchainError::SurfaceLost`] and
+    /// `VK_ERROR_DEVICE_LOST` as [`SwapchainError::DeviceLost`]. A native window which is already
+    /// in use is reported as [`DriverError::InvalidData`], out of memory errors as
+    /// [`DriverError::OutOfMemory`] and all other errors as [`DriverError::Unsupported`].
+    fn surface_error(err: vk::Result) -> SwapchainError {
+        match err {
+            vk::Result::ERROR_DEVICE_LOST => SwapchainError::DeviceLost,
+            vk::Result::ERROR_SURFACE_LOST_KHR => SwapchainError::SurfaceLost,
+            vk::Result::ERROR_NATIVE_WINDOW_IN_USE_KHR => {
+                SwapchainError::Driver(DriverError::InvalidData)
+            }
+            vk::Result::ERROR_OUT_OF_DEVICE_MEMORY | vk::Result::ERROR_OUT_OF_HOST_MEMORY => {
+                SwapchainError::Driver(DriverError::OutOfMemory)
+            }
+            _ => SwapchainError::Driver(DriverError::Unsupported),
+        }
+    }
+
+    /// Waits until the image with the given identifier, or a later image, has been presented.
+    ///
+    /// Returns `Ok(false)` if the image was not presented before `timeout` elapsed. Images which
+    /// were presented using a previous swapchain are considered presented.
+    ///
+    /// Returns [`DriverError::Unsupported`] if the device does not support `VK_KHR_present_wait`.
+    #[profiling::function]
+    pub fn wait_for_present(
+        &self,
+        present_id: u64,
+        timeout: Duration,
+    ) -> Result<bool, DriverError> {
+        let Some(present_wait_ext) = self.device.present_wait_ext.as_ref() else {
+            warn!("present wait is not supported");
+
+            return Err(DriverError::Unsupported);
+        };
+
+        if present_id <= self.swapchain_present_id || self.swapchain == vk::SwapchainKHR::null() {
+            return Ok(true);
+        }
+
+        match unsafe {
+            present_wait_ext.wait_for_present(
+                self.swapchain,
+                present_id,
+                timeout.as_nanos().min(u64::MAX as _) as _,
+            )
+        } {
+            Ok(_) => Ok(true),
+            Err(vk::Result::TIMEOUT) => Ok(false),
+            Err(err)
+                if err == vk::Result::ERROR_OUT_OF_DATE_KHR
+                    || err == vk::Result::ERROR_SURFACE_LOST_KHR
+                    || err == vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT =>
+            {
+                // The image will not be presented; handled in the next frame
+                Ok(true)
+            }
+            Err(err) => {
+                warn!("unable to wait for present: {err}");
+
+                Err(DriverError::InvalidData)
+            }
+        }
+    }
+
     fn supported_surface_usage(
         &mut self,
         surface_capabilities: vk::ImageUsageFlags,
@@ -472,12 +839,72 @@ impl Drop for Swapchain {
     }
 }
 
+/// HDR metadata of a mastering display and content, as defined by SMPTE ST 2086 and CTA-861.3.
+///
+/// Chromaticity coordinates are CIE 1931 `[x, y]` values and luminance values are in nits.
+///
+/// See
+/// [VkHdrMetadataEXT](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkHdrMetadataEXT.html)
+/// for more information.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HdrMetadata {
+    /// The chromaticity of the blue primary of the mastering display.
+    pub display_primary_blue: [f32; 2],
+
+    /// The chromaticity of the green primary of the mastering display.
+    pub display_primary_green: [f32; 2],
+
+    /// The chromaticity of the red primary of the mastering display.
+    pub display_primary_red: [f32; 2],
+
+    /// The maximum luminance of any single pixel of the content.
+    pub max_content_light_level: f32,
+
+    /// The maximum average luminance of any single frame of the content.
+    pub max_frame_average_light_level: f32,
+
+    /// The maximum luminance of the mastering display.
+    pub max_luminance: f32,
+
+    /// The minimum luminance of the mastering display.
+    pub min_luminance: f32,
+
+    /// The chromaticity of the white point of the mastering display.
+    pub white_point: [f32; 2],
+}
+
+impl HdrMetadata {
+    /// Specifies HDR metadata using the ITU-R BT.2020 primaries and D65 white point with the given
+    /// luminance values.
+    pub const fn bt2020(
+        max_luminance: f32,
+        min_luminance: f32,
+        max_content_light_level: f32,
+        max_frame_average_light_level: f32,
+    ) -> Self {
+        Self {
+            display_primary_blue: [0.131, 0.046],
+            display_primary_green: [0.170, 0.797],
+            display_primary_red: [0.708, 0.292],
+            max_content_light_level,
+            max_frame_average_light_level,
+            max_luminance,
+            min_luminance,
+            white_point: [0.3127, 0.3290],
+        }
+    }
+}
+
 /// Describes the condition of a swapchain.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum SwapchainError {
     /// This frame is lost but more may be acquired later.
     DeviceLost,
 
+    /// The swapchain could not be created or an image could not be acquired, such as when the
+    /// device has run out of memory.
+    Driver(DriverError),
+
     /// This frame is not lost but there may be a delay while the next frame is recreated.
     Suboptimal,
 
@@ -526,6 +953,14 @@ impl Deref for SwapchainImage {
 )]
 #[non_exhaustive]
 pub struct SwapchainInfo {
+    /// The composite alpha mode used when the surface is composited with other surfaces.
+    ///
+    /// If not specified, or not supported by the surface, `OPAQUE` is used when supported and
+    /// otherwise the first supported mode. The chosen mode is available from
+    /// [`Swapchain::composite_alpha`].
+    #[builder(default, setter(strip_option))]
+    pub composite_alpha: Option<vk::CompositeAlphaFlagsKHR>,
+
     /// The desired, but not guaranteed, number of images that will be in the created swapchain.
     ///
     /// More images introduces more display lag, but smoother animation.
@@ -535,6 +970,32 @@ pub struct SwapchainInfo {
     /// The initial height of the surface.
     pub height: u32,
 
+    /// Additional usage which swapchain images must support, such as `STORAGE` for presenting
+    /// images written by compute shaders.
+    ///
+    /// [`Swapchain::new`] returns [`DriverError::Unsupported`] if the surface does not support this
+    /// usage. The swapchain images are created with all usage supported by the surface, available
+    /// from [`Swapchain::image_usage`].
+    #[builder(default)]
+    pub image_usage: vk::ImageUsageFlags,
+
+    /// The transform applied to images relative to the presentation engine's natural orientation.
+    ///
+    /// If not specified, or not supported by the surface, `IDENTITY` is used when supported and
+    /// otherwise the current transform of the surface. The chosen transform is available from
+    /// [`Swapchain::pre_transform`].
+    #[builder(default, setter(strip_option))]
+    pub pre_transform: Option<vk::SurfaceTransformFlagsKHR>,
+
+    /// An ordered list of preferred present modes, where unused entries are `None`.
+    ///
+    /// The first present mode supported by the surface is used. If empty, the preferred present
+    /// modes are chosen using [`SwapchainInfo::sync_display`]. `FIFO` is used when no preferred
+    /// present mode is supported. The chosen present mode is available from
+    /// [`Swapchain::present_mode`].
+    #[builder(default, setter(custom))]
+    pub present_modes: [Option<vk::PresentModeKHR>; SwapchainInfo::MAX_PRESENT_MODES],
+
     /// The format and color space of the surface.
     pub surface: vk::SurfaceFormatKHR,
 
@@ -542,6 +1003,8 @@ pub struct SwapchainInfo {
     /// should be displayed as fast as possible instead.
     ///
     /// Turn on to eliminate visual tearing at the expense of latency.
+    ///
+    /// Ignored when [`SwapchainInfo::present_modes`] is not empty.
     #[builder(default = "true")]
     pub sync_display: bool,
 
@@ -550,6 +1013,9 @@ pub struct SwapchainInfo {
 }
 
 impl SwapchainInfo {
+    /// The maximum number of preferred present modes.
+    pub const MAX_PRESENT_MODES: usize = 4;
+
     /// Specifies a default swapchain with the given `width`, `height` and `format` values.
     #[inline(always)]
     pub const fn new(width: u32, height: u32, surface: vk::SurfaceFormatKHR) -> SwapchainInfo {
@@ -557,7 +1023,11 @@ impl SwapchainInfo {
             width,
             height,
             surface,
+            composite_alpha: None,
             desired_image_count: 3,
+            image_usage: vk::ImageUsageFlags::empty(),
+            pre_transform: None,
+            present_modes: [None; Self::MAX_PRESENT_MODES],
             sync_display: true,
         }
     }
@@ -566,8 +1036,12 @@ impl SwapchainInfo {
     #[inline(always)]
     pub fn to_builder(self) -> SwapchainInfoBuilder {
         SwapchainInfoBuilder {
+            composite_alpha: Some(self.composite_alpha),
             desired_image_count: Some(self.desired_image_count),
             height: Some(self.height),
+            image_usage: Some(self.image_usage),
+            pre_transform: Some(self.pre_transform),
+            present_modes: Some(self.present_modes),
             surface: Some(self.surface),
             sync_display: Some(self.sync_display),
             width: Some(self.width),
@@ -582,6 +1056,32 @@ impl From<SwapchainInfoBuilder> for SwapchainInfo {
 }
 
 impl SwapchainInfoBuilder {
+    /// An ordered list of preferred present modes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`SwapchainInfo::MAX_PRESENT_MODES`] present modes are given.
+    pub fn present_modes(
+        mut self,
+        present_modes: impl IntoIterator<Item = vk::PresentModeKHR>,
+    ) -> Self {
+        let mut res = [None; SwapchainInfo::MAX_PRESENT_MODES];
+        let mut present_modes = present_modes.into_iter();
+
+        for present_mode in &mut res {
+            *present_mode = present_modes.next();
+        }
+
+        assert!(
+            present_modes.next().is_none(),
+            "more than {} present modes",
+            SwapchainInfo::MAX_PRESENT_MODES
+        );
+
+        self.present_modes = Some(res);
+        self
+    }
+
     /// Builds a new `SwapchainInfo`.
     ///
     /// # Panics
@@ -616,6 +1116,84 @@ mod tests {
     type Info = SwapchainInfo;
     type Builder = SwapchainInfoBuilder;
 
+    #[test]
+    pub fn swapchain_surface_error() {
+        assert_eq!(
+            Swapchain::surface_error(vk::Result::ERROR_SURFACE_LOST_KHR),
+            SwapchainError::SurfaceLost
+        );
+        assert_eq!(
+            Swapchain::surface_error(vk::Result::ERROR_DEVICE_LOST),
+            SwapchainError::DeviceLost
+        );
+        assert_eq!(
+            Swapchain::surface_error(vk::Result::ERROR_OUT_OF_HOST_MEMORY),
+            SwapchainError::Driver(DriverError::OutOfMemory)
+        );
+        assert_eq!(
+            Swapchain::surface_error(vk::Result::ERROR_OUT_OF_DEVICE_MEMORY),
+            SwapchainError::Driver(DriverError::OutOfMemory)
+        );
+        assert_eq!(
+            Swapchain::surface_error(vk::Result::ERROR_INITIALIZATION_FAILED),
+            SwapchainError::Driver(DriverError::Unsupported)
+        );
+    }
+
+    #[test]
+    pub fn swapchain_present_mode() {
+        let supported = [vk::PresentModeKHR::FIFO, vk::PresentModeKHR::MAILBOX];
+        let info = Info::new(20, 24, vk::SurfaceFormatKHR::default());
+
+        assert_eq!(
+            Swapchain::choose_present_mode(&info, &supported),
+            vk::PresentModeKHR::FIFO
+        );
+
+        let info = info.to_builder().sync_display(false).build();
+
+        assert_eq!(
+            Swapchain::choose_present_mode(&info, &supported),
+            vk::PresentModeKHR::MAILBOX
+        );
+
+        let info = info
+            .to_builder()
+            .sync_display(true)
+            .present_modes([vk::PresentModeKHR::IMMEDIATE, vk::PresentModeKHR::MAILBOX])
+            .build();
+
+        assert_eq!(
+            info.present_modes,
+            [
+                Some(vk::PresentModeKHR::IMMEDIATE),
+                Some(vk::PresentModeKHR::MAILBOX),
+                None,
+                None
+            ]
+        );
+        assert_eq!(
+            Swapchain::choose_present_mode(&info, &supported),
+            vk::PresentModeKHR::MAILBOX
+        );
+
+        let info = info
+            .to_builder()
+            .present_modes([vk::PresentModeKHR::IMMEDIATE])
+            .build();
+
+        assert_eq!(
+            Swapchain::choose_present_mode(&info, &supported),
+            vk::PresentModeKHR::FIFO
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "more than 4 present modes")]
+    pub fn swapchain_present_mode_overflow() {
+        Builder::default().present_modes([vk::PresentModeKHR::FIFO; 5]);
+    }
+
     #[test]
     pub fn swapchain_info() {
         let info = Info::new(20, 24, vk::SurfaceFormatKHR::default());
@@ -0,0 +1,594 @@
+//! Helpers for building acceleration structures from graph nodes.
+
+use {
+    super::{
+        RenderGraph,
+        node::{
+            AccelerationStructureLeaseNode, AnyAccelerationStructureNode, AnyBufferNode,
+            BufferLeaseNode, Node,
+        },
+        pass_ref::AccelerationStructureBuildInfo,
+    },
+    crate::{
+        driver::{
+            DriverError,
+            accel_struct::{
+                AccelerationStructure, AccelerationStructureGeometry,
+                AccelerationStructureGeometryData, AccelerationStructureGeometryInfo,
+                AccelerationStructureInfo,
+            },
+            buffer::{Buffer, BufferInfo},
+            device::Device,
+            vk_sync::AccessType,
+        },
+        pool::Pool,
+    },
+    ash::vk,
+    log::warn,
+    std::sync::Arc,
+};
+
+/// Builds bottom-level and top-level acceleration structures using leased resources.
+///
+/// Acceleration structures, scratch buffers and instance buffers are leased from the given pool
+/// and bound to the render graph. Scratch buffers are sized and aligned using the acceleration
+/// structure properties of the device.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use ash::vk;
+/// # use screen_13::driver::DriverError;
+/// # use screen_13::driver::device::{Device, DeviceInfo};
+/// # use screen_13::driver::buffer::{Buffer, BufferInfo};
+/// # use screen_13::graph::{AccelerationStructureBuilder, AccelerationStructureInstance, RenderGraph, TriangleMesh};
+/// # use screen_13::pool::lazy::LazyPool;
+/// # fn main() -> Result<(), DriverError> {
+/// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+/// # let info = BufferInfo::device_mem(1024, vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS);
+/// # let my_vertex_buf = Buffer::create(&device, info)?;
+/// # let my_index_buf = Buffer::create(&device, info)?;
+/// # let mut my_pool = LazyPool::new(&device);
+/// let mut my_graph = RenderGraph::new();
+/// let vertex_node = my_graph.bind_node(my_vertex_buf);
+/// let index_node = my_graph.bind_node(my_index_buf);
+/// let my_mesh = TriangleMesh::indexed(vertex_node, 42, index_node, vk::IndexType::UINT32, 16);
+///
+/// let builder = AccelerationStructureBuilder::new(&device);
+/// let blas_nodes = builder.build_blas(&mut my_graph, &mut my_pool, &[&[my_mesh]])?;
+/// let tlas_node = builder.build_tlas(
+///     &mut my_graph,
+///     &mut my_pool,
+///     &[AccelerationStructureInstance::new(blas_nodes[0])],
+/// )?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct AccelerationStructureBuilder {
+    device: Arc<Device>,
+
+    /// Specifies additional parameters of the built acceleration structures.
+    ///
+    /// The default value is `PREFER_FAST_TRACE`.
+    pub flags: vk::BuildAccelerationStructureFlagsKHR,
+}
+
+impl AccelerationStructureBuilder {
+    /// Constructs a new builder for the given device.
+    pub fn new(device: &Arc<Device>) -> Self {
+        let device = Arc::clone(device);
+
+        Self {
+            device,
+            flags: vk::BuildAccelerationStructureFlagsKHR::PREFER_FAST_TRACE,
+        }
+    }
+
+    /// Sets the flags on this instance.
+    pub fn flags(mut self, flags: vk::BuildAccelerationStructureFlagsKHR) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Builds one bottom-level acceleration structure for each slice of triangle meshes.
+    ///
+    /// All builds are recorded in a single pass which shares one scratch buffer. The returned nodes
+    /// are in the same order as `meshes`.
+    ///
+    /// The vertex and index buffers must have been created with the
+    /// `ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR` and `SHADER_DEVICE_ADDRESS` usage flags.
+    #[profiling::function]
+    pub fn build_blas<P>(
+        &self,
+        render_graph: &mut RenderGraph,
+        pool: &mut P,
+        meshes: &[&[TriangleMesh]],
+    ) -> Result<Vec<AccelerationStructureLeaseNode>, DriverError>
+    where
+        P: Pool<AccelerationStructureInfo, AccelerationStructure> + Pool<BufferInfo, Buffer>,
+    {
+        if meshes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let scratch_alignment = self.scratch_alignment()?;
+        let mut build_datas = Vec::with_capacity(meshes.len());
+        let mut scratch_offsets = Vec::with_capacity(meshes.len());
+        let mut scratch_size = 0;
+        let mut accel_struct_nodes = Vec::with_capacity(meshes.len());
+
+        for meshes in meshes {
+            let build_data = AccelerationStructureGeometryInfo::blas(
+                meshes
+                    .iter()
+                    .map(|mesh| mesh.geometry(render_graph))
+                    .collect::<Box<_>>(),
+            )
+            .flags(self.flags);
+            let size = AccelerationStructure::size_of(&self.device, &build_data);
+            let accel_struct = pool.lease(AccelerationStructureInfo::blas(size.create_size))?;
+
+            accel_struct_nodes.push(render_graph.bind_node(accel_struct));
+            scratch_offsets.push(scratch_size);
+            scratch_size += size.build_size.next_multiple_of(scratch_alignment);
+            build_datas.push(build_data);
+        }
+
+        let scratch_node =
+            self.lease_scratch(render_graph, pool, scratch_size, scratch_alignment)?;
+        let scratch_addr = render_graph.node_device_address(scratch_node);
+        let build_infos = accel_struct_nodes
+            .iter()
+            .zip(build_datas)
+            .zip(scratch_offsets)
+            .map(|((accel_struct_node, build_data), scratch_offset)| {
+                AccelerationStructureBuildInfo::new(
+                    *accel_struct_node,
+                    build_data,
+                    scratch_addr + scratch_offset,
+                )
+            })
+            .collect::<Box<_>>();
+
+        let mut pass = render_graph.begin_pass("build bottom-level acceleration structures");
+
+        for mesh in meshes.iter().copied().flatten() {
+            pass = pass.access_node(
+                mesh.vertex_buffer,
+                AccessType::AccelerationStructureBuildRead,
+            );
+
+            if let Some(index_buffer) = mesh.index_buffer {
+                pass = pass.access_node(index_buffer, AccessType::AccelerationStructureBuildRead);
+            }
+        }
+
+        for accel_struct_node in &accel_struct_nodes {
+            pass = pass.access_node(
+                *accel_struct_node,
+                AccessType::AccelerationStructureBuildWrite,
+            );
+        }
+
+        pass.access_node(scratch_node, AccessType::AccelerationStructureBufferWrite)
+            .record_acceleration(move |acceleration, _| {
+                acceleration.build_structures(&build_infos);
+            });
+
+        Ok(accel_struct_nodes)
+    }
+
+    /// Builds a top-level acceleration structure containing the given instances.
+    ///
+    /// The instance data is written into a leased host-visible buffer and each referenced
+    /// bottom-level acceleration structure is read during the build.
+    #[profiling::function]
+    pub fn build_tlas<P>(
+        &self,
+        render_graph: &mut RenderGraph,
+        pool: &mut P,
+        instances: &[AccelerationStructureInstance],
+    ) -> Result<AccelerationStructureLeaseNode, DriverError>
+    where
+        P: Pool<AccelerationStructureInfo, AccelerationStructure> + Pool<BufferInfo, Buffer>,
+    {
+        let scratch_alignment = self.scratch_alignment()?;
+        let instance_count = instances.len() as u32;
+        let instances_khr = instances
+            .iter()
+            .enumerate()
+            .map(|(instance_idx, instance)| {
+                let blas = render_graph.bindings[instance.blas.index()]
+                    .as_driver_acceleration_structure()
+                    .unwrap_or_else(|| panic!("instance {instance_idx} specified in top-level acceleration structure build does not reference a bound acceleration structure node"));
+
+                instance.to_khr(AccelerationStructure::device_address(blas))
+            })
+            .collect::<Box<_>>();
+        let instance_data = AccelerationStructure::instance_slice(&instances_khr);
+
+        // Instance data must be aligned to 16 bytes and the buffer cannot be empty
+        let mut instance_buf = pool.lease(
+            BufferInfo::host_mem(
+                instance_data.len().max(1) as _,
+                vk::BufferUsageFlags::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR
+                    | vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS,
+            )
+            .to_builder()
+            .alignment(16)
+            .build(),
+        )?;
+        Buffer::copy_from_slice(&mut instance_buf, 0, instance_data);
+
+        let instance_node = render_graph.bind_node(instance_buf);
+        let build_data = AccelerationStructureGeometryInfo::tlas([(
+            AccelerationStructureGeometry::opaque(
+                instance_count,
+                AccelerationStructureGeometryData::instances(
+                    render_graph.node_device_address(instance_node),
+                ),
+            ),
+            vk::AccelerationStructureBuildRangeInfoKHR::default().primitive_count(instance_count),
+        )])
+        .flags(self.flags);
+        let size = AccelerationStructure::size_of(&self.device, &build_data);
+        let accel_struct_node =
+            render_graph.bind_node(pool.lease(AccelerationStructureInfo::tlas(size.create_size))?);
+        let scratch_node =
+            self.lease_scratch(render_graph, pool, size.build_size, scratch_alignment)?;
+        let scratch_addr = render_graph.node_device_address(scratch_node);
+
+        let mut blas_nodes = instances
+            .iter()
+            .map(|instance| instance.blas)
+            .collect::<Vec<_>>();
+        blas_nodes.sort_unstable_by_key(|node| node.index());
+        blas_nodes.dedup_by_key(|node| node.index());
+
+        let mut pass = render_graph.begin_pass("build top-level acceleration structure");
+
+        for blas_node in blas_nodes {
+            pass = pass.access_node(blas_node, AccessType::AccelerationStructureBuildRead);
+        }
+
+        pass.access_node(instance_node, AccessType::AccelerationStructureBuildRead)
+            .access_node(scratch_node, AccessType::AccelerationStructureBufferWrite)
+            .access_node(
+                accel_struct_node,
+                AccessType::AccelerationStructureBuildWrite,
+            )
+            .record_acceleration(move |acceleration, _| {
+                acceleration.build_structure(&build_data, accel_struct_node, scratch_addr);
+            });
+
+        Ok(accel_struct_node)
+    }
+
+    fn lease_scratch<P>(
+        &self,
+        render_graph: &mut RenderGraph,
+        pool: &mut P,
+        size: vk::DeviceSize,
+        alignment: vk::DeviceSize,
+    ) -> Result<BufferLeaseNode, DriverError>
+    where
+        P: Pool<BufferInfo, Buffer>,
+    {
+        let scratch_buf = pool.lease(
+            BufferInfo::device_mem(
+                size.max(1),
+                vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS | vk::BufferUsageFlags::STORAGE_BUFFER,
+            )
+            .to_builder()
+            .alignment(alignment)
+            .build(),
+        )?;
+
+        Ok(render_graph.bind_node(scratch_buf))
+    }
+
+    fn scratch_alignment(&self) -> Result<vk::DeviceSize, DriverError> {
+        self.device
+            .physical_device
+            .accel_struct_properties
+            .as_ref()
+            .map(|properties| properties.min_accel_struct_scratch_offset_alignment as _)
+            .ok_or_else(|| {
+                warn!("acceleration structures are not supported");
+
+                DriverError::Unsupported
+            })
+    }
+}
+
+/// An instance of a bottom-level acceleration structure within a top-level acceleration structure.
+///
+/// See
+/// [VkAccelerationStructureInstanceKHR](https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkAccelerationStructureInstanceKHR.html)
+/// for more information.
+#[derive(Clone, Copy, Debug)]
+pub struct AccelerationStructureInstance {
+    /// The bottom-level acceleration structure used by this instance.
+    pub blas: AnyAccelerationStructureNode,
+
+    /// A 24-bit value which is made available to shaders as `InstanceCustomIndexKHR`.
+    pub custom_index: u32,
+
+    /// Flags which apply to this instance.
+    pub flags: vk::GeometryInstanceFlagsKHR,
+
+    /// An 8-bit visibility mask which is combined with the cull mask of a ray.
+    pub mask: u8,
+
+    /// A 24-bit offset used when calculating the hit shader binding table index.
+    pub shader_binding_table_record_offset: u32,
+
+    /// A 3x4 row-major affine transformation matrix.
+    pub transform: [f32; 12],
+}
+
+impl AccelerationStructureInstance {
+    /// Constructs a new visible instance of the given bottom-level acceleration structure using an
+    /// identity transform.
+    pub fn new(blas: impl Into<AnyAccelerationStructureNode>) -> Self {
+        let blas = blas.into();
+
+        Self {
+            blas,
+            custom_index: 0,
+            flags: vk::GeometryInstanceFlagsKHR::empty(),
+            mask: 0xff,
+            shader_binding_table_record_offset: 0,
+            transform: [
+                1.0, 0.0, 0.0, 0.0, //
+                0.0, 1.0, 0.0, 0.0, //
+                0.0, 0.0, 1.0, 0.0, //
+            ],
+        }
+    }
+
+    /// Sets the custom index on this instance.
+    pub fn custom_index(mut self, custom_index: u32) -> Self {
+        self.custom_index = custom_index;
+        self
+    }
+
+    /// Sets the flags on this instance.
+    pub fn flags(mut self, flags: vk::GeometryInstanceFlagsKHR) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the visibility mask on this instance.
+    pub fn mask(mut self, mask: u8) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Sets the shader binding table record offset on this instance.
+    pub fn shader_binding_table_record_offset(mut self, offset: u32) -> Self {
+        self.shader_binding_table_record_offset = offset;
+        self
+    }
+
+    /// Sets the transform on this instance.
+    pub fn transform(mut self, transform: [f32; 12]) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    fn to_khr(self, blas_addr: vk::DeviceAddress) -> vk::AccelerationStructureInstanceKHR {
+        vk::AccelerationStructureInstanceKHR {
+            transform: vk::TransformMatrixKHR {
+                matrix: self.transform,
+            },
+            instance_custom_index_and_mask: vk::Packed24_8::new(self.custom_index, self.mask),
+            instance_shader_binding_table_record_offset_and_flags: vk::Packed24_8::new(
+                self.shader_binding_table_record_offset,
+                self.flags.as_raw() as _,
+            ),
+            acceleration_structure_reference: vk::AccelerationStructureReferenceKHR {
+                device_handle: blas_addr,
+            },
+        }
+    }
+}
+
+/// Triangle geometry, stored in buffer nodes, of a bottom-level acceleration structure.
+#[derive(Clone, Copy, Debug)]
+pub struct TriangleMesh {
+    /// Flags which apply to this geometry.
+    ///
+    /// The default value is `OPAQUE`.
+    pub flags: vk::GeometryFlagsKHR,
+
+    /// The optional buffer containing index data.
+    pub index_buffer: Option<AnyBufferNode>,
+
+    /// The offset, in bytes, of the first index within `index_buffer`.
+    pub index_offset: vk::DeviceSize,
+
+    /// The type of each index within `index_buffer`.
+    pub index_type: vk::IndexType,
+
+    /// The number of triangles in this geometry.
+    pub triangle_count: u32,
+
+    /// The buffer containing vertex data.
+    pub vertex_buffer: AnyBufferNode,
+
+    /// The number of vertices within `vertex_buffer`.
+    pub vertex_count: u32,
+
+    /// The format of each vertex position.
+    ///
+    /// The default value is `R32G32B32_SFLOAT`.
+    pub vertex_format: vk::Format,
+
+    /// The offset, in bytes, of the first vertex within `vertex_buffer`.
+    pub vertex_offset: vk::DeviceSize,
+
+    /// The stride, in bytes, between each vertex.
+    ///
+    /// The default value is `12`.
+    pub vertex_stride: vk::DeviceSize,
+}
+
+impl TriangleMesh {
+    /// Specifies non-indexed triangles where every three vertices form one triangle.
+    pub fn new(vertex_buffer: impl Into<AnyBufferNode>, vertex_count: u32) -> Self {
+        let vertex_buffer = vertex_buffer.into();
+
+        Self {
+            flags: vk::GeometryFlagsKHR::OPAQUE,
+            index_buffer: None,
+            index_offset: 0,
+            index_type: vk::IndexType::NONE_KHR,
+            triangle_count: vertex_count / 3,
+            vertex_buffer,
+            vertex_count,
+            vertex_format: vk::Format::R32G32B32_SFLOAT,
+            vertex_offset: 0,
+            vertex_stride: 12,
+        }
+    }
+
+    /// Specifies indexed triangles where every three indices form one triangle.
+    pub fn indexed(
+        vertex_buffer: impl Into<AnyBufferNode>,
+        vertex_count: u32,
+        index_buffer: impl Into<AnyBufferNode>,
+        index_type: vk::IndexType,
+        triangle_count: u32,
+    ) -> Self {
+        let index_buffer = Some(index_buffer.into());
+
+        Self {
+            index_buffer,
+            index_type,
+            triangle_count,
+            ..Self::new(vertex_buffer, vertex_count)
+        }
+    }
+
+    /// Sets the flags on this instance.
+    pub fn flags(mut self, flags: vk::GeometryFlagsKHR) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the index offset on this instance.
+    pub fn index_offset(mut self, offset: vk::DeviceSize) -> Self {
+        self.index_offset = offset;
+        self
+    }
+
+    /// Sets the vertex format and stride on this instance.
+    pub fn vertex_format(mut self, format: vk::Format, stride: vk::DeviceSize) -> Self {
+        self.vertex_format = format;
+        self.vertex_stride = stride;
+        self
+    }
+
+    /// Sets the vertex offset on this instance.
+    pub fn vertex_offset(mut self, offset: vk::DeviceSize) -> Self {
+        self.vertex_offset = offset;
+        self
+    }
+
+    fn geometry(
+        &self,
+        render_graph: &RenderGraph,
+    ) -> (
+        AccelerationStructureGeometry,
+        vk::AccelerationStructureBuildRangeInfoKHR,
+    ) {
+        let index_addr = self
+            .index_buffer
+            .map(|index_buffer| render_graph.node_device_address(index_buffer) + self.index_offset)
+            .unwrap_or_default();
+        let vertex_addr = render_graph.node_device_address(self.vertex_buffer) + self.vertex_offset;
+
+        (
+            AccelerationStructureGeometry::new(
+                self.triangle_count,
+                AccelerationStructureGeometryData::triangles(
+                    index_addr,
+                    self.index_type,
+                    self.vertex_count.saturating_sub(1),
+                    None,
+                    vertex_addr,
+                    self.vertex_format,
+                    self.vertex_stride,
+                ),
+            )
+            .flags(self.flags),
+            vk::AccelerationStructureBuildRangeInfoKHR::default()
+                .primitive_count(self.triangle_count),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::graph::node::AccelerationStructureNode};
+
+    #[test]
+    pub fn acceleration_structure_instance_to_khr() {
+        let transform = [
+            1.0, 2.0, 3.0, 4.0, //
+            5.0, 6.0, 7.0, 8.0, //
+            9.0, 10.0, 11.0, 12.0, //
+        ];
+        let instance = AccelerationStructureInstance::new(AccelerationStructureNode::new(0))
+            .custom_index(0x12_3456)
+            .flags(
+                vk::GeometryInstanceFlagsKHR::TRIANGLE_FACING_CULL_DISABLE
+                    | vk::GeometryInstanceFlagsKHR::FORCE_OPAQUE,
+            )
+            .mask(0x7f)
+            .shader_binding_table_record_offset(0xab_cdef)
+            .transform(transform)
+            .to_khr(0xdead_beef_0000);
+
+        assert_eq!(instance.transform.matrix, transform);
+        assert_eq!(instance.instance_custom_index_and_mask.low_24(), 0x12_3456);
+        assert_eq!(instance.instance_custom_index_and_mask.high_8(), 0x7f);
+        assert_eq!(
+            instance
+                .instance_shader_binding_table_record_offset_and_flags
+                .low_24(),
+            0xab_cdef
+        );
+        assert_eq!(
+            instance
+                .instance_shader_binding_table_record_offset_and_flags
+                .high_8() as u32,
+            (vk::GeometryInstanceFlagsKHR::TRIANGLE_FACING_CULL_DISABLE
+                | vk::GeometryInstanceFlagsKHR::FORCE_OPAQUE)
+                .as_raw()
+        );
+        assert_eq!(
+            unsafe { instance.acceleration_structure_reference.device_handle },
+            0xdead_beef_0000
+        );
+    }
+
+    #[test]
+    pub fn acceleration_structure_instance_default() {
+        let instance =
+            AccelerationStructureInstance::new(AccelerationStructureNode::new(0)).to_khr(0);
+
+        assert_eq!(instance.instance_custom_index_and_mask.low_24(), 0);
+        assert_eq!(instance.instance_custom_index_and_mask.high_8(), 0xff);
+        assert_eq!(
+            instance
+                .instance_shader_binding_table_record_offset_and_flags
+                .high_8(),
+            0
+        );
+    }
+}
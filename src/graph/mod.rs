@@ -5,6 +5,7 @@
 pub mod node;
 pub mod pass_ref;
 
+mod accel_struct;
 mod binding;
 mod edge;
 mod info;
@@ -12,6 +13,7 @@ mod resolver;
 mod swapchain;
 
 pub use self::{
+    accel_struct::{AccelerationStructureBuilder, AccelerationStructureInstance, TriangleMesh},
     binding::{Bind, Unbind},
     resolver::Resolver,
 };
@@ -31,6 +33,8 @@ use {
     },
     crate::driver::{
         DescriptorBindingMap,
+        accel_struct::AccelerationStructureQueryPool,
+        bindless::{BindlessHeap, BindlessHeapUse},
         buffer::Buffer,
         compute::ComputePipeline,
         device::Device,
@@ -40,7 +44,7 @@ use {
         image_subresource_range_from_layers,
         ray_trace::RayTracePipeline,
         render_pass::ResolveMode,
-        shader::PipelineDescriptorInfo,
+        shader::{PipelineDescriptorInfo, SamplerInfo},
         vk_sync::AccessType,
     },
     ash::vk,
@@ -146,10 +150,18 @@ impl From<[u8; 4]> for ClearColorValue {
     }
 }
 
+/// A bindless heap bound to an execution.
+#[derive(Debug)]
+struct BindlessHeapBinding {
+    heap: Arc<BindlessHeap>,
+    _use: BindlessHeapUse,
+}
+
 #[derive(Default)]
 struct Execution {
     accesses: HashMap<NodeIndex, Vec<SubresourceAccess>>,
     bindings: BTreeMap<Descriptor, (NodeIndex, Option<ViewType>)>,
+    samplers: BTreeMap<Descriptor, SamplerInfo>,
 
     correlated_view_mask: u32,
     depth_stencil: Option<DepthStencilMode>,
@@ -172,6 +184,7 @@ struct Execution {
     )>,
     depth_stencil_store: Option<Attachment>,
 
+    bindless_heap: Option<Arc<BindlessHeapBinding>>,
     func: Option<ExecutionFunction>,
     pipeline: Option<ExecutionPipeline>,
 }
@@ -183,6 +196,7 @@ impl Debug for Execution {
         f.debug_struct("Execution")
             .field("accesses", &self.accesses)
             .field("bindings", &self.bindings)
+            .field("samplers", &self.samplers)
             .field("depth_stencil", &self.depth_stencil)
             .field("color_attachments", &self.color_attachments)
             .field("color_clears", &self.color_clears)
@@ -194,6 +208,7 @@ impl Debug for Execution {
             .field("depth_stencil_load", &self.depth_stencil_load)
             .field("depth_stencil_resolve", &self.depth_stencil_resolve)
             .field("depth_stencil_store", &self.depth_stencil_store)
+            .field("bindless_heap", &self.bindless_heap)
             .field("pipeline", &self.pipeline)
             .finish()
     }
@@ -509,6 +524,105 @@ impl RenderGraph {
             .submit_pass()
     }
 
+    /// Copies an acceleration structure into a smaller acceleration structure, removing unused
+    /// memory.
+    ///
+    /// `src_node` must have been built using
+    /// [`vk::BuildAccelerationStructureFlagsKHR::ALLOW_COMPACTION`] and `dst_node` must be at least
+    /// the compacted size returned by [`RenderGraph::query_accel_struct`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::accel_struct::{AccelerationStructure, AccelerationStructureInfo, AccelerationStructureQueryPool};
+    /// # use screen_13::graph::RenderGraph;
+    /// # use screen_13::pool::{Pool};
+    /// # use screen_13::pool::lazy::{LazyPool};
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let mut pool = LazyPool::new(&device);
+    /// # let mut my_graph = RenderGraph::new();
+    /// # let my_blas = AccelerationStructure::create(&device, AccelerationStructureInfo::blas(1024))?;
+    /// # let my_blas = my_graph.bind_node(my_blas);
+    /// # let query_ty = vk::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR;
+    /// # let query_pool = Arc::new(AccelerationStructureQueryPool::create(&device, query_ty, 1)?);
+    /// // After building my_blas and submitting a graph which called query_accel_struct:
+    /// if let Some(size) = AccelerationStructureQueryPool::result(&query_pool, 0)? {
+    ///     let compacted_blas = pool.lease(AccelerationStructureInfo::blas(size))?;
+    ///     let compacted_blas = my_graph.bind_node(compacted_blas);
+    ///
+    ///     my_graph.compact_accel_struct(my_blas, compacted_blas);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn compact_accel_struct(
+        &mut self,
+        src_node: impl Into<AnyAccelerationStructureNode>,
+        dst_node: impl Into<AnyAccelerationStructureNode>,
+    ) -> &mut Self {
+        self.copy_accel_struct_mode(
+            src_node.into(),
+            dst_node.into(),
+            vk::CopyAccelerationStructureModeKHR::COMPACT,
+        )
+    }
+
+    /// Copies an acceleration structure into another acceleration structure of at least the same
+    /// size.
+    pub fn copy_accel_struct(
+        &mut self,
+        src_node: impl Into<AnyAccelerationStructureNode>,
+        dst_node: impl Into<AnyAccelerationStructureNode>,
+    ) -> &mut Self {
+        let src_node = src_node.into();
+        let dst_node = dst_node.into();
+
+        debug_assert!(
+            self.node_info(dst_node).size >= self.node_info(src_node).size,
+            "Destination acceleration structure is smaller than source"
+        );
+
+        self.copy_accel_struct_mode(
+            src_node,
+            dst_node,
+            vk::CopyAccelerationStructureModeKHR::CLONE,
+        )
+    }
+
+    #[profiling::function]
+    fn copy_accel_struct_mode(
+        &mut self,
+        src_node: AnyAccelerationStructureNode,
+        dst_node: AnyAccelerationStructureNode,
+        mode: vk::CopyAccelerationStructureModeKHR,
+    ) -> &mut Self {
+        debug_assert_eq!(
+            self.node_info(src_node).ty,
+            self.node_info(dst_node).ty,
+            "Acceleration structure types must match"
+        );
+
+        self.begin_pass("copy acceleration structure")
+            .access_node(src_node, AccessType::AccelerationStructureBuildRead)
+            .access_node(dst_node, AccessType::AccelerationStructureBuildWrite)
+            .record_cmd_buf(move |device, cmd_buf, bindings| unsafe {
+                Device::expect_accel_struct_ext(device).cmd_copy_acceleration_structure(
+                    cmd_buf,
+                    &vk::CopyAccelerationStructureInfoKHR::default()
+                        .src(*bindings[src_node])
+                        .dst(*bindings[dst_node])
+                        .mode(mode),
+                );
+            })
+            .submit_pass()
+    }
+
     /// Copy data between buffers
     pub fn copy_buffer(
         &mut self,
@@ -884,6 +998,35 @@ impl RenderGraph {
         .submit_pass()
     }
 
+    /// Deserializes an acceleration structure from a buffer written by
+    /// [`RenderGraph::serialize_accel_struct`].
+    ///
+    /// `src_node` must have been created with the `SHADER_DEVICE_ADDRESS` usage flag and a device
+    /// address aligned to 256 bytes. Use
+    /// [`AccelerationStructure::deserialized_info`](crate::driver::accel_struct::AccelerationStructure::deserialized_info)
+    /// to check that the data is compatible with the device and to create `dst_node`.
+    #[profiling::function]
+    pub fn deserialize_accel_struct(
+        &mut self,
+        src_node: impl Into<AnyBufferNode>,
+        dst_node: impl Into<AnyAccelerationStructureNode>,
+    ) -> &mut Self {
+        let src_node = src_node.into();
+        let dst_node = dst_node.into();
+
+        self.begin_pass("deserialize acceleration structure")
+            .access_node(src_node, AccessType::AccelerationStructureBufferRead)
+            .access_node(dst_node, AccessType::AccelerationStructureBuildWrite)
+            .record_acceleration(move |acceleration, bindings| {
+                let src_addr = Buffer::device_address(&bindings[src_node]);
+
+                debug_assert_eq!(src_addr % 256, 0, "Source address must be aligned to 256");
+
+                acceleration.deserialize_structure(src_addr, dst_node);
+            })
+            .submit_pass()
+    }
+
     /// Fill a region of a buffer with a fixed value.
     pub fn fill_buffer(&mut self, buffer_node: impl Into<AnyBufferNode>, data: u32) -> &mut Self {
         let buffer_node = buffer_node.into();
@@ -958,6 +1101,32 @@ impl RenderGraph {
         node.get(self)
     }
 
+    /// Writes a property of a built acceleration structure, such as the compacted size, into a query
+    /// of the given query pool.
+    ///
+    /// The result may be read using [`AccelerationStructureQueryPool::result`] once the commands of
+    /// this graph have executed.
+    #[profiling::function]
+    pub fn query_accel_struct(
+        &mut self,
+        accel_struct_node: impl Into<AnyAccelerationStructureNode>,
+        query_pool: &Arc<AccelerationStructureQueryPool>,
+        query: u32,
+    ) -> &mut Self {
+        let accel_struct_node = accel_struct_node.into();
+        let query_pool = Arc::clone(query_pool);
+
+        self.begin_pass("query acceleration structure")
+            .access_node(
+                accel_struct_node,
+                AccessType::AccelerationStructureBuildRead,
+            )
+            .record_acceleration(move |acceleration, _| {
+                acceleration.write_structure_properties(&[accel_struct_node], &query_pool, query);
+            })
+            .submit_pass()
+    }
+
     /// Finalizes the graph and provides an object with functions for submitting the resulting
     /// commands.
     #[profiling::function]
@@ -970,6 +1139,39 @@ impl RenderGraph {
         Resolver::new(self)
     }
 
+    /// Serializes an acceleration structure into a buffer in a format which may be stored, for
+    /// example in a file, and later deserialized using [`RenderGraph::deserialize_accel_struct`].
+    ///
+    /// `dst_node` must have been created with the `SHADER_DEVICE_ADDRESS` usage flag, a device
+    /// address aligned to 256 bytes and a size of at least the serialization size returned by
+    /// [`RenderGraph::query_accel_struct`] using
+    /// [`vk::QueryType::ACCELERATION_STRUCTURE_SERIALIZATION_SIZE_KHR`].
+    #[profiling::function]
+    pub fn serialize_accel_struct(
+        &mut self,
+        src_node: impl Into<AnyAccelerationStructureNode>,
+        dst_node: impl Into<AnyBufferNode>,
+    ) -> &mut Self {
+        let src_node = src_node.into();
+        let dst_node = dst_node.into();
+
+        self.begin_pass("serialize acceleration structure")
+            .access_node(src_node, AccessType::AccelerationStructureBuildRead)
+            .access_node(dst_node, AccessType::AccelerationStructureBufferWrite)
+            .record_acceleration(move |acceleration, bindings| {
+                let dst_addr = Buffer::device_address(&bindings[dst_node]);
+
+                debug_assert_eq!(
+                    dst_addr % 256,
+                    0,
+                    "Destination address must be aligned to 256"
+                );
+
+                acceleration.serialize_structure(src_node, dst_addr);
+            })
+            .submit_pass()
+    }
+
     /// Removes a node from this graph.
     ///
     /// Future access to `node` on this graph will return invalid results.
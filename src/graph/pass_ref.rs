@@ -3,19 +3,23 @@
 use {
     super::{
         AccelerationStructureLeaseNode, AccelerationStructureNode, AnyAccelerationStructureNode,
-        AnyBufferNode, AnyImageNode, Area, Attachment, Bind, Binding, BufferLeaseNode, BufferNode,
-        ClearColorValue, Edge, Execution, ExecutionFunction, ExecutionPipeline, ImageLeaseNode,
-        ImageNode, Information, Node, NodeIndex, Pass, RenderGraph, SampleCount,
-        SwapchainImageNode,
+        AnyBufferNode, AnyImageNode, Area, Attachment, Bind, Binding, BindlessHeapBinding,
+        BufferLeaseNode, BufferNode, ClearColorValue, Edge, Execution, ExecutionFunction,
+        ExecutionPipeline, ImageLeaseNode, ImageNode, Information, Node, NodeIndex, Pass,
+        RenderGraph, SampleCount, SwapchainImageNode,
     },
     crate::driver::{
+        DriverError,
         accel_struct::{
             AccelerationStructure, AccelerationStructureGeometry,
-            AccelerationStructureGeometryInfo, DeviceOrHostAddress,
+            AccelerationStructureGeometryInfo, AccelerationStructureQueryPool, DeviceOrHostAddress,
         },
-        buffer::{Buffer, BufferSubresourceRange},
+        bindless::{BindlessHeap, BindlessIndex, BindlessResource},
+        buffer::{Buffer, BufferSubresourceRange, BufferViewInfo},
         compute::ComputePipeline,
+        descriptor_image_layout,
         device::Device,
+        format_aspect_mask,
         graphic::{DepthStencilMode, GraphicPipeline},
         image::{
             Image, ImageViewInfo, image_subresource_range_contains,
@@ -23,10 +27,12 @@ use {
         },
         ray_trace::RayTracePipeline,
         render_pass::ResolveMode,
+        shader::{Descriptor as ShaderDescriptor, SamplerInfo},
         vk_sync::AccessType,
     },
     ash::vk,
-    log::trace,
+    bytemuck::{Pod, bytes_of},
+    log::{trace, warn},
     std::{
         cell::RefCell,
         marker::PhantomData,
@@ -35,6 +41,9 @@ use {
     },
 };
 
+#[cfg(debug_assertions)]
+use crate::driver::validate_push_constants;
+
 /// Alias for the index of a framebuffer attachment.
 pub type AttachmentIndex = u32;
 
@@ -419,6 +428,124 @@ impl Acceleration<'_> {
         self
     }
 
+    /// Copy an acceleration structure into a smaller acceleration structure, removing unused
+    /// memory.
+    ///
+    /// `src_accel_struct` must have been built using
+    /// [`vk::BuildAccelerationStructureFlagsKHR::ALLOW_COMPACTION`] and `dst_accel_struct` must be at
+    /// least the compacted size returned by [`Acceleration::write_structure_properties`].
+    ///
+    /// The source must be accessed using [`AccessType::AccelerationStructureBuildRead`] and the
+    /// destination must be accessed using [`AccessType::AccelerationStructureBuildWrite`].
+    ///
+    /// See [`RenderGraph::compact_accel_struct`] for a complete pass.
+    pub fn compact_structure(
+        &self,
+        src_accel_struct: impl Into<AnyAccelerationStructureNode>,
+        dst_accel_struct: impl Into<AnyAccelerationStructureNode>,
+    ) -> &Self {
+        self.copy_structure_mode(
+            src_accel_struct.into(),
+            dst_accel_struct.into(),
+            vk::CopyAccelerationStructureModeKHR::COMPACT,
+        )
+    }
+
+    /// Copy an acceleration structure into another acceleration structure of at least the same
+    /// size.
+    ///
+    /// The source must be accessed using [`AccessType::AccelerationStructureBuildRead`] and the
+    /// destination must be accessed using [`AccessType::AccelerationStructureBuildWrite`].
+    ///
+    /// See [`RenderGraph::copy_accel_struct`] for a complete pass.
+    pub fn copy_structure(
+        &self,
+        src_accel_struct: impl Into<AnyAccelerationStructureNode>,
+        dst_accel_struct: impl Into<AnyAccelerationStructureNode>,
+    ) -> &Self {
+        self.copy_structure_mode(
+            src_accel_struct.into(),
+            dst_accel_struct.into(),
+            vk::CopyAccelerationStructureModeKHR::CLONE,
+        )
+    }
+
+    fn copy_structure_mode(
+        &self,
+        src_accel_struct: AnyAccelerationStructureNode,
+        dst_accel_struct: AnyAccelerationStructureNode,
+        mode: vk::CopyAccelerationStructureModeKHR,
+    ) -> &Self {
+        unsafe {
+            Device::expect_accel_struct_ext(self.device).cmd_copy_acceleration_structure(
+                self.cmd_buf,
+                &vk::CopyAccelerationStructureInfoKHR::default()
+                    .src(*self.bindings[src_accel_struct])
+                    .dst(*self.bindings[dst_accel_struct])
+                    .mode(mode),
+            );
+        }
+
+        self
+    }
+
+    /// Deserialize an acceleration structure from memory written by
+    /// [`Acceleration::serialize_structure`].
+    ///
+    /// `src_addr` must be aligned to 256 bytes. A device address must be accessed using
+    /// [`AccessType::AccelerationStructureBufferRead`] and the destination must be accessed using
+    /// [`AccessType::AccelerationStructureBuildWrite`].
+    ///
+    /// See [`RenderGraph::deserialize_accel_struct`] for a complete pass.
+    pub fn deserialize_structure(
+        &self,
+        src_addr: impl Into<DeviceOrHostAddress>,
+        dst_accel_struct: impl Into<AnyAccelerationStructureNode>,
+    ) -> &Self {
+        let dst_accel_struct = dst_accel_struct.into();
+
+        unsafe {
+            Device::expect_accel_struct_ext(self.device).cmd_copy_memory_to_acceleration_structure(
+                self.cmd_buf,
+                &vk::CopyMemoryToAccelerationStructureInfoKHR::default()
+                    .src(src_addr.into().into())
+                    .dst(*self.bindings[dst_accel_struct])
+                    .mode(vk::CopyAccelerationStructureModeKHR::DESERIALIZE),
+            );
+        }
+
+        self
+    }
+
+    /// Serialize an acceleration structure into memory in a format which may be stored and later
+    /// deserialized on a compatible device.
+    ///
+    /// `dst_addr` must be aligned to 256 bytes and have space for the serialization size returned
+    /// by [`Acceleration::write_structure_properties`]. The source must be accessed using
+    /// [`AccessType::AccelerationStructureBuildRead`] and a device address must be accessed using
+    /// [`AccessType::AccelerationStructureBufferWrite`].
+    ///
+    /// See [`RenderGraph::serialize_accel_struct`] for a complete pass.
+    pub fn serialize_structure(
+        &self,
+        src_accel_struct: impl Into<AnyAccelerationStructureNode>,
+        dst_addr: impl Into<DeviceOrHostAddress>,
+    ) -> &Self {
+        let src_accel_struct = src_accel_struct.into();
+
+        unsafe {
+            Device::expect_accel_struct_ext(self.device).cmd_copy_acceleration_structure_to_memory(
+                self.cmd_buf,
+                &vk::CopyAccelerationStructureToMemoryInfoKHR::default()
+                    .src(*self.bindings[src_accel_struct])
+                    .dst(dst_addr.into().into())
+                    .mode(vk::CopyAccelerationStructureModeKHR::SERIALIZE),
+            );
+        }
+
+        self
+    }
+
     /// Update an acceleration structure.
     ///
     /// Requires a scratch buffer which was created with the following requirements:
@@ -699,6 +826,59 @@ impl Acceleration<'_> {
 
         self
     }
+
+    /// Writes properties of built acceleration structures into consecutive queries of a query pool,
+    /// starting at `first_query`.
+    ///
+    /// The queries are reset before being written and the results may be read using
+    /// [`AccelerationStructureQueryPool::result`] once the commands have executed. Each acceleration
+    /// structure must be accessed using [`AccessType::AccelerationStructureBuildRead`].
+    ///
+    /// See [`RenderGraph::query_accel_struct`] for a complete pass.
+    pub fn write_structure_properties(
+        &self,
+        accel_structs: &[AnyAccelerationStructureNode],
+        query_pool: &AccelerationStructureQueryPool,
+        first_query: u32,
+    ) -> &Self {
+        thread_local! {
+            static TLS: RefCell<Vec<vk::AccelerationStructureKHR>> = Default::default();
+        }
+
+        debug_assert!(
+            first_query as usize + accel_structs.len()
+                <= AccelerationStructureQueryPool::query_count(query_pool) as usize,
+            "Query out of bounds"
+        );
+
+        TLS.with_borrow_mut(|tls| {
+            tls.clear();
+            tls.extend(
+                accel_structs
+                    .iter()
+                    .map(|&accel_struct| *self.bindings[accel_struct]),
+            );
+
+            unsafe {
+                self.device.cmd_reset_query_pool(
+                    self.cmd_buf,
+                    **query_pool,
+                    first_query,
+                    tls.len() as _,
+                );
+                Device::expect_accel_struct_ext(self.device)
+                    .cmd_write_acceleration_structures_properties(
+                        self.cmd_buf,
+                        tls,
+                        AccelerationStructureQueryPool::query_ty(query_pool),
+                        **query_pool,
+                        first_query,
+                    );
+            }
+        });
+
+        self
+    }
 }
 
 /// Specifies the information and data used to build an acceleration structure.
@@ -1510,6 +1690,88 @@ impl Compute<'_> {
 
         self
     }
+
+    /// Updates push constants using the bytes of a [plain old data] value.
+    ///
+    /// Behaves similarly to [`Compute::push_constants`] except that `data` is a typed value instead
+    /// of a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the size of `T` is not a multiple of four or if any bytes of
+    /// `data` are not declared by the push constant ranges of the currently bound pipeline. Each
+    /// shader stage which does not declare the data is logged before panicking.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::buffer::{Buffer, BufferInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::shader::{Shader};
+    /// # use screen_13::graph::RenderGraph;
+    /// # use bytemuck::{Pod, Zeroable};
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy, Pod, Zeroable)]
+    /// struct PushConstants {
+    ///     the_answer: u32,
+    /// }
+    ///
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let info = ComputePipelineInfo::default();
+    /// # let shader = Shader::new_compute([0u8; 1].as_slice());
+    /// # let my_compute_pipeline = Arc::new(ComputePipeline::create(&device, info, shader)?);
+    /// # let mut my_graph = RenderGraph::new();
+    /// my_graph.begin_pass("compute the ultimate question")
+    ///         .bind_pipeline(&my_compute_pipeline)
+    ///         .record_compute(move |compute, bindings| {
+    ///             compute.push_constants_typed(&PushConstants { the_answer: 42 })
+    ///                    .dispatch(1, 1, 1);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [plain old data]: Pod
+    pub fn push_constants_typed<T>(&self, data: &T) -> &Self
+    where
+        T: Pod,
+    {
+        self.push_constants_typed_offset(0, data)
+    }
+
+    /// Updates push constants starting at the given `offset` using the bytes of a
+    /// [plain old data] value.
+    ///
+    /// Behaves similarly to [`Compute::push_constants_offset`] except that `data` is a typed value
+    /// instead of a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `offset` or the size of `T` are not multiples of four or if any
+    /// bytes of `data` are not declared by the push constant ranges of the currently bound
+    /// pipeline. Each shader stage which does not declare the data is logged before panicking.
+    ///
+    /// [plain old data]: Pod
+    #[profiling::function]
+    pub fn push_constants_typed_offset<T>(&self, offset: u32, data: &T) -> &Self
+    where
+        T: Pod,
+    {
+        #[cfg(debug_assertions)]
+        validate_push_constants(
+            self.pipeline.push_constants.as_slice(),
+            offset,
+            size_of::<T>() as _,
+        );
+
+        self.push_constants_offset(offset, bytes_of(data))
+    }
 }
 
 /// Describes the SPIR-V binding index, and optionally a specific descriptor set
@@ -2202,32 +2464,556 @@ impl Draw<'_> {
     /// # Ok(()) }
     /// ```
     ///
-    /// [gpuinfo.org]: https://vulkan.gpuinfo.org/displaydevicelimit.php?name=maxPushConstantsSize&platform=all
+    /// [gpuinfo.org]: https://vulkan.gpuinfo.org/displaydevicelimit.php?name=maxPushConstantsSize&platform=all
+    #[profiling::function]
+    pub fn push_constants_offset(&self, offset: u32, data: &[u8]) -> &Self {
+        for push_const in self.pipeline.push_constants.iter() {
+            // Determine the range of the overall pipline push constants which overlap with `data`
+            let push_const_end = push_const.offset + push_const.size;
+            let data_end = offset + data.len() as u32;
+            let end = data_end.min(push_const_end);
+            let start = offset.max(push_const.offset);
+
+            if end > start {
+                trace!(
+                    "      push constants {:?} {}..{}",
+                    push_const.stage_flags, start, end
+                );
+
+                unsafe {
+                    self.device.cmd_push_constants(
+                        self.cmd_buf,
+                        self.pipeline.layout,
+                        push_const.stage_flags,
+                        start,
+                        &data[(start - offset) as usize..(end - offset) as usize],
+                    );
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Updates push constants using the bytes of a [plain old data] value.
+    ///
+    /// Behaves similarly to [`Draw::push_constants`] except that `data` is a typed value instead
+    /// of a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the size of `T` is not a multiple of four or if any bytes of
+    /// `data` are not declared by the push constant ranges of the currently bound pipeline. Each
+    /// shader stage which does not declare the data is logged before panicking.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::graphic::{GraphicPipeline, GraphicPipelineInfo};
+    /// # use screen_13::driver::image::{Image, ImageInfo};
+    /// # use screen_13::graph::RenderGraph;
+    /// # use screen_13::driver::shader::Shader;
+    /// # use bytemuck::{Pod, Zeroable};
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy, Pod, Zeroable)]
+    /// struct PushConstants {
+    ///     the_answer: u32,
+    /// }
+    ///
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let my_frag_code = [0u8; 1];
+    /// # let my_vert_code = [0u8; 1];
+    /// # let vert = Shader::new_vertex(my_vert_code.as_slice());
+    /// # let frag = Shader::new_fragment(my_frag_code.as_slice());
+    /// # let info = GraphicPipelineInfo::default();
+    /// # let my_graphic_pipeline = Arc::new(GraphicPipeline::create(&device, info, [vert, frag])?);
+    /// # let info = ImageInfo::image_2d(32, 32, vk::Format::R8G8B8A8_UNORM, vk::ImageUsageFlags::SAMPLED);
+    /// # let swapchain_image = Image::create(&device, info)?;
+    /// # let mut my_graph = RenderGraph::new();
+    /// # let swapchain_image = my_graph.bind_node(swapchain_image);
+    /// my_graph.begin_pass("draw a quad")
+    ///         .bind_pipeline(&my_graphic_pipeline)
+    ///         .store_color(0, swapchain_image)
+    ///         .record_subpass(move |subpass, bindings| {
+    ///             subpass.push_constants_typed(&PushConstants { the_answer: 42 })
+    ///                    .draw(6, 1, 0, 0);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [plain old data]: Pod
+    pub fn push_constants_typed<T>(&self, data: &T) -> &Self
+    where
+        T: Pod,
+    {
+        self.push_constants_typed_offset(0, data)
+    }
+
+    /// Updates push constants starting at the given `offset` using the bytes of a
+    /// [plain old data] value.
+    ///
+    /// Behaves similarly to [`Draw::push_constants_offset`] except that `data` is a typed value
+    /// instead of a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `offset` or the size of `T` are not multiples of four or if any
+    /// bytes of `data` are not declared by the push constant ranges of the currently bound
+    /// pipeline. Each shader stage which does not declare the data is logged before panicking.
+    ///
+    /// [plain old data]: Pod
+    #[profiling::function]
+    pub fn push_constants_typed_offset<T>(&self, offset: u32, data: &T) -> &Self
+    where
+        T: Pod,
+    {
+        #[cfg(debug_assertions)]
+        validate_push_constants(&self.pipeline.push_constants, offset, size_of::<T>() as _);
+
+        self.push_constants_offset(offset, bytes_of(data))
+    }
+
+    /// Writes the given `node` to a descriptor of the push descriptor set of the currently bound
+    /// pipeline.
+    ///
+    /// Push descriptors may be updated between draws without allocating new descriptor sets, which
+    /// is useful when each draw of a pass reads a different resource. The descriptor set must be
+    /// the [`GraphicPipelineInfo::push_descriptor_set`] of the pipeline.
+    ///
+    /// The node must have been accessed by the pass, for example using
+    /// [`PipelinePassRef::read_node`], so that the resolver is able to track the access. Images are
+    /// written using a view of the whole image; see [`Draw::push_descriptor_as`] for other views.
+    ///
+    /// If the image view, buffer view or mutable sampler of the descriptor cannot be created the
+    /// error is logged and the descriptor is not written.
+    ///
+    /// [`GraphicPipelineInfo::push_descriptor_set`]: crate::driver::graphic::GraphicPipelineInfo::push_descriptor_set
+    ///
+    /// # Panics
+    ///
+    /// If `descriptor` was not discovered through shader reflection.
+    ///
+    /// In debug builds, panics if `descriptor` is not part of the push descriptor set of the
+    /// currently bound pipeline or if `node` was not accessed by the pass.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::graphic::{GraphicPipeline, GraphicPipelineInfo};
+    /// # use screen_13::driver::image::{Image, ImageInfo};
+    /// # use screen_13::driver::shader::Shader;
+    /// # use screen_13::graph::RenderGraph;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let my_frag_code = [0u8; 1];
+    /// # let my_vert_code = [0u8; 1];
+    /// # let vert = Shader::new_vertex(my_vert_code.as_slice());
+    /// # let frag = Shader::new_fragment(my_frag_code.as_slice());
+    /// let info = GraphicPipelineInfo::builder().push_descriptor_set(0);
+    /// let my_graphic_pipeline = Arc::new(GraphicPipeline::create(&device, info, [vert, frag])?);
+    /// # let mut my_graph = RenderGraph::new();
+    /// # let info = ImageInfo::image_2d(32, 32, vk::Format::R8G8B8A8_UNORM, vk::ImageUsageFlags::SAMPLED);
+    /// # let swapchain_image = my_graph.bind_node(Image::create(&device, info)?);
+    /// # let texture_a = my_graph.bind_node(Image::create(&device, info)?);
+    /// # let texture_b = my_graph.bind_node(Image::create(&device, info)?);
+    /// my_graph.begin_pass("draw two textured quads")
+    ///         .bind_pipeline(&my_graphic_pipeline)
+    ///         .store_color(0, swapchain_image)
+    ///         .read_node(texture_a)
+    ///         .read_node(texture_b)
+    ///         .record_subpass(move |subpass, bindings| {
+    ///             for (texture, first_vertex) in [(texture_a, 0), (texture_b, 6)] {
+    ///                 subpass.push_descriptor(0, texture)
+    ///                        .draw(6, 1, first_vertex, 0);
+    ///             }
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    #[profiling::function]
+    pub fn push_descriptor(&self, descriptor: impl Into<Descriptor>, node: impl Node) -> &Self {
+        self.push_descriptor_view(descriptor.into(), node.index(), None);
+
+        self
+    }
+
+    /// Writes the given `node` to a descriptor of the push descriptor set of the currently bound
+    /// pipeline. The node will be interpreted using `view_info`.
+    ///
+    /// Behaves similarly to [`Draw::push_descriptor`] except that images may be written using a
+    /// specific view and buffers may be written using a subregion.
+    #[profiling::function]
+    pub fn push_descriptor_as<N>(
+        &self,
+        descriptor: impl Into<Descriptor>,
+        node: N,
+        view_info: impl Into<N::Information>,
+    ) -> &Self
+    where
+        N: View,
+        <N as View>::Information: Into<ViewType>,
+    {
+        self.push_descriptor_view(
+            descriptor.into(),
+            node.index(),
+            Some(view_info.into().into()),
+        );
+
+        self
+    }
+
+    fn push_descriptor_view(
+        &self,
+        descriptor: Descriptor,
+        node_idx: NodeIndex,
+        view_info: Option<ViewType>,
+    ) {
+        if let Err(err) = self.try_push_descriptor_view(descriptor, node_idx, view_info) {
+            let (descriptor_set_idx, dst_binding, binding_offset) = descriptor.into_tuple();
+
+            warn!(
+                "unable to push descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}]: {err}"
+            );
+        }
+    }
+
+    fn try_push_descriptor_view(
+        &self,
+        descriptor: Descriptor,
+        node_idx: NodeIndex,
+        view_info: Option<ViewType>,
+    ) -> Result<(), DriverError> {
+        use std::slice::from_ref;
+
+        let (descriptor_set_idx, dst_binding, binding_offset) = descriptor.into_tuple();
+
+        debug_assert_eq!(
+            self.pipeline.descriptor_info.push_descriptor_set,
+            Some(descriptor_set_idx),
+            "descriptor set {descriptor_set_idx} is not a push descriptor set"
+        );
+
+        let (descriptor_info, _) = self
+            .pipeline
+            .descriptor_bindings
+            .get(&ShaderDescriptor {
+                set: descriptor_set_idx,
+                binding: dst_binding,
+            })
+            .unwrap_or_else(|| {
+                panic!(
+                    "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] was not discovered through shader reflection"
+                )
+            });
+        let descriptor_type = descriptor_info.descriptor_type();
+        let write = vk::WriteDescriptorSet::default()
+            .dst_binding(dst_binding)
+            .dst_array_element(binding_offset)
+            .descriptor_type(descriptor_type);
+        let binding = self.bindings.binding_ref(node_idx);
+
+        if let Some(image) = binding.as_driver_image() {
+            let mut image_view_info = view_info
+                .as_ref()
+                .and_then(ViewType::as_image)
+                .copied()
+                .unwrap_or_else(|| image.info.into());
+
+            // Handle default views which did not specify a particaular aspect
+            if image_view_info.aspect_mask.is_empty() {
+                image_view_info.aspect_mask = format_aspect_mask(image.info.fmt);
+            }
+
+            let image_view = Image::view(image, image_view_info)?;
+            let mut image_info = vk::DescriptorImageInfo::default()
+                .image_layout(descriptor_image_layout(
+                    descriptor_type,
+                    image_view_info.aspect_mask,
+                ))
+                .image_view(image_view);
+
+            if descriptor_info.is_mutable_sampler() {
+                let sampler_info = self
+                    .bindings
+                    .exec
+                    .samplers
+                    .get(&descriptor)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] uses a mutable sampler which was not bound"
+                        )
+                    });
+                image_info = image_info.sampler(Device::sampler(self.device, *sampler_info)?);
+            }
+
+            self.push_descriptor_writes(from_ref(&write.image_info(from_ref(&image_info))));
+        } else if let Some(buffer) = binding.as_driver_buffer() {
+            if matches!(
+                descriptor_type,
+                vk::DescriptorType::UNIFORM_TEXEL_BUFFER | vk::DescriptorType::STORAGE_TEXEL_BUFFER
+            ) {
+                let buffer_view_info = view_info
+                    .as_ref()
+                    .and_then(ViewType::as_texel_buffer)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] is a texel buffer which was not bound with a view format"
+                        )
+                    });
+                let buffer_view = Buffer::view(buffer, *buffer_view_info)?;
+
+                self.push_descriptor_writes(from_ref(
+                    &write.texel_buffer_view(from_ref(&buffer_view)),
+                ));
+            } else {
+                let range = view_info
+                    .as_ref()
+                    .and_then(ViewType::as_buffer)
+                    .unwrap_or(0..buffer.info.size);
+                let buffer_info = vk::DescriptorBufferInfo::default()
+                    .buffer(**buffer)
+                    .offset(range.start)
+                    .range(range.end - range.start);
+
+                self.push_descriptor_writes(from_ref(&write.buffer_info(from_ref(&buffer_info))));
+            }
+        } else if let Some(accel_struct) = binding.as_driver_acceleration_structure() {
+            let mut accel_struct_info = vk::WriteDescriptorSetAccelerationStructureKHR::default()
+                .acceleration_structures(from_ref(&**accel_struct));
+            let mut write = write.push_next(&mut accel_struct_info);
+            write.descriptor_count = 1;
+
+            self.push_descriptor_writes(from_ref(&write));
+        } else {
+            unreachable!("bound nodes are always images, buffers or acceleration structures");
+        }
+
+        Ok(())
+    }
+
+    fn push_descriptor_writes(&self, writes: &[vk::WriteDescriptorSet]) {
+        trace!("    push descriptors {writes:?}");
+
+        unsafe {
+            Device::expect_push_descriptor_ext(self.device).cmd_push_descriptor_set(
+                self.cmd_buf,
+                vk::PipelineBindPoint::GRAPHICS,
+                self.pipeline.layout,
+                self.pipeline.descriptor_info.push_descriptor_set.unwrap(),
+                writes,
+            );
+        }
+    }
+
+    /// Set triangle culling mode dynamically for a pass.
+    ///
+    /// The currently bound pipeline must have been created with
+    /// [`ExtendedDynamicState::cull_mode`] enabled.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the currently bound pipeline does not use this dynamic state.
+    ///
+    /// [`ExtendedDynamicState::cull_mode`]: crate::driver::graphic::ExtendedDynamicState::cull_mode
+    #[profiling::function]
+    pub fn set_cull_mode(&self, cull_mode: vk::CullModeFlags) -> &Self {
+        debug_assert!(
+            self.pipeline.info.extended_dynamic_state.cull_mode,
+            "pipeline does not use dynamic cull mode"
+        );
+
+        unsafe {
+            Device::expect_extended_dynamic_state_ext(self.device)
+                .cmd_set_cull_mode(self.cmd_buf, cull_mode);
+        }
+
+        self
+    }
+
+    /// Set depth bias factors dynamically for a pass.
+    ///
+    /// The currently bound pipeline must have been created with
+    /// [`GraphicPipelineInfo::dynamic_depth_bias`] enabled.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the currently bound pipeline does not use dynamic depth bias.
+    ///
+    /// [`GraphicPipelineInfo::dynamic_depth_bias`]: crate::driver::graphic::GraphicPipelineInfo::dynamic_depth_bias
+    #[profiling::function]
+    pub fn set_depth_bias(&self, constant_factor: f32, clamp: f32, slope_factor: f32) -> &Self {
+        debug_assert!(
+            self.pipeline.info.dynamic_depth_bias,
+            "pipeline does not use dynamic depth bias"
+        );
+
+        unsafe {
+            self.device
+                .cmd_set_depth_bias(self.cmd_buf, constant_factor, clamp, slope_factor);
+        }
+
+        self
+    }
+
+    /// Set whether depth bounds testing is enabled dynamically for a pass.
+    ///
+    /// The currently bound pipeline must have been created with
+    /// [`ExtendedDynamicState::depth_bounds_test`] enabled.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the currently bound pipeline does not use this dynamic state.
+    ///
+    /// [`ExtendedDynamicState::depth_bounds_test`]: crate::driver::graphic::ExtendedDynamicState::depth_bounds_test
+    #[profiling::function]
+    pub fn set_depth_bounds_test(&self, enable: bool) -> &Self {
+        debug_assert!(
+            self.pipeline.info.extended_dynamic_state.depth_bounds_test,
+            "pipeline does not use dynamic depth bounds test"
+        );
+
+        unsafe {
+            Device::expect_extended_dynamic_state_ext(self.device)
+                .cmd_set_depth_bounds_test_enable(self.cmd_buf, enable);
+        }
+
+        self
+    }
+
+    /// Set the depth comparison operator dynamically for a pass.
+    ///
+    /// The currently bound pipeline must have been created with
+    /// [`ExtendedDynamicState::depth_compare_op`] enabled.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the currently bound pipeline does not use this dynamic state.
+    ///
+    /// [`ExtendedDynamicState::depth_compare_op`]: crate::driver::graphic::ExtendedDynamicState::depth_compare_op
+    #[profiling::function]
+    pub fn set_depth_compare_op(&self, compare_op: vk::CompareOp) -> &Self {
+        debug_assert!(
+            self.pipeline.info.extended_dynamic_state.depth_compare_op,
+            "pipeline does not use dynamic depth compare op"
+        );
+
+        unsafe {
+            Device::expect_extended_dynamic_state_ext(self.device)
+                .cmd_set_depth_compare_op(self.cmd_buf, compare_op);
+        }
+
+        self
+    }
+
+    /// Set whether depth testing is enabled dynamically for a pass.
+    ///
+    /// The currently bound pipeline must have been created with
+    /// [`ExtendedDynamicState::depth_test`] enabled.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the currently bound pipeline does not use this dynamic state.
+    ///
+    /// [`ExtendedDynamicState::depth_test`]: crate::driver::graphic::ExtendedDynamicState::depth_test
+    #[profiling::function]
+    pub fn set_depth_test(&self, enable: bool) -> &Self {
+        debug_assert!(
+            self.pipeline.info.extended_dynamic_state.depth_test,
+            "pipeline does not use dynamic depth test"
+        );
+
+        unsafe {
+            Device::expect_extended_dynamic_state_ext(self.device)
+                .cmd_set_depth_test_enable(self.cmd_buf, enable);
+        }
+
+        self
+    }
+
+    /// Set whether depth writes are enabled dynamically for a pass.
+    ///
+    /// The currently bound pipeline must have been created with
+    /// [`ExtendedDynamicState::depth_write`] enabled.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the currently bound pipeline does not use this dynamic state.
+    ///
+    /// [`ExtendedDynamicState::depth_write`]: crate::driver::graphic::ExtendedDynamicState::depth_write
+    #[profiling::function]
+    pub fn set_depth_write(&self, enable: bool) -> &Self {
+        debug_assert!(
+            self.pipeline.info.extended_dynamic_state.depth_write,
+            "pipeline does not use dynamic depth write"
+        );
+
+        unsafe {
+            Device::expect_extended_dynamic_state_ext(self.device)
+                .cmd_set_depth_write_enable(self.cmd_buf, enable);
+        }
+
+        self
+    }
+
+    /// Set polygon front-facing orientation dynamically for a pass.
+    ///
+    /// The currently bound pipeline must have been created with
+    /// [`ExtendedDynamicState::front_face`] enabled.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the currently bound pipeline does not use this dynamic state.
+    ///
+    /// [`ExtendedDynamicState::front_face`]: crate::driver::graphic::ExtendedDynamicState::front_face
     #[profiling::function]
-    pub fn push_constants_offset(&self, offset: u32, data: &[u8]) -> &Self {
-        for push_const in self.pipeline.push_constants.iter() {
-            // Determine the range of the overall pipline push constants which overlap with `data`
-            let push_const_end = push_const.offset + push_const.size;
-            let data_end = offset + data.len() as u32;
-            let end = data_end.min(push_const_end);
-            let start = offset.max(push_const.offset);
+    pub fn set_front_face(&self, front_face: vk::FrontFace) -> &Self {
+        debug_assert!(
+            self.pipeline.info.extended_dynamic_state.front_face,
+            "pipeline does not use dynamic front face"
+        );
 
-            if end > start {
-                trace!(
-                    "      push constants {:?} {}..{}",
-                    push_const.stage_flags, start, end
-                );
+        unsafe {
+            Device::expect_extended_dynamic_state_ext(self.device)
+                .cmd_set_front_face(self.cmd_buf, front_face);
+        }
 
-                unsafe {
-                    self.device.cmd_push_constants(
-                        self.cmd_buf,
-                        self.pipeline.layout,
-                        push_const.stage_flags,
-                        start,
-                        &data[(start - offset) as usize..(end - offset) as usize],
-                    );
-                }
-            }
+        self
+    }
+
+    /// Set input primitive topology dynamically for a pass.
+    ///
+    /// The currently bound pipeline must have been created with
+    /// [`ExtendedDynamicState::primitive_topology`] enabled.
+    ///
+    /// The topology must be of the same class (point, line, or triangle) as the pipeline topology.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the currently bound pipeline does not use this dynamic state.
+    ///
+    /// [`ExtendedDynamicState::primitive_topology`]: crate::driver::graphic::ExtendedDynamicState::primitive_topology
+    #[profiling::function]
+    pub fn set_primitive_topology(&self, topology: vk::PrimitiveTopology) -> &Self {
+        debug_assert!(
+            self.pipeline.info.extended_dynamic_state.primitive_topology,
+            "pipeline does not use dynamic primitive topology"
+        );
+
+        unsafe {
+            Device::expect_extended_dynamic_state_ext(self.device)
+                .cmd_set_primitive_topology(self.cmd_buf, topology);
         }
 
         self
@@ -2280,6 +3066,69 @@ impl Draw<'_> {
         self
     }
 
+    /// Set stencil test operations dynamically for a pass.
+    ///
+    /// The currently bound pipeline must have been created with
+    /// [`ExtendedDynamicState::stencil_op`] enabled.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the currently bound pipeline does not use this dynamic state.
+    ///
+    /// [`ExtendedDynamicState::stencil_op`]: crate::driver::graphic::ExtendedDynamicState::stencil_op
+    #[profiling::function]
+    pub fn set_stencil_op(
+        &self,
+        face_mask: vk::StencilFaceFlags,
+        fail_op: vk::StencilOp,
+        pass_op: vk::StencilOp,
+        depth_fail_op: vk::StencilOp,
+        compare_op: vk::CompareOp,
+    ) -> &Self {
+        debug_assert!(
+            self.pipeline.info.extended_dynamic_state.stencil_op,
+            "pipeline does not use dynamic stencil op"
+        );
+
+        unsafe {
+            Device::expect_extended_dynamic_state_ext(self.device).cmd_set_stencil_op(
+                self.cmd_buf,
+                face_mask,
+                fail_op,
+                pass_op,
+                depth_fail_op,
+                compare_op,
+            );
+        }
+
+        self
+    }
+
+    /// Set whether stencil testing is enabled dynamically for a pass.
+    ///
+    /// The currently bound pipeline must have been created with
+    /// [`ExtendedDynamicState::stencil_test`] enabled.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the currently bound pipeline does not use this dynamic state.
+    ///
+    /// [`ExtendedDynamicState::stencil_test`]: crate::driver::graphic::ExtendedDynamicState::stencil_test
+    #[profiling::function]
+    pub fn set_stencil_test(&self, enable: bool) -> &Self {
+        debug_assert!(
+            self.pipeline.info.extended_dynamic_state.stencil_test,
+            "pipeline does not use dynamic stencil test"
+        );
+
+        unsafe {
+            Device::expect_extended_dynamic_state_ext(self.device)
+                .cmd_set_stencil_test_enable(self.cmd_buf, enable);
+        }
+
+        self
+    }
+
     /// Set the viewport dynamically for a pass.
     #[profiling::function]
     pub fn set_viewport(
@@ -2488,6 +3337,7 @@ impl<'a> PassRef<'a> {
             last_exec.func = Some(ExecutionFunction(Box::new(func)));
 
             Execution {
+                bindless_heap: last_exec.bindless_heap.clone(),
                 pipeline: last_exec.pipeline.clone(),
                 ..Default::default()
             }
@@ -2806,6 +3656,203 @@ where
         );
     }
 
+    /// Binds a bindless heap to the next recorded command buffer.
+    ///
+    /// The currently bound pipeline must have been created with a
+    /// [`ComputePipelineInfo::bindless_heap`] (or graphic or ray trace equivalent) using the same
+    /// information as `heap`; the heap descriptor set is bound at the descriptor set index given
+    /// to the pipeline. The heap remains bound for any later recorded command buffers of this
+    /// pass.
+    ///
+    /// Each index accessed by a shader must be declared using [`Self::access_bindless`], which binds
+    /// the image or buffer stored at that index to the graph so that the pass is correctly
+    /// synchronized. Descriptors removed from the heap remain valid until this pass has been
+    /// retired.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::{AccessType, DriverError};
+    /// # use screen_13::driver::bindless::{BindlessHeap, BindlessHeapInfo};
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::image::{Image, ImageInfo};
+    /// # use screen_13::driver::shader::Shader;
+    /// # use screen_13::graph::RenderGraph;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let my_shader_code = [0u8; 1];
+    /// let heap_info = BindlessHeapInfo::default();
+    /// let heap = Arc::new(BindlessHeap::create(&device, heap_info)?);
+    /// let info = ComputePipelineInfo::default().to_builder().bindless_heap(1, heap_info);
+    /// let my_compute_pipeline = Arc::new(ComputePipeline::create(&device, info, Shader::new_compute(my_shader_code.as_slice()))?);
+    /// # let info = ImageInfo::image_2d(32, 32, vk::Format::R8G8B8A8_UNORM, vk::ImageUsageFlags::SAMPLED);
+    /// let my_image = Arc::new(Image::create(&device, info)?);
+    /// let my_image_idx = BindlessHeap::insert_sampled_image(&heap, &my_image, info)?;
+    /// # let mut my_graph = RenderGraph::new();
+    /// my_graph.begin_pass("sample an image")
+    ///         .bind_pipeline(&my_compute_pipeline)
+    ///         .bind_bindless_heap(&heap)
+    ///         .access_bindless(my_image_idx, AccessType::ComputeShaderReadSampledImageOrUniformTexelBuffer)
+    ///         .record_compute(move |compute, bindings| {
+    ///             compute.push_constants(&my_image_idx.index().to_ne_bytes())
+    ///                    .dispatch(32, 32, 1);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`ComputePipelineInfo::bindless_heap`]: crate::driver::compute::ComputePipelineInfo::bindless_heap
+    pub fn bind_bindless_heap(mut self, heap: &Arc<BindlessHeap>) -> Self {
+        debug_assert_eq!(
+            self.pass
+                .as_ref()
+                .execs
+                .last()
+                .unwrap()
+                .pipeline
+                .as_ref()
+                .and_then(|pipeline| pipeline.descriptor_info().bindless_heap)
+                .map(|(_, info)| info),
+            Some(heap.info),
+            "incompatible bindless heap"
+        );
+
+        self.pass.as_mut().execs.last_mut().unwrap().bindless_heap =
+            Some(Arc::new(BindlessHeapBinding {
+                heap: Arc::clone(heap),
+                _use: BindlessHeap::use_heap(heap),
+            }));
+
+        self
+    }
+
+    /// Informs the pass that the next recorded command buffer will access the image or buffer
+    /// stored at `index` of the bound bindless heap using `access`.
+    ///
+    /// Images are accessed using the view they were inserted with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no bindless heap has been bound using [`Self::bind_bindless_heap`], if `index` is
+    /// a sampler, or if `index` is not resident in the heap.
+    pub fn access_bindless(mut self, index: BindlessIndex, access: AccessType) -> Self {
+        self.access_bindless_mut(index, access);
+
+        self
+    }
+
+    /// Informs the pass that the next recorded command buffer will access the image or buffer
+    /// stored at `index` of the bound bindless heap using `access`.
+    ///
+    /// See [`Self::access_bindless`].
+    pub fn access_bindless_mut(&mut self, index: BindlessIndex, access: AccessType) {
+        let resource = self
+            .pass
+            .as_ref()
+            .execs
+            .last()
+            .unwrap()
+            .bindless_heap
+            .as_ref()
+            .map(|binding| BindlessHeap::resource(&binding.heap, index))
+            .expect("bindless heap not bound")
+            .unwrap_or_else(|| panic!("bindless index {index:?} is not resident in the heap"));
+
+        // Binding reuses the node of any resource the graph already holds
+        let (node_idx, subresource) = match resource {
+            BindlessResource::Buffer(buffer) => {
+                let subresource = Subresource::Buffer((0..buffer.info.size).into());
+
+                (self.pass.graph.bind_node(buffer).index(), subresource)
+            }
+            BindlessResource::Image(image, view_info) => (
+                self.pass.graph.bind_node(image).index(),
+                Subresource::Image(view_info.into()),
+            ),
+        };
+        let access = SubresourceAccess {
+            access,
+            subresource,
+        };
+
+        self.pass
+            .as_mut()
+            .execs
+            .last_mut()
+            .unwrap()
+            .accesses
+            .entry(node_idx)
+            .and_modify(|accesses| accesses.push(access))
+            .or_insert(vec![access]);
+    }
+
+    /// Binds a sampler to the specified shader descriptor for the next recorded command buffer.
+    ///
+    /// The descriptor must be a sampler or combined image sampler which was specified as mutable
+    /// using [`SamplerBinding::Mutable`]; immutable samplers are part of the pipeline and cannot be
+    /// changed. Combined image samplers must additionally have an image bound using one of the
+    /// `read_descriptor` functions.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::compute::{ComputePipeline, ComputePipelineInfo};
+    /// # use screen_13::driver::image::{Image, ImageInfo};
+    /// # use screen_13::driver::shader::{SamplerInfo, Shader};
+    /// # use screen_13::graph::RenderGraph;
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let my_shader_code = [0u8; 1];
+    /// let shader = Shader::new_compute(my_shader_code.as_slice()).mutable_sampler(0);
+    /// let info = ComputePipelineInfo::default();
+    /// let my_compute_pipeline = Arc::new(ComputePipeline::create(&device, info, shader)?);
+    /// # let info = ImageInfo::image_2d(32, 32, vk::Format::R8G8B8A8_UNORM, vk::ImageUsageFlags::SAMPLED);
+    /// # let my_image = Image::create(&device, info)?;
+    /// # let mut my_graph = RenderGraph::new();
+    /// # let my_image = my_graph.bind_node(my_image);
+    /// my_graph.begin_pass("sample an image")
+    ///         .bind_pipeline(&my_compute_pipeline)
+    ///         .read_descriptor(0, my_image)
+    ///         .bind_sampler(0, SamplerInfo::LINEAR)
+    ///         .record_compute(move |compute, bindings| {
+    ///             compute.dispatch(32, 32, 1);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`SamplerBinding::Mutable`]: crate::driver::shader::SamplerBinding::Mutable
+    pub fn bind_sampler(
+        mut self,
+        descriptor: impl Into<Descriptor>,
+        info: impl Into<SamplerInfo>,
+    ) -> Self {
+        let descriptor = descriptor.into();
+
+        assert!(
+            self.pass
+                .as_mut()
+                .execs
+                .last_mut()
+                .unwrap()
+                .samplers
+                .insert(descriptor, info.into())
+                .is_none(),
+            "sampler {descriptor:?} has already been bound"
+        );
+
+        self
+    }
+
     /// Informs the pass that the next recorded command buffer will read the given `node` at the
     /// specified shader descriptor.
     ///
@@ -4883,6 +5930,92 @@ impl RayTrace<'_> {
         self
     }
 
+    /// Updates push constants using the bytes of a [plain old data] value.
+    ///
+    /// Behaves similarly to [`RayTrace::push_constants`] except that `data` is a typed value
+    /// instead of a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the size of `T` is not a multiple of four or if any bytes of
+    /// `data` are not declared by the push constant ranges of the currently bound pipeline. Each
+    /// shader stage which does not declare the data is logged before panicking.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use ash::vk;
+    /// # use screen_13::driver::DriverError;
+    /// # use screen_13::driver::device::{Device, DeviceInfo};
+    /// # use screen_13::driver::buffer::{Buffer, BufferInfo};
+    /// # use screen_13::driver::ray_trace::{RayTracePipeline, RayTracePipelineInfo, RayTraceShaderGroup};
+    /// # use screen_13::driver::shader::Shader;
+    /// # use screen_13::graph::RenderGraph;
+    /// # use bytemuck::{Pod, Zeroable};
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy, Pod, Zeroable)]
+    /// struct PushConstants {
+    ///     the_answer: u32,
+    /// }
+    ///
+    /// # fn main() -> Result<(), DriverError> {
+    /// # let device = Arc::new(Device::create_headless(DeviceInfo::default())?);
+    /// # let shader = [0u8; 1];
+    /// # let info = RayTracePipelineInfo::default();
+    /// # let my_miss_code = [0u8; 1];
+    /// # let my_ray_trace_pipeline = Arc::new(RayTracePipeline::create(&device, info,
+    /// #     [Shader::new_miss(my_miss_code.as_slice())],
+    /// #     [RayTraceShaderGroup::new_general(0)],
+    /// # )?);
+    /// # let rgen_sbt = vk::StridedDeviceAddressRegionKHR { device_address: 0, stride: 0, size: 0 };
+    /// # let hit_sbt = vk::StridedDeviceAddressRegionKHR { device_address: 0, stride: 0, size: 0 };
+    /// # let miss_sbt = vk::StridedDeviceAddressRegionKHR { device_address: 0, stride: 0, size: 0 };
+    /// # let call_sbt = vk::StridedDeviceAddressRegionKHR { device_address: 0, stride: 0, size: 0 };
+    /// # let mut my_graph = RenderGraph::new();
+    /// my_graph.begin_pass("draw a cornell box")
+    ///         .bind_pipeline(&my_ray_trace_pipeline)
+    ///         .record_ray_trace(move |ray_trace, bindings| {
+    ///             ray_trace.push_constants_typed(&PushConstants { the_answer: 42 })
+    ///                      .trace_rays(&rgen_sbt, &hit_sbt, &miss_sbt, &call_sbt, 320, 200, 1);
+    ///         });
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [plain old data]: Pod
+    pub fn push_constants_typed<T>(&self, data: &T) -> &Self
+    where
+        T: Pod,
+    {
+        self.push_constants_typed_offset(0, data)
+    }
+
+    /// Updates push constants starting at the given `offset` using the bytes of a
+    /// [plain old data] value.
+    ///
+    /// Behaves similarly to [`RayTrace::push_constants_offset`] except that `data` is a typed value
+    /// instead of a byte slice.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `offset` or the size of `T` are not multiples of four or if any
+    /// bytes of `data` are not declared by the push constant ranges of the currently bound
+    /// pipeline. Each shader stage which does not declare the data is logged before panicking.
+    ///
+    /// [plain old data]: Pod
+    #[profiling::function]
+    pub fn push_constants_typed_offset<T>(&self, offset: u32, data: &T) -> &Self
+    where
+        T: Pod,
+    {
+        #[cfg(debug_assertions)]
+        validate_push_constants(&self.pipeline.push_constants, offset, size_of::<T>() as _);
+
+        self.push_constants_offset(offset, bytes_of(data))
+    }
+
     /// Set the stack size dynamically for a ray trace pipeline.
     ///
     /// See
@@ -5096,7 +6229,7 @@ impl View for AnyAccelerationStructureNode {
 }
 
 impl View for AnyBufferNode {
-    type Information = BufferSubresourceRange;
+    type Information = BufferViewInfo;
     type Subresource = BufferSubresourceRange;
 }
 
@@ -5106,12 +6239,12 @@ impl View for AnyImageNode {
 }
 
 impl View for BufferLeaseNode {
-    type Information = BufferSubresourceRange;
+    type Information = BufferViewInfo;
     type Subresource = BufferSubresourceRange;
 }
 
 impl View for BufferNode {
-    type Information = BufferSubresourceRange;
+    type Information = BufferViewInfo;
     type Subresource = BufferSubresourceRange;
 }
 
@@ -5141,12 +6274,16 @@ pub enum ViewType {
 
     /// Buffers may be interpreted as subregions of the same buffer.
     Buffer(Range<vk::DeviceSize>),
+
+    /// Buffers may be interpreted as formatted texel buffers.
+    TexelBuffer(BufferViewInfo),
 }
 
 impl ViewType {
-    pub(super) fn as_buffer(&self) -> Option<&Range<vk::DeviceSize>> {
+    pub(super) fn as_buffer(&self) -> Option<Range<vk::DeviceSize>> {
         match self {
-            Self::Buffer(view_info) => Some(view_info),
+            Self::Buffer(view_info) => Some(view_info.clone()),
+            Self::TexelBuffer(view_info) => Some(view_info.range.into()),
             _ => None,
         }
     }
@@ -5157,6 +6294,13 @@ impl ViewType {
             _ => None,
         }
     }
+
+    pub(super) fn as_texel_buffer(&self) -> Option<&BufferViewInfo> {
+        match self {
+            Self::TexelBuffer(view_info) => Some(view_info),
+            _ => None,
+        }
+    }
 }
 
 impl From<()> for ViewType {
@@ -5171,6 +6315,16 @@ impl From<BufferSubresourceRange> for ViewType {
     }
 }
 
+impl From<BufferViewInfo> for ViewType {
+    fn from(info: BufferViewInfo) -> Self {
+        if info.fmt == vk::Format::UNDEFINED {
+            info.range.into()
+        } else {
+            Self::TexelBuffer(info)
+        }
+    }
+}
+
 impl From<ImageViewInfo> for ViewType {
     fn from(info: ImageViewInfo) -> Self {
         Self::Image(info)
@@ -11,10 +11,13 @@ use {
             FramebufferAttachmentImageInfo, FramebufferInfo, RenderPass, RenderPassInfo,
             SubpassDependency, SubpassInfo,
             accel_struct::AccelerationStructure,
+            bindless::BindlessHeap,
             buffer::Buffer,
+            descriptor_image_layout,
+            device::Device,
             format_aspect_mask,
             graphic::{DepthStencilMode, GraphicPipeline},
-            image::{Image, ImageAccess, ImageViewInfo},
+            image::{Image, ImageAccess, ImageViewInfo, Swizzle},
             image_access_layout, initial_image_layout_access, is_read_access, is_write_access,
             pipeline_stage_access_flags,
             swapchain::SwapchainImage,
@@ -160,7 +163,7 @@ impl std::fmt::Debug for ImageSubresourceRangeDebug {
 #[derive(Debug)]
 struct PhysicalPass {
     descriptor_pool: Option<Lease<DescriptorPool>>,
-    exec_descriptor_sets: HashMap<usize, Vec<DescriptorSet>>,
+    exec_descriptor_sets: HashMap<usize, BTreeMap<u32, DescriptorSet>>,
     render_pass: Option<Lease<RenderPass>>,
 }
 
@@ -451,6 +454,7 @@ impl Resolver {
                                 base_mip_level: attachment.base_mip_level,
                                 fmt: attachment.format,
                                 mip_level_count: attachment.mip_level_count,
+                                swizzle: Swizzle::IDENTITY,
                                 ty: image.info.ty,
                             },
                         )?;
@@ -489,6 +493,7 @@ impl Resolver {
                                 base_mip_level: attachment.base_mip_level,
                                 fmt: attachment.format,
                                 mip_level_count: attachment.mip_level_count,
+                                swizzle: Swizzle::IDENTITY,
                                 ty: image.info.ty,
                             },
                         )?;
@@ -525,6 +530,7 @@ impl Resolver {
                                 base_mip_level: attachment.base_mip_level,
                                 fmt: attachment.format,
                                 mip_level_count: attachment.mip_level_count,
+                                swizzle: Swizzle::IDENTITY,
                                 ty: image.info.ty,
                             },
                         )?;
@@ -561,6 +567,7 @@ impl Resolver {
                                 base_mip_level: attachment.base_mip_level,
                                 fmt: attachment.format,
                                 mip_level_count: attachment.mip_level_count,
+                                swizzle: Swizzle::IDENTITY,
                                 ty: image.info.ty,
                             },
                         )?;
@@ -595,6 +602,7 @@ impl Resolver {
                                 base_mip_level: attachment.base_mip_level,
                                 fmt: attachment.format,
                                 mip_level_count: attachment.mip_level_count,
+                                swizzle: Swizzle::IDENTITY,
                                 ty: image.info.ty,
                             },
                         )?;
@@ -640,38 +648,74 @@ impl Resolver {
         pipeline: &ExecutionPipeline,
         physical_pass: &PhysicalPass,
         exec_idx: usize,
+        bindless_heap: Option<&BindlessHeap>,
     ) {
-        if let Some(exec_descriptor_sets) = physical_pass.exec_descriptor_sets.get(&exec_idx) {
-            thread_local! {
-                static DESCRIPTOR_SETS: RefCell<Vec<vk::DescriptorSet>> = Default::default();
-            }
+        thread_local! {
+            static DESCRIPTOR_SETS: RefCell<Vec<vk::DescriptorSet>> = Default::default();
+        }
+
+        let exec_descriptor_sets = physical_pass.exec_descriptor_sets.get(&exec_idx);
+        let bindless_set_idx = pipeline
+            .descriptor_info()
+            .bindless_heap
+            .map(|(bindless_set_idx, _)| bindless_set_idx);
+
+        if exec_descriptor_sets.is_none() && bindless_set_idx.is_none() {
+            return;
+        }
 
-            if exec_descriptor_sets.is_empty() {
+        let bind_descriptor_sets = |first_set: u32, descriptor_sets: &[vk::DescriptorSet]| {
+            if descriptor_sets.is_empty() {
                 return;
             }
 
-            DESCRIPTOR_SETS.with_borrow_mut(|descriptor_sets| {
-                descriptor_sets.clear();
-                descriptor_sets.extend(
-                    exec_descriptor_sets
-                        .iter()
-                        .map(|descriptor_set| **descriptor_set),
+            trace!(
+                "    bind descriptor sets {first_set}: {:?}",
+                descriptor_sets
+            );
+
+            unsafe {
+                cmd_buf.device.cmd_bind_descriptor_sets(
+                    **cmd_buf,
+                    pipeline.bind_point(),
+                    pipeline.layout(),
+                    first_set,
+                    descriptor_sets,
+                    &[],
                 );
+            }
+        };
 
-                trace!("    bind descriptor sets {:?}", descriptor_sets);
+        DESCRIPTOR_SETS.with_borrow_mut(|descriptor_sets| {
+            descriptor_sets.clear();
+
+            // Sets which are not allocated by this pass (such as push descriptor sets or unused
+            // sets) split the bound sets into contiguous ranges
+            let mut first_set = 0;
+            for &descriptor_set_idx in pipeline.descriptor_info().layouts.keys() {
+                let descriptor_set = if bindless_set_idx == Some(descriptor_set_idx) {
+                    Some(BindlessHeap::descriptor_set(bindless_heap.unwrap_or_else(
+                        || panic!("bindless heap set {descriptor_set_idx} was not bound"),
+                    )))
+                } else {
+                    exec_descriptor_sets
+                        .and_then(|exec_descriptor_sets| {
+                            exec_descriptor_sets.get(&descriptor_set_idx)
+                        })
+                        .map(|descriptor_set| **descriptor_set)
+                };
 
-                unsafe {
-                    cmd_buf.device.cmd_bind_descriptor_sets(
-                        **cmd_buf,
-                        pipeline.bind_point(),
-                        pipeline.layout(),
-                        0,
-                        descriptor_sets,
-                        &[],
-                    );
+                if let Some(descriptor_set) = descriptor_set {
+                    descriptor_sets.push(descriptor_set);
+                } else {
+                    bind_descriptor_sets(first_set, descriptor_sets);
+                    descriptor_sets.clear();
+                    first_set = descriptor_set_idx + 1;
                 }
-            });
-        }
+            }
+
+            bind_descriptor_sets(first_set, descriptor_sets);
+        });
     }
 
     #[profiling::function]
@@ -1741,13 +1785,27 @@ impl Resolver {
                             exec.pipeline.as_ref().map(|pipeline| (exec_idx, pipeline))
                         })
                 {
-                    let layouts = pipeline.descriptor_info().layouts.values();
-                    let mut descriptor_sets = Vec::with_capacity(layouts.len());
-                    for descriptor_set_layout in layouts {
-                        descriptor_sets.push(DescriptorPool::allocate_descriptor_set(
-                            descriptor_pool,
-                            descriptor_set_layout,
-                        )?);
+                    let descriptor_info = pipeline.descriptor_info();
+                    let layouts =
+                        descriptor_info
+                            .layouts
+                            .iter()
+                            .filter(|&(&descriptor_set_idx, _)| {
+                                descriptor_info.push_descriptor_set != Some(descriptor_set_idx)
+                                    && descriptor_info
+                                        .bindless_heap
+                                        .map(|(bindless_set_idx, _)| bindless_set_idx)
+                                        != Some(descriptor_set_idx)
+                            });
+                    let mut descriptor_sets = BTreeMap::new();
+                    for (&descriptor_set_idx, descriptor_set_layout) in layouts {
+                        descriptor_sets.insert(
+                            descriptor_set_idx,
+                            DescriptorPool::allocate_descriptor_set(
+                                descriptor_pool,
+                                descriptor_set_layout,
+                            )?,
+                        );
                     }
                     exec_descriptor_sets.insert(exec_idx, descriptor_sets);
                 }
@@ -2542,7 +2600,15 @@ impl Resolver {
                         );
                     }
 
-                    Self::bind_descriptor_sets(cmd_buf, pipeline, physical_pass, exec_idx);
+                    Self::bind_descriptor_sets(
+                        cmd_buf,
+                        pipeline,
+                        physical_pass,
+                        exec_idx,
+                        exec.bindless_heap
+                            .as_ref()
+                            .map(|bindless_heap| &*bindless_heap.heap),
+                    );
                 }
 
                 if !is_graphic {
@@ -3032,6 +3098,8 @@ impl Resolver {
             descriptors: Vec<vk::WriteDescriptorSet<'a>>,
             image_infos: Vec<vk::DescriptorImageInfo>,
             image_writes: Vec<IndexWrite<'a>>,
+            texel_buffer_views: Vec<vk::BufferView>,
+            texel_buffer_writes: Vec<IndexWrite<'a>>,
         }
 
         let mut tls = Tls::default();
@@ -3047,11 +3115,22 @@ impl Resolver {
             })
             .filter(|(.., pipeline)| !pipeline.descriptor_info().layouts.is_empty())
         {
-            let descriptor_sets = &physical_pass.exec_descriptor_sets[&exec_idx];
+            // Pipelines which only use push descriptor or bindless heap sets have no descriptor sets
+            // to write
+            let Some(descriptor_sets) = physical_pass.exec_descriptor_sets.get(&exec_idx) else {
+                continue;
+            };
 
             // Write the manually bound things (access, read, and write functions)
             for (descriptor, (node_idx, view_info)) in exec.bindings.iter() {
                 let (descriptor_set_idx, dst_binding, binding_offset) = descriptor.into_tuple();
+
+                // Push descriptors are written while recording using Draw::push_descriptor and
+                // bindless heap descriptors are written by the heap itself
+                if !descriptor_sets.contains_key(&descriptor_set_idx) {
+                    continue;
+                }
+
                 let (descriptor_info, _) = pipeline
                         .descriptor_bindings()
                         .get(&Descriptor { set: descriptor_set_idx, binding: dst_binding })
@@ -3068,36 +3147,14 @@ impl Resolver {
                     }
 
                     let image_view = Image::view(image, image_view_info)?;
-                    let image_layout = match descriptor_type {
-                        vk::DescriptorType::COMBINED_IMAGE_SAMPLER
-                        | vk::DescriptorType::SAMPLED_IMAGE => {
-                            if image_view_info.aspect_mask.contains(
-                                vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL,
-                            ) {
-                                vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL
-                            } else if image_view_info
-                                .aspect_mask
-                                .contains(vk::ImageAspectFlags::DEPTH)
-                            {
-                                vk::ImageLayout::DEPTH_READ_ONLY_OPTIMAL
-                            } else if image_view_info
-                                .aspect_mask
-                                .contains(vk::ImageAspectFlags::STENCIL)
-                            {
-                                vk::ImageLayout::STENCIL_READ_ONLY_OPTIMAL
-                            } else {
-                                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL
-                            }
-                        }
-                        vk::DescriptorType::STORAGE_IMAGE => vk::ImageLayout::GENERAL,
-                        _ => unimplemented!("{descriptor_type:?}"),
-                    };
+                    let image_layout =
+                        descriptor_image_layout(descriptor_type, image_view_info.aspect_mask);
 
                     if binding_offset == 0 {
                         tls.image_writes.push(IndexWrite {
                             idx: tls.image_infos.len(),
                             write: vk::WriteDescriptorSet {
-                                dst_set: *descriptor_sets[descriptor_set_idx as usize],
+                                dst_set: *descriptor_sets[&descriptor_set_idx],
                                 dst_binding,
                                 descriptor_type,
                                 descriptor_count: 1,
@@ -3108,20 +3165,59 @@ impl Resolver {
                         tls.image_writes.last_mut().unwrap().write.descriptor_count += 1;
                     }
 
-                    tls.image_infos.push(
-                        vk::DescriptorImageInfo::default()
-                            .image_layout(image_layout)
-                            .image_view(image_view),
-                    );
+                    let mut image_info = vk::DescriptorImageInfo::default()
+                        .image_layout(image_layout)
+                        .image_view(image_view);
+
+                    if descriptor_info.is_mutable_sampler() {
+                        let sampler_info = exec.samplers.get(descriptor).unwrap_or_else(|| panic!("descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] uses a mutable sampler which was not bound in recorded execution of pass \"{}\"", &pass.name));
+                        image_info =
+                            image_info.sampler(Device::sampler(&cmd_buf.device, *sampler_info)?);
+                    }
+
+                    tls.image_infos.push(image_info);
                 } else if let Some(buffer) = bound_node.as_driver_buffer() {
                     let view_info = view_info.as_ref().unwrap();
+
+                    if matches!(
+                        descriptor_type,
+                        vk::DescriptorType::UNIFORM_TEXEL_BUFFER
+                            | vk::DescriptorType::STORAGE_TEXEL_BUFFER
+                    ) {
+                        let buffer_view_info = view_info.as_texel_buffer().unwrap_or_else(|| panic!("descriptor {descriptor_set_idx}.{dst_binding}[{binding_offset}] specified in recorded execution of pass \"{}\" is a texel buffer which was not bound with a view format", &pass.name));
+                        let buffer_view = Buffer::view(buffer, *buffer_view_info)?;
+
+                        if binding_offset == 0 {
+                            tls.texel_buffer_writes.push(IndexWrite {
+                                idx: tls.texel_buffer_views.len(),
+                                write: vk::WriteDescriptorSet {
+                                    dst_set: *descriptor_sets[&descriptor_set_idx],
+                                    dst_binding,
+                                    descriptor_type,
+                                    descriptor_count: 1,
+                                    ..Default::default()
+                                },
+                            });
+                        } else {
+                            tls.texel_buffer_writes
+                                .last_mut()
+                                .unwrap()
+                                .write
+                                .descriptor_count += 1;
+                        }
+
+                        tls.texel_buffer_views.push(buffer_view);
+
+                        continue;
+                    }
+
                     let buffer_view_info = view_info.as_buffer().unwrap();
 
                     if binding_offset == 0 {
                         tls.buffer_writes.push(IndexWrite {
                             idx: tls.buffer_infos.len(),
                             write: vk::WriteDescriptorSet {
-                                dst_set: *descriptor_sets[descriptor_set_idx as usize],
+                                dst_set: *descriptor_sets[&descriptor_set_idx],
                                 dst_binding,
                                 descriptor_type,
                                 descriptor_count: 1,
@@ -3143,7 +3239,7 @@ impl Resolver {
                         tls.accel_struct_writes.push(IndexWrite {
                             idx: tls.accel_struct_infos.len(),
                             write: vk::WriteDescriptorSet::default()
-                                .dst_set(*descriptor_sets[descriptor_set_idx as usize])
+                                .dst_set(*descriptor_sets[&descriptor_set_idx])
                                 .dst_binding(dst_binding)
                                 .descriptor_type(descriptor_type)
                                 .descriptor_count(1),
@@ -3165,6 +3261,57 @@ impl Resolver {
                 }
             }
 
+            // Write the mutable samplers which are not combined with an image
+            for (descriptor, sampler_info) in exec.samplers.iter() {
+                let (descriptor_set_idx, dst_binding, binding_offset) = descriptor.into_tuple();
+
+                if !descriptor_sets.contains_key(&descriptor_set_idx) {
+                    continue;
+                }
+
+                let (descriptor_info, _) = pipeline
+                        .descriptor_bindings()
+                        .get(&Descriptor { set: descriptor_set_idx, binding: dst_binding })
+                        .unwrap_or_else(|| panic!("sampler {descriptor_set_idx}.{dst_binding}[{binding_offset}] specified in recorded execution of pass \"{}\" was not discovered through shader reflection", &pass.name));
+
+                assert!(
+                    descriptor_info.is_mutable_sampler(),
+                    "sampler {descriptor_set_idx}.{dst_binding}[{binding_offset}] specified in recorded execution of pass \"{}\" is not a mutable sampler descriptor",
+                    &pass.name
+                );
+
+                if descriptor_info.descriptor_type() != vk::DescriptorType::SAMPLER {
+                    // Mutable combined image samplers are written along with their image
+                    assert!(
+                        exec.bindings.contains_key(descriptor),
+                        "combined image sampler {descriptor_set_idx}.{dst_binding}[{binding_offset}] specified in recorded execution of pass \"{}\" has no image bound",
+                        &pass.name
+                    );
+
+                    continue;
+                }
+
+                if binding_offset == 0 {
+                    tls.image_writes.push(IndexWrite {
+                        idx: tls.image_infos.len(),
+                        write: vk::WriteDescriptorSet {
+                            dst_set: *descriptor_sets[&descriptor_set_idx],
+                            dst_binding,
+                            descriptor_type: vk::DescriptorType::SAMPLER,
+                            descriptor_count: 1,
+                            ..Default::default()
+                        },
+                    });
+                } else {
+                    tls.image_writes.last_mut().unwrap().write.descriptor_count += 1;
+                }
+
+                tls.image_infos.push(
+                    vk::DescriptorImageInfo::default()
+                        .sampler(Device::sampler(&cmd_buf.device, *sampler_info)?),
+                );
+            }
+
             if let ExecutionPipeline::Graphic(pipeline) = pipeline {
                 // Write graphic render pass input attachments (they're automatic)
                 if exec_idx > 0 {
@@ -3176,6 +3323,10 @@ impl Resolver {
                         (descriptor_info, _),
                     ) in &pipeline.descriptor_bindings
                     {
+                        if !descriptor_sets.contains_key(&descriptor_set_idx) {
+                            continue;
+                        }
+
                         if let DescriptorInfo::InputAttachment(_, attachment_idx) = *descriptor_info
                         {
                             let is_random_access = exec.color_stores.contains_key(&attachment_idx)
@@ -3208,6 +3359,7 @@ impl Resolver {
                                 base_mip_level: image_range.base_mip_level,
                                 fmt: attachment.format,
                                 mip_level_count: image_range.level_count,
+                                swizzle: Swizzle::IDENTITY,
                                 ty: image.info.ty,
                             };
                             let image_view = Image::view(image, image_view_info)?;
@@ -3215,7 +3367,7 @@ impl Resolver {
                             tls.image_writes.push(IndexWrite {
                                 idx: tls.image_infos.len(),
                                 write: vk::WriteDescriptorSet {
-                                    dst_set: *descriptor_sets[descriptor_set_idx as usize],
+                                    dst_set: *descriptor_sets[&descriptor_set_idx],
                                     dst_binding,
                                     descriptor_type: vk::DescriptorType::INPUT_ATTACHMENT,
                                     descriptor_count: 1,
@@ -3259,13 +3411,21 @@ impl Resolver {
                 write
             },
         ));
+        tls.descriptors
+            .extend(tls.texel_buffer_writes.drain(..).map(
+                |IndexWrite { idx, mut write }| unsafe {
+                    write.p_texel_buffer_view = tls.texel_buffer_views.as_ptr().add(idx);
+                    write
+                },
+            ));
 
         if !tls.descriptors.is_empty() {
             trace!(
-                "  writing {} descriptors ({} buffers, {} images)",
+                "  writing {} descriptors ({} buffers, {} images, {} texel buffers)",
                 tls.descriptors.len(),
                 tls.buffer_infos.len(),
-                tls.image_infos.len()
+                tls.image_infos.len(),
+                tls.texel_buffer_views.len()
             );
 
             unsafe {
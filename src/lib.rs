@@ -276,8 +276,13 @@ For example, the following sampler named `pages_sampler_nnr` specifies nearest t
 layout(set = 0, binding = 0) uniform sampler2D pages_sampler_nnr[NUM_PAGES];
 ```
 
+An invalid suffix, such as `pages_sampler_nnx`, causes pipeline creation to return
+[`DriverError::InvalidData`].
+
 For more complex image sampling, use [`ShaderBuilder::image_sampler`] to specify the exact image
-sampling mode.
+sampling mode, or use a [`SamplerTable`] to assign samplers to an entire pipeline by descriptor or
+name. Samplers may also be bound while recording a pass, instead of being part of the pipeline, by
+specifying [`SamplerBinding::Mutable`] and calling [`PipelinePassRef::bind_sampler`].
 
 ## Vertex input
 
@@ -302,6 +307,7 @@ layout.
 [`ComputePipeline::create`]: driver::compute::ComputePipeline::create
 [`ComputePipelineInfo`]: driver::compute::ComputePipelineInfo
 [`Device`]: driver::device::Device
+[`DriverError::InvalidData`]: driver::DriverError::InvalidData
 [`EventLoop`]: EventLoop
 [`FrameContext`]: FrameContext
 [Granite]: https://github.com/Themaister/Granite
@@ -310,9 +316,12 @@ layout.
 [`Image::create`]: driver::image::Image::create
 [`ImageInfo`]: driver::image::ImageInfo
 [`PassRef`]: graph::pass_ref::PassRef
+[`PipelinePassRef::bind_sampler`]: graph::pass_ref::PipelinePassRef::bind_sampler
 [`RayTracePipeline::create`]: driver::ray_trace::RayTracePipeline::create
 [`RayTracePipelineInfo`]: driver::ray_trace::RayTracePipelineInfo
 [`RenderGraph`]: graph::RenderGraph
+[`SamplerBinding::Mutable`]: driver::shader::SamplerBinding::Mutable
+[`SamplerTable`]: driver::shader::SamplerTable
 [`ShaderBuilder::image_sampler`]: driver::shader::ShaderBuilder::image_sampler
 [`ShaderBuilder::vertex_input`]: driver::shader::ShaderBuilder::vertex_input
 
@@ -329,36 +338,48 @@ mod display;
 /// Things which are used in almost every single _Screen 13_ program.
 pub mod prelude {
     pub use super::{
-        display::{Display, DisplayError, DisplayInfo, DisplayInfoBuilder, ResolverPool},
+        display::{
+            Display, DisplayError, DisplayEvent, DisplayInfo, DisplayInfoBuilder, DisplayStats,
+            ResolverPool,
+        },
         driver::{
-            AccessType, CommandBuffer, DriverError, Instance,
+            AccessType, CommandBuffer, DriverError, HostAccess, Instance,
             accel_struct::{
                 AccelerationStructure, AccelerationStructureGeometry,
                 AccelerationStructureGeometryData, AccelerationStructureGeometryInfo,
                 AccelerationStructureInfo, AccelerationStructureInfoBuilder,
-                AccelerationStructureSize, DeviceOrHostAddress,
+                AccelerationStructureQueryPool, AccelerationStructureSize, DeviceOrHostAddress,
             },
             ash::vk,
-            buffer::{Buffer, BufferInfo, BufferInfoBuilder, BufferSubresourceRange},
+            bindless::{BindlessHeap, BindlessHeapInfo, BindlessHeapInfoBuilder, BindlessIndex},
+            buffer::{
+                Buffer, BufferInfo, BufferInfoBuilder, BufferSubresourceRange, BufferViewInfo,
+            },
             compute::{ComputePipeline, ComputePipelineInfo, ComputePipelineInfoBuilder},
-            device::{Device, DeviceInfo, DeviceInfoBuilder},
+            device::{
+                Device, DeviceInfo, DeviceInfoBuilder, MemoryAllocationReport, MemoryBlockReport,
+                MemoryHeapBudget, MemoryReport,
+            },
             graphic::{
-                BlendMode, BlendModeBuilder, DepthStencilMode, DepthStencilModeBuilder,
-                GraphicPipeline, GraphicPipelineInfo, GraphicPipelineInfoBuilder, StencilMode,
+                BlendMode, BlendModeBuilder, DepthBias, DepthStencilMode, DepthStencilModeBuilder,
+                ExtendedDynamicState, GraphicPipeline, GraphicPipelineInfo,
+                GraphicPipelineInfoBuilder, StencilMode,
             },
             image::{
                 Image, ImageInfo, ImageInfoBuilder, ImageType, ImageViewInfo, ImageViewInfoBuilder,
-                SampleCount,
+                SampleCount, Swizzle,
             },
             physical_device::{
-                AccelerationStructureProperties, PhysicalDevice, RayQueryFeatures,
-                RayTraceFeatures, RayTraceProperties, Vulkan10Features, Vulkan10Limits,
-                Vulkan10Properties, Vulkan11Features, Vulkan11Properties, Vulkan12Features,
-                Vulkan12Properties,
+                AccelerationStructureProperties, ConservativeRasterizationProperties,
+                ExtendedDynamicStateFeatures, MemoryPriorityFeatures, PhysicalDevice,
+                PushDescriptorProperties, RayQueryFeatures, RayTraceFeatures, RayTraceProperties,
+                Vulkan10Features, Vulkan10Limits, Vulkan10Properties, Vulkan11Features,
+                Vulkan11Properties, Vulkan12Features, Vulkan12Properties,
             },
             ray_trace::{
                 RayTracePipeline, RayTracePipelineInfo, RayTracePipelineInfoBuilder,
-                RayTraceShaderGroup, RayTraceShaderGroupType,
+                RayTraceShaderGroup, RayTraceShaderGroupType, ShaderBindingTable,
+                ShaderBindingTableInfo, ShaderBindingTableRecord,
             },
             render_pass::ResolveMode,
             shader::{
@@ -367,11 +388,13 @@ pub mod prelude {
             },
             surface::Surface,
             swapchain::{
-                Swapchain, SwapchainError, SwapchainImage, SwapchainInfo, SwapchainInfoBuilder,
+                HdrMetadata, Swapchain, SwapchainError, SwapchainImage, SwapchainInfo,
+                SwapchainInfoBuilder,
             },
         },
         graph::{
-            Bind, ClearColorValue, RenderGraph, Unbind,
+            AccelerationStructureBuilder, AccelerationStructureInstance, Bind, ClearColorValue,
+            RenderGraph, TriangleMesh, Unbind,
             node::{
                 AccelerationStructureLeaseNode, AccelerationStructureNode,
                 AnyAccelerationStructureNode, AnyBufferNode, AnyImageNode, BufferLeaseNode,
@@ -380,8 +403,8 @@ pub mod prelude {
             pass_ref::{PassRef, PipelinePassRef},
         },
         pool::{
-            Lease, Pool, PoolInfo, PoolInfoBuilder,
-            alias::{Alias, AliasPool},
+            Lease, Pool, PoolInfo, PoolInfoBuilder, PoolStats, ResourceStats, SizeClass,
+            alias::{Alias, AliasPool, AliasStats},
             fifo::FifoPool,
             hash::HashPool,
             lazy::LazyPool,
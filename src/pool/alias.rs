@@ -1,7 +1,7 @@
 //! Pool wrapper which enables memory-efficient resource aliasing.
 
 use {
-    super::{Lease, Pool},
+    super::{Evict, Lease, Pool, ResourceStats},
     crate::driver::{
         DriverError,
         accel_struct::{
@@ -71,6 +71,7 @@ pub struct AliasPool<T> {
     buffers: Vec<(BufferInfo, Weak<Lease<Buffer>>)>,
     images: Vec<(ImageInfo, Weak<Lease<Image>>)>,
     pool: T,
+    stats: AliasStats,
 }
 
 impl<T> AliasPool<T> {
@@ -81,8 +82,61 @@ impl<T> AliasPool<T> {
             buffers: Default::default(),
             images: Default::default(),
             pool,
+            stats: Default::default(),
         }
     }
+
+    /// Returns the alias counters and actively aliased resources of this wrapper.
+    ///
+    /// Use `stats` to read the counters of the wrapped pool.
+    pub fn alias_stats(&self) -> AliasStats {
+        fn active_stats<I, T>(stats: &mut ResourceStats, aliases: &[(I, Weak<Lease<T>>)])
+        where
+            T: Evict,
+        {
+            stats.hits = stats.leases - stats.misses;
+
+            for item in aliases.iter().filter_map(|(_, item)| item.upgrade()) {
+                stats.bytes += item.memory_size();
+                stats.count += 1;
+            }
+        }
+
+        let mut stats = self.stats;
+
+        active_stats(&mut stats.accel_structs, &self.accel_structs);
+        active_stats(&mut stats.buffers, &self.buffers);
+        active_stats(&mut stats.images, &self.images);
+
+        stats
+    }
+
+    /// Clears the alias counters of this wrapper.
+    ///
+    /// Use `reset_stats` to clear the counters of the wrapped pool.
+    pub fn reset_alias_stats(&mut self) {
+        self.stats = Default::default();
+    }
+}
+
+/// Counters which describe how effectively an [`AliasPool`] reuses actively aliased resources.
+///
+/// For each resource type [`ResourceStats::leases`] is the number of alias requests,
+/// [`ResourceStats::hits`] is the number of requests fulfilled by an active alias and
+/// [`ResourceStats::misses`] is the number of requests which leased a resource from the wrapped
+/// pool. [`ResourceStats::count`] and [`ResourceStats::bytes`] describe the resources which are
+/// currently aliased. Returns and evictions are counted by the wrapped pool.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AliasStats {
+    /// Counters of acceleration structure aliases.
+    pub accel_structs: ResourceStats,
+
+    /// Counters of buffer aliases.
+    pub buffers: ResourceStats,
+
+    /// Counters of image aliases.
+    pub images: ResourceStats,
 }
 
 // Enable aliasing items using their info builder type for convenience
@@ -110,6 +164,8 @@ where
         &mut self,
         info: AccelerationStructureInfo,
     ) -> Result<Arc<Lease<AccelerationStructure>>, DriverError> {
+        self.stats.accel_structs.leases += 1;
+
         self.accel_structs
             .retain(|(_, item)| item.strong_count() > 0);
 
@@ -129,6 +185,8 @@ where
 
         debug!("Leasing new {}", stringify!(AccelerationStructure));
 
+        self.stats.accel_structs.misses += 1;
+
         let item = Arc::new(self.pool.lease(info)?);
         self.accel_structs.push((info, Arc::downgrade(&item)));
 
@@ -141,6 +199,8 @@ where
     T: Pool<BufferInfo, Buffer>,
 {
     fn alias(&mut self, info: BufferInfo) -> Result<Arc<Lease<Buffer>>, DriverError> {
+        self.stats.buffers.leases += 1;
+
         self.buffers.retain(|(_, item)| item.strong_count() > 0);
 
         {
@@ -148,6 +208,9 @@ where
 
             for (item_info, item) in &self.buffers {
                 if item_info.mappable == info.mappable
+                    && item_info.dedicated == info.dedicated
+                    && item_info.host_access == info.host_access
+                    && item_info.memory_priority == info.memory_priority
                     && item_info.alignment >= info.alignment
                     && item_info.size >= info.size
                     && item_info.usage.contains(info.usage)
@@ -163,6 +226,8 @@ where
 
         debug!("Leasing new {}", stringify!(Buffer));
 
+        self.stats.buffers.misses += 1;
+
         let item = Arc::new(self.pool.lease(info)?);
         self.buffers.push((info, Arc::downgrade(&item)));
 
@@ -175,6 +240,8 @@ where
     T: Pool<ImageInfo, Image>,
 {
     fn alias(&mut self, info: ImageInfo) -> Result<Arc<Lease<Image>>, DriverError> {
+        self.stats.images.leases += 1;
+
         self.images.retain(|(_, item)| item.strong_count() > 0);
 
         {
@@ -183,8 +250,12 @@ where
             for (item_info, item) in &self.images {
                 if item_info.array_layer_count == info.array_layer_count
                     && item_info.depth == info.depth
+                    && item_info.dedicated == info.dedicated
                     && item_info.fmt == info.fmt
                     && item_info.height == info.height
+                    && item_info.host_access == info.host_access
+                    && item_info.mappable == info.mappable
+                    && item_info.memory_priority == info.memory_priority
                     && item_info.mip_level_count == info.mip_level_count
                     && item_info.sample_count == info.sample_count
                     && item_info.tiling == info.tiling
@@ -204,6 +275,8 @@ where
 
         debug!("Leasing new {}", stringify!(Image));
 
+        self.stats.images.misses += 1;
+
         let item = Arc::new(self.pool.lease(info)?);
         self.images.push((info, Arc::downgrade(&item)));
 
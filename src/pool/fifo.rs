@@ -1,7 +1,10 @@
 //! Pool which leases from a single bucket per resource type.
 
 use {
-    super::{Cache, Lease, Pool, PoolInfo, lease_command_buffer},
+    super::{
+        Cache, Lease, Pool, PoolInfo, PoolStats, buffer_key, cache_stats, lease_command_buffer,
+        lease_size, reset_cache_stats,
+    },
     crate::driver::{
         CommandBuffer, CommandBufferInfo, DescriptorPool, DescriptorPoolInfo, DriverError,
         RenderPass, RenderPassInfo,
@@ -11,7 +14,7 @@ use {
         image::{Image, ImageInfo},
     },
     log::debug,
-    std::{collections::HashMap, sync::Arc},
+    std::{collections::HashMap, iter::once, sync::Arc},
 };
 
 /// A memory-efficient resource allocator.
@@ -50,6 +53,7 @@ pub struct FifoPool {
     image_cache: Cache<Image>,
     info: PoolInfo,
     render_pass_cache: HashMap<RenderPassInfo, Cache<RenderPass>>,
+    stats: PoolStats,
 }
 
 impl FifoPool {
@@ -72,6 +76,7 @@ impl FifoPool {
             image_cache: PoolInfo::explicit_cache(info.image_capacity),
             info,
             render_pass_cache: Default::default(),
+            stats: Default::default(),
         }
     }
 
@@ -96,6 +101,45 @@ impl FifoPool {
     pub fn clear_images(&mut self) {
         self.image_cache = PoolInfo::explicit_cache(self.info.image_capacity);
     }
+
+    /// Clears the lease, return and eviction counters of the pool.
+    ///
+    /// Call this function once per frame in order to read per-frame values from
+    /// [`FifoPool::stats`].
+    pub fn reset_stats(&mut self) {
+        self.stats = Default::default();
+
+        reset_cache_stats(once(&self.accel_struct_cache));
+        reset_cache_stats(once(&self.buffer_cache));
+        reset_cache_stats(self.command_buffer_cache.values());
+        reset_cache_stats(once(&self.descriptor_pool_cache));
+        reset_cache_stats(once(&self.image_cache));
+        reset_cache_stats(self.render_pass_cache.values());
+    }
+
+    /// Returns the counters and stored resources of the pool.
+    ///
+    /// Counters of resources removed using [`FifoPool::clear`] and the other memory management
+    /// functions are not included.
+    #[profiling::function]
+    pub fn stats(&self) -> PoolStats {
+        let mut stats = self.stats;
+
+        cache_stats(&mut stats.accel_structs, once(&self.accel_struct_cache));
+        cache_stats(&mut stats.buffers, once(&self.buffer_cache));
+        cache_stats(
+            &mut stats.command_buffers,
+            self.command_buffer_cache.values(),
+        );
+        cache_stats(
+            &mut stats.descriptor_pools,
+            once(&self.descriptor_pool_cache),
+        );
+        cache_stats(&mut stats.images, once(&self.image_cache));
+        cache_stats(&mut stats.render_passes, self.render_pass_cache.values());
+
+        stats
+    }
 }
 
 impl Pool<AccelerationStructureInfo, AccelerationStructure> for FifoPool {
@@ -104,6 +148,8 @@ impl Pool<AccelerationStructureInfo, AccelerationStructure> for FifoPool {
         &mut self,
         info: AccelerationStructureInfo,
     ) -> Result<Lease<AccelerationStructure>, DriverError> {
+        self.stats.accel_structs.leases += 1;
+
         let cache_ref = Arc::downgrade(&self.accel_struct_cache);
 
         {
@@ -119,7 +165,7 @@ impl Pool<AccelerationStructureInfo, AccelerationStructure> for FifoPool {
             for idx in 0..cache.len() {
                 let item = unsafe { cache.get_unchecked(idx) };
                 if item.info.size >= info.size && item.info.ty == info.ty {
-                    let item = cache.swap_remove(idx);
+                    let item = cache.swap_remove(idx).item;
 
                     return Ok(Lease::new(cache_ref, item));
                 }
@@ -128,6 +174,8 @@ impl Pool<AccelerationStructureInfo, AccelerationStructure> for FifoPool {
 
         debug!("Creating new {}", stringify!(AccelerationStructure));
 
+        self.stats.accel_structs.misses += 1;
+
         let item = AccelerationStructure::create(&self.device, info)?;
 
         Ok(Lease::new(cache_ref, item))
@@ -137,6 +185,10 @@ impl Pool<AccelerationStructureInfo, AccelerationStructure> for FifoPool {
 impl Pool<BufferInfo, Buffer> for FifoPool {
     #[profiling::function]
     fn lease(&mut self, info: BufferInfo) -> Result<Lease<Buffer>, DriverError> {
+        self.stats.buffers.leases += 1;
+
+        let size = info.size;
+        let info = buffer_key(info, self.info.buffer_size_class);
         let cache_ref = Arc::downgrade(&self.buffer_cache);
 
         {
@@ -153,11 +205,16 @@ impl Pool<BufferInfo, Buffer> for FifoPool {
             for idx in 0..cache.len() {
                 let item = unsafe { cache.get_unchecked(idx) };
                 if item.info.alignment >= info.alignment
+                    && item.info.dedicated == info.dedicated
                     && item.info.mappable == info.mappable
-                    && item.info.size >= info.size
+                    && item.info.host_access == info.host_access
+                    && item.info.memory_priority == info.memory_priority
+                    && Buffer::capacity(item) >= info.size
                     && item.info.usage.contains(info.usage)
                 {
-                    let item = cache.swap_remove(idx);
+                    let mut item = cache.swap_remove(idx).item;
+                    item.info.size =
+                        lease_size(Buffer::capacity(&item), self.info.buffer_size_class, size);
 
                     return Ok(Lease::new(cache_ref, item));
                 }
@@ -166,7 +223,10 @@ impl Pool<BufferInfo, Buffer> for FifoPool {
 
         debug!("Creating new {}", stringify!(Buffer));
 
-        let item = Buffer::create(&self.device, info)?;
+        self.stats.buffers.misses += 1;
+
+        let mut item = Buffer::create(&self.device, info)?;
+        item.info.size = lease_size(Buffer::capacity(&item), self.info.buffer_size_class, size);
 
         Ok(Lease::new(cache_ref, item))
     }
@@ -175,6 +235,8 @@ impl Pool<BufferInfo, Buffer> for FifoPool {
 impl Pool<CommandBufferInfo, CommandBuffer> for FifoPool {
     #[profiling::function]
     fn lease(&mut self, info: CommandBufferInfo) -> Result<Lease<CommandBuffer>, DriverError> {
+        self.stats.command_buffers.leases += 1;
+
         let cache_ref = self
             .command_buffer_cache
             .entry(info.queue_family_index)
@@ -193,6 +255,8 @@ impl Pool<CommandBufferInfo, CommandBuffer> for FifoPool {
         .unwrap_or_else(|| {
             debug!("Creating new {}", stringify!(CommandBuffer));
 
+            self.stats.command_buffers.misses += 1;
+
             CommandBuffer::create(&self.device, info)
         })?;
 
@@ -206,6 +270,8 @@ impl Pool<CommandBufferInfo, CommandBuffer> for FifoPool {
 impl Pool<DescriptorPoolInfo, DescriptorPool> for FifoPool {
     #[profiling::function]
     fn lease(&mut self, info: DescriptorPoolInfo) -> Result<Lease<DescriptorPool>, DriverError> {
+        self.stats.descriptor_pools.leases += 1;
+
         let cache_ref = Arc::downgrade(&self.descriptor_pool_cache);
 
         {
@@ -234,7 +300,7 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for FifoPool {
                     && item.info.uniform_buffer_dynamic_count >= info.uniform_buffer_dynamic_count
                     && item.info.uniform_texel_buffer_count >= info.uniform_texel_buffer_count
                 {
-                    let item = cache.swap_remove(idx);
+                    let item = cache.swap_remove(idx).item;
 
                     return Ok(Lease::new(cache_ref, item));
                 }
@@ -243,6 +309,8 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for FifoPool {
 
         debug!("Creating new {}", stringify!(DescriptorPool));
 
+        self.stats.descriptor_pools.misses += 1;
+
         let item = DescriptorPool::create(&self.device, info)?;
 
         Ok(Lease::new(cache_ref, item))
@@ -252,6 +320,8 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for FifoPool {
 impl Pool<ImageInfo, Image> for FifoPool {
     #[profiling::function]
     fn lease(&mut self, info: ImageInfo) -> Result<Lease<Image>, DriverError> {
+        self.stats.images.leases += 1;
+
         let cache_ref = Arc::downgrade(&self.image_cache);
 
         {
@@ -269,8 +339,12 @@ impl Pool<ImageInfo, Image> for FifoPool {
                 let item = unsafe { cache.get_unchecked(idx) };
                 if item.info.array_layer_count == info.array_layer_count
                     && item.info.depth == info.depth
+                    && item.info.dedicated == info.dedicated
                     && item.info.fmt == info.fmt
                     && item.info.height == info.height
+                    && item.info.host_access == info.host_access
+                    && item.info.mappable == info.mappable
+                    && item.info.memory_priority == info.memory_priority
                     && item.info.mip_level_count == info.mip_level_count
                     && item.info.sample_count == info.sample_count
                     && item.info.tiling == info.tiling
@@ -279,7 +353,7 @@ impl Pool<ImageInfo, Image> for FifoPool {
                     && item.info.flags.contains(info.flags)
                     && item.info.usage.contains(info.usage)
                 {
-                    let item = cache.swap_remove(idx);
+                    let item = cache.swap_remove(idx).item;
 
                     return Ok(Lease::new(cache_ref, item));
                 }
@@ -288,6 +362,8 @@ impl Pool<ImageInfo, Image> for FifoPool {
 
         debug!("Creating new {}", stringify!(Image));
 
+        self.stats.images.misses += 1;
+
         let item = Image::create(&self.device, info)?;
 
         Ok(Lease::new(cache_ref, item))
@@ -297,6 +373,8 @@ impl Pool<ImageInfo, Image> for FifoPool {
 impl Pool<RenderPassInfo, RenderPass> for FifoPool {
     #[profiling::function]
     fn lease(&mut self, info: RenderPassInfo) -> Result<Lease<RenderPass>, DriverError> {
+        self.stats.render_passes.leases += 1;
+
         let cache_ref = if let Some(cache) = self.render_pass_cache.get(&info) {
             cache
         } else {
@@ -312,12 +390,14 @@ impl Pool<RenderPassInfo, RenderPass> for FifoPool {
             #[cfg(not(feature = "parking_lot"))]
             let mut cache = cache.unwrap();
 
-            cache.pop()
+            cache.pop().map(|entry| entry.item)
         }
         .map(Ok)
         .unwrap_or_else(|| {
             debug!("Creating new {}", stringify!(RenderPass));
 
+            self.stats.render_passes.misses += 1;
+
             RenderPass::create(&self.device, info)
         })?;
 
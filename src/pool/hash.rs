@@ -1,7 +1,10 @@
 //! Pool which leases by exactly matching the information before creating new resources.
 
 use {
-    super::{Cache, Lease, Pool, PoolInfo, lease_command_buffer},
+    super::{
+        Bucket, Cache, Lease, Pool, PoolInfo, PoolStats, buffer_key, cache_stats, is_unused_cache,
+        lease_command_buffer, lease_size, reset_cache_stats, trim_caches,
+    },
     crate::driver::{
         CommandBuffer, CommandBufferInfo, DescriptorPool, DescriptorPoolInfo, DriverError,
         RenderPass, RenderPassInfo,
@@ -36,6 +39,9 @@ use std::sync::Mutex;
 ///
 /// If requests for varying resources is common [`HashPool::clear_images_by_info`] and other memory
 /// management functions are nessecery in order to avoid using all available device memory.
+/// Alternatively, set an eviction policy using [`PoolInfo::max_bytes`],
+/// [`PoolInfo::max_unused_frames`] or [`PoolInfo::max_unused_duration`] and call
+/// [`HashPool::trim`] once per frame.
 #[derive(Debug)]
 pub struct HashPool {
     acceleration_structure_cache: HashMap<AccelerationStructureInfo, Cache<AccelerationStructure>>,
@@ -46,6 +52,7 @@ pub struct HashPool {
     image_cache: HashMap<ImageInfo, Cache<Image>>,
     info: PoolInfo,
     render_pass_cache: HashMap<RenderPassInfo, Cache<RenderPass>>,
+    stats: PoolStats,
 }
 
 impl HashPool {
@@ -68,6 +75,7 @@ impl HashPool {
             image_cache: Default::default(),
             info,
             render_pass_cache: Default::default(),
+            stats: Default::default(),
         }
     }
 
@@ -77,10 +85,80 @@ impl HashPool {
         self.clear_buffers();
         self.clear_images();
     }
+
+    /// Drops stored acceleration structure, buffer and image resources according to the eviction
+    /// policy of the [`PoolInfo`] used to create this pool, and removes buckets which are no longer
+    /// used.
+    ///
+    /// This function should be called once per frame. Resources which have not been leased within
+    /// [`PoolInfo::max_unused_frames`] calls or [`PoolInfo::max_unused_duration`] are dropped, and
+    /// then the least recently used resources are dropped until the total size of stored resources
+    /// is within [`PoolInfo::max_bytes`].
+    #[profiling::function]
+    pub fn trim(&mut self) {
+        trim_caches(
+            &self.info,
+            self.acceleration_structure_cache.values(),
+            self.buffer_cache.values(),
+            self.image_cache.values(),
+        );
+
+        self.acceleration_structure_cache
+            .retain(|_, cache| !is_unused_cache(cache, &mut self.stats.accel_structs));
+        self.buffer_cache
+            .retain(|_, cache| !is_unused_cache(cache, &mut self.stats.buffers));
+        self.image_cache
+            .retain(|_, cache| !is_unused_cache(cache, &mut self.stats.images));
+    }
+
+    /// Clears the lease, return and eviction counters of the pool.
+    ///
+    /// Call this function once per frame in order to read per-frame values from
+    /// [`HashPool::stats`].
+    pub fn reset_stats(&mut self) {
+        self.stats = Default::default();
+
+        reset_cache_stats(self.acceleration_structure_cache.values());
+        reset_cache_stats(self.buffer_cache.values());
+        reset_cache_stats(self.command_buffer_cache.values());
+        reset_cache_stats(self.descriptor_pool_cache.values());
+        reset_cache_stats(self.image_cache.values());
+        reset_cache_stats(self.render_pass_cache.values());
+    }
+
+    /// Returns the counters and stored resources of the pool.
+    ///
+    /// Counters of resources removed using [`HashPool::clear`] and the other memory management
+    /// functions are not included.
+    #[profiling::function]
+    pub fn stats(&self) -> PoolStats {
+        let mut stats = self.stats;
+
+        cache_stats(
+            &mut stats.accel_structs,
+            self.acceleration_structure_cache.values(),
+        );
+        cache_stats(&mut stats.buffers, self.buffer_cache.values());
+        cache_stats(
+            &mut stats.command_buffers,
+            self.command_buffer_cache.values(),
+        );
+        cache_stats(
+            &mut stats.descriptor_pools,
+            self.descriptor_pool_cache.values(),
+        );
+        cache_stats(&mut stats.images, self.image_cache.values());
+        cache_stats(&mut stats.render_passes, self.render_pass_cache.values());
+
+        stats
+    }
 }
 
 macro_rules! resource_mgmt_fns {
     ($fn_plural:literal, $doc_singular:literal, $ty:ty, $field:ident) => {
+        resource_mgmt_fns!($fn_plural, $doc_singular, $ty, $field, |_, info| info);
+    };
+    ($fn_plural:literal, $doc_singular:literal, $ty:ty, $field:ident, $key:expr) => {
         paste! {
             impl HashPool {
                 #[doc = "Clears the pool of " $doc_singular " resources."]
@@ -94,7 +172,10 @@ information."]
                     &mut self,
                     info: impl Into<$ty>,
                 ) {
-                    self.$field.remove(&info.into());
+                    let key: fn(&Self, $ty) -> $ty = $key;
+                    let info = key(self, info.into());
+
+                    self.$field.remove(&info);
                 }
 
                 #[doc = "Retains only the " $doc_singular " resources specified by the predicate.\n
@@ -123,12 +204,53 @@ resource_mgmt_fns!(
     AccelerationStructureInfo,
     acceleration_structure_cache
 );
-resource_mgmt_fns!("buffers", "buffer", BufferInfo, buffer_cache);
+resource_mgmt_fns!(
+    "buffers",
+    "buffer",
+    BufferInfo,
+    buffer_cache,
+    |pool, info| buffer_key(info, pool.info.buffer_size_class)
+);
 resource_mgmt_fns!("images", "image", ImageInfo, image_cache);
 
+impl Pool<BufferInfo, Buffer> for HashPool {
+    #[profiling::function]
+    fn lease(&mut self, info: BufferInfo) -> Result<Lease<Buffer>, DriverError> {
+        self.stats.buffers.leases += 1;
+
+        let size = info.size;
+        let info = buffer_key(info, self.info.buffer_size_class);
+        let cache_ref = self.buffer_cache.entry(info).or_insert_with(|| {
+            Cache::new(Mutex::new(Bucket::with_capacity(self.info.buffer_capacity)))
+        });
+        let mut item = {
+            #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+            let mut cache = cache_ref.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let mut cache = cache.unwrap();
+
+            cache.pop().map(|entry| entry.item)
+        }
+        .map(Ok)
+        .unwrap_or_else(|| {
+            debug!("Creating new {}", stringify!(Buffer));
+
+            self.stats.buffers.misses += 1;
+
+            Buffer::create(&self.device, info)
+        })?;
+        item.info.size = lease_size(Buffer::capacity(&item), self.info.buffer_size_class, size);
+
+        Ok(Lease::new(Arc::downgrade(cache_ref), item))
+    }
+}
+
 impl Pool<CommandBufferInfo, CommandBuffer> for HashPool {
     #[profiling::function]
     fn lease(&mut self, info: CommandBufferInfo) -> Result<Lease<CommandBuffer>, DriverError> {
+        self.stats.command_buffers.leases += 1;
+
         let cache_ref = self
             .command_buffer_cache
             .entry(info.queue_family_index)
@@ -146,6 +268,8 @@ impl Pool<CommandBufferInfo, CommandBuffer> for HashPool {
         .unwrap_or_else(|| {
             debug!("Creating new {}", stringify!(CommandBuffer));
 
+            self.stats.command_buffers.misses += 1;
+
             CommandBuffer::create(&self.device, info)
         })?;
 
@@ -159,6 +283,8 @@ impl Pool<CommandBufferInfo, CommandBuffer> for HashPool {
 impl Pool<DescriptorPoolInfo, DescriptorPool> for HashPool {
     #[profiling::function]
     fn lease(&mut self, info: DescriptorPoolInfo) -> Result<Lease<DescriptorPool>, DriverError> {
+        self.stats.descriptor_pools.leases += 1;
+
         let cache_ref = self
             .descriptor_pool_cache
             .entry(info.clone())
@@ -170,12 +296,14 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for HashPool {
             #[cfg(not(feature = "parking_lot"))]
             let mut cache = cache.unwrap();
 
-            cache.pop()
+            cache.pop().map(|entry| entry.item)
         }
         .map(Ok)
         .unwrap_or_else(|| {
             debug!("Creating new {}", stringify!(DescriptorPool));
 
+            self.stats.descriptor_pools.misses += 1;
+
             DescriptorPool::create(&self.device, info)
         })?;
 
@@ -186,6 +314,8 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for HashPool {
 impl Pool<RenderPassInfo, RenderPass> for HashPool {
     #[profiling::function]
     fn lease(&mut self, info: RenderPassInfo) -> Result<Lease<RenderPass>, DriverError> {
+        self.stats.render_passes.leases += 1;
+
         let cache_ref = if let Some(cache) = self.render_pass_cache.get(&info) {
             cache
         } else {
@@ -201,12 +331,14 @@ impl Pool<RenderPassInfo, RenderPass> for HashPool {
             #[cfg(not(feature = "parking_lot"))]
             let mut cache = cache.unwrap();
 
-            cache.pop()
+            cache.pop().map(|entry| entry.item)
         }
         .map(Ok)
         .unwrap_or_else(|| {
             debug!("Creating new {}", stringify!(RenderPass));
 
+            self.stats.render_passes.misses += 1;
+
             RenderPass::create(&self.device, info)
         })?;
 
@@ -216,14 +348,16 @@ impl Pool<RenderPassInfo, RenderPass> for HashPool {
 
 // Enable leasing items using their basic info
 macro_rules! lease {
-    ($info:ident => $item:ident, $capacity:ident) => {
+    ($info:ident => $item:ident, $capacity:ident, $stats:ident) => {
         paste::paste! {
             impl Pool<$info, $item> for HashPool {
                 #[profiling::function]
                 fn lease(&mut self, info: $info) -> Result<Lease<$item>, DriverError> {
+                    self.stats.$stats.leases += 1;
+
                     let cache_ref = self.[<$item:snake _cache>].entry(info)
                         .or_insert_with(|| {
-                            Cache::new(Mutex::new(Vec::with_capacity(self.info.$capacity)))
+                            Cache::new(Mutex::new(Bucket::with_capacity(self.info.$capacity)))
                         });
                     let item = {
                         #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
@@ -232,12 +366,14 @@ macro_rules! lease {
                         #[cfg(not(feature = "parking_lot"))]
                         let mut cache = cache.unwrap();
 
-                        cache.pop()
+                        cache.pop().map(|entry| entry.item)
                     }
                     .map(Ok)
                     .unwrap_or_else(|| {
                         debug!("Creating new {}", stringify!($item));
 
+                        self.stats.$stats.misses += 1;
+
                         $item::create(&self.device, info)
                     })?;
 
@@ -248,6 +384,5 @@ macro_rules! lease {
     };
 }
 
-lease!(AccelerationStructureInfo => AccelerationStructure, accel_struct_capacity);
-lease!(BufferInfo => Buffer, buffer_capacity);
-lease!(ImageInfo => Image, image_capacity);
+lease!(AccelerationStructureInfo => AccelerationStructure, accel_struct_capacity, accel_structs);
+lease!(ImageInfo => Image, image_capacity, images);
@@ -1,10 +1,13 @@
 //! Pool which leases by looking for compatibile information before creating new resources.
 
 use {
-    super::{Cache, Lease, Pool, PoolInfo, lease_command_buffer},
+    super::{
+        Cache, Lease, Pool, PoolInfo, PoolStats, buffer_key, cache_stats, is_unused_cache,
+        lease_command_buffer, lease_size, reset_cache_stats, trim_caches,
+    },
     crate::driver::{
         CommandBuffer, CommandBufferInfo, DescriptorPool, DescriptorPoolInfo, DriverError,
-        RenderPass, RenderPassInfo,
+        HostAccess, RenderPass, RenderPassInfo,
         accel_struct::{AccelerationStructure, AccelerationStructureInfo},
         buffer::{Buffer, BufferInfo},
         device::Device,
@@ -12,15 +15,41 @@ use {
     },
     ash::vk,
     log::debug,
-    std::{collections::HashMap, sync::Arc},
+    ordered_float::OrderedFloat,
+    std::{collections::HashMap, iter::once, sync::Arc},
 };
 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+struct BufferKey {
+    alignment: vk::DeviceSize,
+    dedicated: bool,
+    host_access: HostAccess,
+    mappable: bool,
+    memory_priority: Option<OrderedFloat<f32>>,
+}
+
+impl From<BufferInfo> for BufferKey {
+    fn from(info: BufferInfo) -> Self {
+        Self {
+            alignment: info.alignment,
+            dedicated: info.dedicated,
+            host_access: info.host_access,
+            mappable: info.mappable,
+            memory_priority: info.memory_priority,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 struct ImageKey {
     array_layer_count: u32,
+    dedicated: bool,
     depth: u32,
     fmt: vk::Format,
     height: u32,
+    host_access: HostAccess,
+    mappable: bool,
+    memory_priority: Option<OrderedFloat<f32>>,
     mip_level_count: u32,
     sample_count: SampleCount,
     tiling: vk::ImageTiling,
@@ -32,9 +61,13 @@ impl From<ImageInfo> for ImageKey {
     fn from(info: ImageInfo) -> Self {
         Self {
             array_layer_count: info.array_layer_count,
+            dedicated: info.dedicated,
             depth: info.depth,
             fmt: info.fmt,
             height: info.height,
+            host_access: info.host_access,
+            mappable: info.mappable,
+            memory_priority: info.memory_priority,
             mip_level_count: info.mip_level_count,
             sample_count: info.sample_count,
             tiling: info.tiling,
@@ -70,16 +103,20 @@ impl From<ImageInfo> for ImageKey {
 ///
 /// If requests for varying resources is common [`LazyPool::clear_images_by_info`] and other memory
 /// management functions are nessecery in order to avoid using all available device memory.
+/// Alternatively, set an eviction policy using [`PoolInfo::max_bytes`],
+/// [`PoolInfo::max_unused_frames`] or [`PoolInfo::max_unused_duration`] and call
+/// [`LazyPool::trim`] once per frame.
 #[derive(Debug)]
 pub struct LazyPool {
     accel_struct_cache: HashMap<vk::AccelerationStructureTypeKHR, Cache<AccelerationStructure>>,
-    buffer_cache: HashMap<(bool, vk::DeviceSize), Cache<Buffer>>,
+    buffer_cache: HashMap<BufferKey, Cache<Buffer>>,
     command_buffer_cache: HashMap<u32, Cache<CommandBuffer>>,
     descriptor_pool_cache: Cache<DescriptorPool>,
     device: Arc<Device>,
     image_cache: HashMap<ImageKey, Cache<Image>>,
     info: PoolInfo,
     render_pass_cache: HashMap<RenderPassInfo, Cache<RenderPass>>,
+    stats: PoolStats,
 }
 
 impl LazyPool {
@@ -102,6 +139,7 @@ impl LazyPool {
             image_cache: Default::default(),
             info,
             render_pass_cache: Default::default(),
+            stats: Default::default(),
         }
     }
 
@@ -154,6 +192,67 @@ impl LazyPool {
     {
         self.accel_struct_cache.retain(|&ty, _| f(ty))
     }
+
+    /// Drops stored acceleration structure, buffer and image resources according to the eviction
+    /// policy of the [`PoolInfo`] used to create this pool, and removes buckets which are no longer
+    /// used.
+    ///
+    /// See [`HashPool::trim`](super::hash::HashPool::trim) for details.
+    #[profiling::function]
+    pub fn trim(&mut self) {
+        trim_caches(
+            &self.info,
+            self.accel_struct_cache.values(),
+            self.buffer_cache.values(),
+            self.image_cache.values(),
+        );
+
+        self.accel_struct_cache
+            .retain(|_, cache| !is_unused_cache(cache, &mut self.stats.accel_structs));
+        self.buffer_cache
+            .retain(|_, cache| !is_unused_cache(cache, &mut self.stats.buffers));
+        self.image_cache
+            .retain(|_, cache| !is_unused_cache(cache, &mut self.stats.images));
+    }
+
+    /// Clears the lease, return and eviction counters of the pool.
+    ///
+    /// Call this function once per frame in order to read per-frame values from
+    /// [`LazyPool::stats`].
+    pub fn reset_stats(&mut self) {
+        self.stats = Default::default();
+
+        reset_cache_stats(self.accel_struct_cache.values());
+        reset_cache_stats(self.buffer_cache.values());
+        reset_cache_stats(self.command_buffer_cache.values());
+        reset_cache_stats(once(&self.descriptor_pool_cache));
+        reset_cache_stats(self.image_cache.values());
+        reset_cache_stats(self.render_pass_cache.values());
+    }
+
+    /// Returns the counters and stored resources of the pool.
+    ///
+    /// Counters of resources removed using [`LazyPool::clear`] and the other memory management
+    /// functions are not included.
+    #[profiling::function]
+    pub fn stats(&self) -> PoolStats {
+        let mut stats = self.stats;
+
+        cache_stats(&mut stats.accel_structs, self.accel_struct_cache.values());
+        cache_stats(&mut stats.buffers, self.buffer_cache.values());
+        cache_stats(
+            &mut stats.command_buffers,
+            self.command_buffer_cache.values(),
+        );
+        cache_stats(
+            &mut stats.descriptor_pools,
+            once(&self.descriptor_pool_cache),
+        );
+        cache_stats(&mut stats.images, self.image_cache.values());
+        cache_stats(&mut stats.render_passes, self.render_pass_cache.values());
+
+        stats
+    }
 }
 
 impl Pool<AccelerationStructureInfo, AccelerationStructure> for LazyPool {
@@ -162,6 +261,8 @@ impl Pool<AccelerationStructureInfo, AccelerationStructure> for LazyPool {
         &mut self,
         info: AccelerationStructureInfo,
     ) -> Result<Lease<AccelerationStructure>, DriverError> {
+        self.stats.accel_structs.leases += 1;
+
         let cache = self
             .accel_struct_cache
             .entry(info.ty)
@@ -181,7 +282,7 @@ impl Pool<AccelerationStructureInfo, AccelerationStructure> for LazyPool {
             for idx in 0..cache.len() {
                 let item = unsafe { cache.get_unchecked(idx) };
                 if item.info.size >= info.size {
-                    let item = cache.swap_remove(idx);
+                    let item = cache.swap_remove(idx).item;
 
                     return Ok(Lease::new(cache_ref, item));
                 }
@@ -190,6 +291,8 @@ impl Pool<AccelerationStructureInfo, AccelerationStructure> for LazyPool {
 
         debug!("Creating new {}", stringify!(AccelerationStructure));
 
+        self.stats.accel_structs.misses += 1;
+
         let item = AccelerationStructure::create(&self.device, info)?;
 
         Ok(Lease::new(cache_ref, item))
@@ -199,9 +302,13 @@ impl Pool<AccelerationStructureInfo, AccelerationStructure> for LazyPool {
 impl Pool<BufferInfo, Buffer> for LazyPool {
     #[profiling::function]
     fn lease(&mut self, info: BufferInfo) -> Result<Lease<Buffer>, DriverError> {
+        self.stats.buffers.leases += 1;
+
+        let size = info.size;
+        let info = buffer_key(info, self.info.buffer_size_class);
         let cache = self
             .buffer_cache
-            .entry((info.mappable, info.alignment))
+            .entry(info.into())
             .or_insert_with(|| PoolInfo::explicit_cache(self.info.buffer_capacity));
         let cache_ref = Arc::downgrade(cache);
 
@@ -217,8 +324,10 @@ impl Pool<BufferInfo, Buffer> for LazyPool {
             // Look for a compatible buffer (big enough and superset of usage flags)
             for idx in 0..cache.len() {
                 let item = unsafe { cache.get_unchecked(idx) };
-                if item.info.size >= info.size && item.info.usage.contains(info.usage) {
-                    let item = cache.swap_remove(idx);
+                if Buffer::capacity(item) >= info.size && item.info.usage.contains(info.usage) {
+                    let mut item = cache.swap_remove(idx).item;
+                    item.info.size =
+                        lease_size(Buffer::capacity(&item), self.info.buffer_size_class, size);
 
                     return Ok(Lease::new(cache_ref, item));
                 }
@@ -227,7 +336,10 @@ impl Pool<BufferInfo, Buffer> for LazyPool {
 
         debug!("Creating new {}", stringify!(Buffer));
 
-        let item = Buffer::create(&self.device, info)?;
+        self.stats.buffers.misses += 1;
+
+        let mut item = Buffer::create(&self.device, info)?;
+        item.info.size = lease_size(Buffer::capacity(&item), self.info.buffer_size_class, size);
 
         Ok(Lease::new(cache_ref, item))
     }
@@ -236,6 +348,8 @@ impl Pool<BufferInfo, Buffer> for LazyPool {
 impl Pool<CommandBufferInfo, CommandBuffer> for LazyPool {
     #[profiling::function]
     fn lease(&mut self, info: CommandBufferInfo) -> Result<Lease<CommandBuffer>, DriverError> {
+        self.stats.command_buffers.leases += 1;
+
         let cache_ref = self
             .command_buffer_cache
             .entry(info.queue_family_index)
@@ -253,6 +367,8 @@ impl Pool<CommandBufferInfo, CommandBuffer> for LazyPool {
         .unwrap_or_else(|| {
             debug!("Creating new {}", stringify!(CommandBuffer));
 
+            self.stats.command_buffers.misses += 1;
+
             CommandBuffer::create(&self.device, info)
         })?;
 
@@ -266,6 +382,8 @@ impl Pool<CommandBufferInfo, CommandBuffer> for LazyPool {
 impl Pool<DescriptorPoolInfo, DescriptorPool> for LazyPool {
     #[profiling::function]
     fn lease(&mut self, info: DescriptorPoolInfo) -> Result<Lease<DescriptorPool>, DriverError> {
+        self.stats.descriptor_pools.leases += 1;
+
         let cache_ref = Arc::downgrade(&self.descriptor_pool_cache);
 
         {
@@ -294,7 +412,7 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for LazyPool {
                     && item.info.uniform_buffer_dynamic_count >= info.uniform_buffer_dynamic_count
                     && item.info.uniform_texel_buffer_count >= info.uniform_texel_buffer_count
                 {
-                    let item = cache.swap_remove(idx);
+                    let item = cache.swap_remove(idx).item;
 
                     return Ok(Lease::new(cache_ref, item));
                 }
@@ -303,6 +421,8 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for LazyPool {
 
         debug!("Creating new {}", stringify!(DescriptorPool));
 
+        self.stats.descriptor_pools.misses += 1;
+
         let item = DescriptorPool::create(&self.device, info)?;
 
         Ok(Lease::new(cache_ref, item))
@@ -312,6 +432,8 @@ impl Pool<DescriptorPoolInfo, DescriptorPool> for LazyPool {
 impl Pool<ImageInfo, Image> for LazyPool {
     #[profiling::function]
     fn lease(&mut self, info: ImageInfo) -> Result<Lease<Image>, DriverError> {
+        self.stats.images.leases += 1;
+
         let cache = self
             .image_cache
             .entry(info.into())
@@ -331,7 +453,7 @@ impl Pool<ImageInfo, Image> for LazyPool {
             for idx in 0..cache.len() {
                 let item = unsafe { cache.get_unchecked(idx) };
                 if item.info.flags.contains(info.flags) && item.info.usage.contains(info.usage) {
-                    let item = cache.swap_remove(idx);
+                    let item = cache.swap_remove(idx).item;
 
                     return Ok(Lease::new(cache_ref, item));
                 }
@@ -340,6 +462,8 @@ impl Pool<ImageInfo, Image> for LazyPool {
 
         debug!("Creating new {}", stringify!(Image));
 
+        self.stats.images.misses += 1;
+
         let item = Image::create(&self.device, info)?;
 
         Ok(Lease::new(cache_ref, item))
@@ -349,6 +473,8 @@ impl Pool<ImageInfo, Image> for LazyPool {
 impl Pool<RenderPassInfo, RenderPass> for LazyPool {
     #[profiling::function]
     fn lease(&mut self, info: RenderPassInfo) -> Result<Lease<RenderPass>, DriverError> {
+        self.stats.render_passes.leases += 1;
+
         let cache_ref = if let Some(cache) = self.render_pass_cache.get(&info) {
             cache
         } else {
@@ -364,12 +490,14 @@ impl Pool<RenderPassInfo, RenderPass> for LazyPool {
             #[cfg(not(feature = "parking_lot"))]
             let mut cache = cache.unwrap();
 
-            cache.pop()
+            cache.pop().map(|entry| entry.item)
         }
         .map(Ok)
         .unwrap_or_else(|| {
             debug!("Creating new {}", stringify!(RenderPass));
 
+            self.stats.render_passes.misses += 1;
+
             RenderPass::create(&self.device, info)
         })?;
 
@@ -91,13 +91,14 @@ pub mod lazy;
 
 use {
     crate::driver::{
-        CommandBuffer, DriverError,
+        CommandBuffer, DescriptorPool, DriverError, RenderPass,
         accel_struct::{
             AccelerationStructure, AccelerationStructureInfo, AccelerationStructureInfoBuilder,
         },
         buffer::{Buffer, BufferInfo, BufferInfoBuilder},
         image::{Image, ImageInfo, ImageInfoBuilder},
     },
+    ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
     std::{
         fmt::Debug,
@@ -105,6 +106,7 @@ use {
         ops::{Deref, DerefMut},
         sync::{Arc, Weak},
         thread::panicking,
+        time::{Duration, Instant},
     },
 };
 
@@ -114,10 +116,315 @@ use parking_lot::Mutex;
 #[cfg(not(feature = "parking_lot"))]
 use std::sync::Mutex;
 
-type Cache<T> = Arc<Mutex<Vec<T>>>;
-type CacheRef<T> = Weak<Mutex<Vec<T>>>;
+type Cache<T> = Arc<Mutex<Bucket<T>>>;
+type CacheRef<T> = Weak<Mutex<Bucket<T>>>;
 
-fn lease_command_buffer(cache: &mut Vec<CommandBuffer>) -> Option<CommandBuffer> {
+/// The resources stored in a pool cache along with counters of how they leave and enter it.
+#[derive(Debug)]
+struct Bucket<T> {
+    entries: Vec<CacheEntry<T>>,
+    evictions: usize,
+    returns: usize,
+}
+
+impl<T> Bucket<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            evictions: 0,
+            returns: 0,
+        }
+    }
+}
+
+impl<T> Deref for Bucket<T> {
+    type Target = Vec<CacheEntry<T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.entries
+    }
+}
+
+impl<T> DerefMut for Bucket<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.entries
+    }
+}
+
+/// A resource stored in a pool cache along with the information used to evict it.
+#[derive(Debug)]
+struct CacheEntry<T> {
+    item: T,
+    returned: Instant,
+    unused_frames: u32,
+}
+
+impl<T> CacheEntry<T> {
+    fn new(item: T) -> Self {
+        Self {
+            item,
+            returned: Instant::now(),
+            unused_frames: 0,
+        }
+    }
+}
+
+impl<T> Deref for CacheEntry<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+/// A resource which may be evicted from a pool using a byte budget.
+trait Evict {
+    fn memory_size(&self) -> vk::DeviceSize;
+}
+
+impl Evict for CommandBuffer {
+    fn memory_size(&self) -> vk::DeviceSize {
+        0
+    }
+}
+
+impl Evict for DescriptorPool {
+    fn memory_size(&self) -> vk::DeviceSize {
+        0
+    }
+}
+
+impl Evict for RenderPass {
+    fn memory_size(&self) -> vk::DeviceSize {
+        0
+    }
+}
+
+impl Evict for AccelerationStructure {
+    fn memory_size(&self) -> vk::DeviceSize {
+        self.info.size
+    }
+}
+
+impl Evict for Buffer {
+    fn memory_size(&self) -> vk::DeviceSize {
+        Buffer::capacity(self)
+    }
+}
+
+impl Evict for Image {
+    fn memory_size(&self) -> vk::DeviceSize {
+        Image::memory_size(self)
+    }
+}
+
+/// Returns `true` if a cache holds no resources and no leases will return resources to it, in which
+/// case the counters of the cache are added to `stats`.
+fn is_unused_cache<T>(cache: &Cache<T>, stats: &mut ResourceStats) -> bool {
+    if Arc::weak_count(cache) > 0 {
+        return false;
+    }
+
+    let cache = cache.lock();
+
+    #[cfg(not(feature = "parking_lot"))]
+    let cache = cache.unwrap();
+
+    if !cache.is_empty() {
+        return false;
+    }
+
+    stats.evictions += cache.evictions;
+    stats.returns += cache.returns;
+
+    true
+}
+
+/// Drops cached resources which have not been leased within the limits of the given information
+/// and gathers the age and size of the remaining resources.
+fn age_cache<T>(
+    cache: &Cache<T>,
+    info: &PoolInfo,
+    now: Instant,
+    ages: &mut Vec<(Instant, vk::DeviceSize)>,
+) where
+    T: Evict,
+{
+    #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+    let mut cache = cache.lock();
+
+    #[cfg(not(feature = "parking_lot"))]
+    let mut cache = cache.unwrap();
+
+    let len = cache.len();
+
+    cache.retain_mut(|entry| {
+        entry.unused_frames = entry.unused_frames.saturating_add(1);
+
+        info.max_unused_frames
+            .is_none_or(|max_unused_frames| entry.unused_frames <= max_unused_frames)
+            && info.max_unused_duration.is_none_or(|max_unused_duration| {
+                now.duration_since(entry.returned) <= max_unused_duration
+            })
+    });
+
+    cache.evictions += len - cache.len();
+
+    ages.extend(
+        cache
+            .iter()
+            .map(|entry| (entry.returned, entry.item.memory_size())),
+    );
+}
+
+/// Drops cached resources which were returned at or before the given time.
+fn evict_cache<T>(cache: &Cache<T>, cutoff: Instant) {
+    #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+    let mut cache = cache.lock();
+
+    #[cfg(not(feature = "parking_lot"))]
+    let mut cache = cache.unwrap();
+
+    let len = cache.len();
+
+    cache.retain(|entry| entry.returned > cutoff);
+
+    cache.evictions += len - cache.len();
+}
+
+/// Returns the return time of the most recently used resource which must be evicted in order for
+/// the total size of all resources to fit within `max_bytes`.
+fn eviction_cutoff(
+    mut ages: Vec<(Instant, vk::DeviceSize)>,
+    max_bytes: vk::DeviceSize,
+) -> Option<Instant> {
+    let mut total_bytes = ages.iter().map(|(_, size)| size).sum::<vk::DeviceSize>();
+
+    if total_bytes <= max_bytes {
+        return None;
+    }
+
+    // Least recently used resources are evicted first
+    ages.sort_unstable_by_key(|(returned, _)| *returned);
+
+    let mut cutoff = None;
+
+    for (returned, size) in ages {
+        if total_bytes <= max_bytes {
+            break;
+        }
+
+        total_bytes -= size;
+        cutoff = Some(returned);
+    }
+
+    cutoff
+}
+
+/// Evicts resources from the given caches according to the eviction policy of `info`.
+fn trim_caches<'a, A, B, I>(info: &PoolInfo, accel_structs: A, buffers: B, images: I)
+where
+    A: Iterator<Item = &'a Cache<AccelerationStructure>> + Clone,
+    B: Iterator<Item = &'a Cache<Buffer>> + Clone,
+    I: Iterator<Item = &'a Cache<Image>> + Clone,
+{
+    let now = Instant::now();
+    let mut ages = vec![];
+
+    for cache in accel_structs.clone() {
+        age_cache(cache, info, now, &mut ages);
+    }
+
+    for cache in buffers.clone() {
+        age_cache(cache, info, now, &mut ages);
+    }
+
+    for cache in images.clone() {
+        age_cache(cache, info, now, &mut ages);
+    }
+
+    if let Some(cutoff) = info
+        .max_bytes
+        .and_then(|max_bytes| eviction_cutoff(ages, max_bytes))
+    {
+        for cache in accel_structs {
+            evict_cache(cache, cutoff);
+        }
+
+        for cache in buffers {
+            evict_cache(cache, cutoff);
+        }
+
+        for cache in images {
+            evict_cache(cache, cutoff);
+        }
+    }
+}
+
+/// Returns the information used to create buffers of the given size class, which is also the key
+/// of the bucket storing those buffers in a [`HashPool`](self::hash::HashPool).
+fn buffer_key(info: BufferInfo, size_class: SizeClass) -> BufferInfo {
+    BufferInfo {
+        size: size_class.round_up(info.size),
+        ..info
+    }
+}
+
+/// Returns the size reported by a lease of `size` bytes which uses a buffer of the given capacity.
+fn lease_size(
+    capacity: vk::DeviceSize,
+    size_class: SizeClass,
+    size: vk::DeviceSize,
+) -> vk::DeviceSize {
+    if size_class == SizeClass::Exact {
+        capacity
+    } else {
+        size
+    }
+}
+
+/// Adds the counters and stored resources of the given caches to `stats`, which must already hold
+/// the lease and miss counters of the pool.
+fn cache_stats<'a, T>(stats: &mut ResourceStats, caches: impl Iterator<Item = &'a Cache<T>>)
+where
+    T: Evict + 'a,
+{
+    stats.hits = stats.leases - stats.misses;
+
+    for cache in caches {
+        let cache = cache.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let cache = cache.unwrap();
+
+        stats.evictions += cache.evictions;
+        stats.returns += cache.returns;
+        stats.count += cache.len();
+        stats.bytes += cache
+            .iter()
+            .map(|entry| entry.item.memory_size())
+            .sum::<vk::DeviceSize>();
+    }
+}
+
+/// Clears the counters of the given caches.
+fn reset_cache_stats<'a, T>(caches: impl Iterator<Item = &'a Cache<T>>)
+where
+    T: 'a,
+{
+    for cache in caches {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut cache = cache.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut cache = cache.unwrap();
+
+        cache.evictions = 0;
+        cache.returns = 0;
+    }
+}
+
+fn lease_command_buffer(cache: &mut Bucket<CommandBuffer>) -> Option<CommandBuffer> {
     for idx in 0..cache.len() {
         if unsafe {
             let cmd_buf = cache.get_unchecked(idx);
@@ -129,7 +436,7 @@ fn lease_command_buffer(cache: &mut Vec<CommandBuffer>) -> Option<CommandBuffer>
                 .get_fence_status(cmd_buf.fence)
                 .unwrap_or_default()
         } {
-            return Some(cache.swap_remove(idx));
+            return Some(cache.swap_remove(idx).item);
         }
     }
 
@@ -201,9 +508,14 @@ impl<T> Drop for Lease<T> {
 
             if cache.len() == cache.capacity() {
                 cache.pop();
+                cache.evictions += 1;
             }
 
-            cache.push(unsafe { ManuallyDrop::take(&mut self.item) });
+            cache.returns += 1;
+
+            cache.push(CacheEntry::new(unsafe {
+                ManuallyDrop::take(&mut self.item)
+            }));
         } else {
             unsafe {
                 ManuallyDrop::drop(&mut self.item);
@@ -241,7 +553,12 @@ lease_builder!(ImageInfo => Image);
 /// [`HashPool`](self::hash::HashPool) or [`LazyPool`](self::lazy::LazyPool) instance.
 #[derive(Builder, Clone, Copy, Debug)]
 #[builder(
-    build_fn(private, name = "fallible_build", error = "PoolInfoBuilderError"),
+    build_fn(
+        private,
+        name = "fallible_build",
+        error = "PoolInfoBuilderError",
+        validate = "Self::validate"
+    ),
     derive(Clone, Copy, Debug),
     pattern = "owned"
 )]
@@ -269,6 +586,24 @@ pub struct PoolInfo {
     #[builder(default = "PoolInfo::DEFAULT_RESOURCE_CAPACITY", setter(strip_option))]
     pub buffer_capacity: usize,
 
+    /// The size class used to round up the size of leased buffers. The default value is
+    /// [`SizeClass::Exact`].
+    ///
+    /// Buffers are created using the rounded size so that they may be reused by later requests of a
+    /// similar size, however each lease reports the requested size as `info.size`. Use
+    /// [`Buffer::capacity`] to read the size of the underlying buffer.
+    ///
+    /// # Note
+    ///
+    /// [`HashPool`](self::hash::HashPool) uses the rounded buffer information as the key of each
+    /// bucket. [`HashPool::clear_buffers_by_info`](self::hash::HashPool::clear_buffers_by_info)
+    /// rounds the given information the same way, so either the requested or the leased
+    /// information may be used to clear a bucket.
+    ///
+    /// Building a `PoolInfo` with [`SizeClass::Multiple`] of zero panics.
+    #[builder(default)]
+    pub buffer_size_class: SizeClass,
+
     /// The maximum size of a single bucket of image resource instances. The default value is
     /// [`PoolInfo::DEFAULT_RESOURCE_CAPACITY`].
     ///
@@ -279,6 +614,33 @@ pub struct PoolInfo {
     /// instances.
     #[builder(default = "PoolInfo::DEFAULT_RESOURCE_CAPACITY", setter(strip_option))]
     pub image_capacity: usize,
+
+    /// The maximum total size in bytes of the acceleration structure, buffer and image resources
+    /// stored by a pool. When exceeded, the least recently used resources are dropped first.
+    ///
+    /// Only applies to pools which support trimming, such as
+    /// [`HashPool::trim`](self::hash::HashPool::trim). The default value is `None`, which does not
+    /// limit the size of stored resources.
+    #[builder(default, setter(strip_option))]
+    pub max_bytes: Option<vk::DeviceSize>,
+
+    /// The maximum length of time an acceleration structure, buffer or image resource may be
+    /// stored by a pool without being leased.
+    ///
+    /// Only applies to pools which support trimming, such as
+    /// [`HashPool::trim`](self::hash::HashPool::trim). The default value is `None`, which does not
+    /// drop stored resources based on time.
+    #[builder(default, setter(strip_option))]
+    pub max_unused_duration: Option<Duration>,
+
+    /// The maximum number of frames an acceleration structure, buffer or image resource may be
+    /// stored by a pool without being leased. Each call to a trim function counts as one frame.
+    ///
+    /// Only applies to pools which support trimming, such as
+    /// [`HashPool::trim`](self::hash::HashPool::trim). The default value is `None`, which does not
+    /// drop stored resources based on frames.
+    #[builder(default, setter(strip_option))]
+    pub max_unused_frames: Option<u32>,
 }
 
 impl PoolInfo {
@@ -291,18 +653,22 @@ impl PoolInfo {
         Self {
             accel_struct_capacity: resource_capacity,
             buffer_capacity: resource_capacity,
+            buffer_size_class: SizeClass::Exact,
             image_capacity: resource_capacity,
+            max_bytes: None,
+            max_unused_duration: None,
+            max_unused_frames: None,
         }
     }
 
     fn default_cache<T>() -> Cache<T> {
-        Cache::new(Mutex::new(Vec::with_capacity(
+        Cache::new(Mutex::new(Bucket::with_capacity(
             Self::DEFAULT_RESOURCE_CAPACITY,
         )))
     }
 
     fn explicit_cache<T>(capacity: usize) -> Cache<T> {
-        Cache::new(Mutex::new(Vec::with_capacity(capacity)))
+        Cache::new(Mutex::new(Bucket::with_capacity(capacity)))
     }
 }
 
@@ -320,28 +686,322 @@ impl From<PoolInfoBuilder> for PoolInfo {
 
 impl From<usize> for PoolInfo {
     fn from(value: usize) -> Self {
-        Self {
-            accel_struct_capacity: value,
-            buffer_capacity: value,
-            image_capacity: value,
-        }
+        Self::with_capacity(value)
     }
 }
 
 // HACK: https://github.com/colin-kiegel/rust-derive-builder/issues/56
 impl PoolInfoBuilder {
     /// Builds a new `PoolInfo`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer_size_class` is [`SizeClass::Multiple`] with a size of zero.
     pub fn build(self) -> PoolInfo {
-        self.fallible_build()
-            .expect("All required fields set at initialization")
+        match self.fallible_build() {
+            Err(PoolInfoBuilderError(err)) => panic!("{err}"),
+            Ok(info) => info,
+        }
+    }
+
+    fn validate(&self) -> Result<(), PoolInfoBuilderError> {
+        if self.buffer_size_class == Some(SizeClass::Multiple(0)) {
+            return Err(PoolInfoBuilderError(
+                "buffer_size_class must not be a multiple of zero".to_owned(),
+            ));
+        }
+
+        Ok(())
     }
 }
 
 #[derive(Debug)]
-struct PoolInfoBuilderError;
+struct PoolInfoBuilderError(String);
 
 impl From<UninitializedFieldError> for PoolInfoBuilderError {
-    fn from(_: UninitializedFieldError) -> Self {
-        Self
+    fn from(err: UninitializedFieldError) -> Self {
+        Self(err.to_string())
+    }
+}
+
+/// Counters which describe how effectively a [`Pool`] implementation reuses its resources.
+///
+/// Counters accumulate from the time a pool is created or most recently reset. Call the
+/// `reset_stats` function of a pool once per frame in order to read per-frame values.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PoolStats {
+    /// Counters of acceleration structure resources.
+    pub accel_structs: ResourceStats,
+
+    /// Counters of buffer resources.
+    pub buffers: ResourceStats,
+
+    /// Counters of command buffer resources.
+    pub command_buffers: ResourceStats,
+
+    /// Counters of descriptor pool resources, which are leased during render graph resolution.
+    pub descriptor_pools: ResourceStats,
+
+    /// Counters of image resources.
+    pub images: ResourceStats,
+
+    /// Counters of render pass resources, which are leased during render graph resolution.
+    pub render_passes: ResourceStats,
+}
+
+/// Rounding applied to the size of buffers leased from a pool.
+///
+/// Requests for buffers of slightly different sizes, such as per-frame dynamic vertex data, would
+/// otherwise each create a new buffer.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum SizeClass {
+    /// Buffers are created using the exact requested size.
+    #[default]
+    Exact,
+
+    /// Buffers are created using a multiple of the given non-zero size.
+    Multiple(vk::DeviceSize),
+
+    /// Buffers are created using the next power of two of the requested size.
+    PowerOfTwo,
+}
+
+impl SizeClass {
+    /// Returns `size` rounded up to this size class.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is [`SizeClass::Multiple`] with a size of zero.
+    pub fn round_up(self, size: vk::DeviceSize) -> vk::DeviceSize {
+        match self {
+            Self::Exact => size,
+            Self::Multiple(multiple) => size.next_multiple_of(multiple),
+            Self::PowerOfTwo => size.next_power_of_two(),
+        }
+    }
+}
+
+/// Counters of a single type of resource stored in a [`Pool`] implementation.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ResourceStats {
+    /// The total size in bytes of the resources currently stored by the pool.
+    ///
+    /// Command buffer, descriptor pool and render pass resources are always zero bytes.
+    pub bytes: vk::DeviceSize,
+
+    /// The number of resources currently stored by the pool and available for lease.
+    pub count: usize,
+
+    /// The number of stored resources which were dropped by the pool due to bucket capacity or
+    /// trimming.
+    pub evictions: usize,
+
+    /// The number of leases which were fulfilled using a stored resource.
+    pub hits: usize,
+
+    /// The number of leases requested from the pool.
+    pub leases: usize,
+
+    /// The number of leases which required a new resource to be created.
+    pub misses: usize,
+
+    /// The number of leased resources which have been returned to the pool.
+    pub returns: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestResource(vk::DeviceSize);
+
+    impl Evict for TestResource {
+        fn memory_size(&self) -> vk::DeviceSize {
+            self.0
+        }
+    }
+
+    fn test_cache(entries: impl IntoIterator<Item = (Instant, u32)>) -> Cache<TestResource> {
+        let cache = PoolInfo::default_cache();
+
+        {
+            #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+            let mut bucket = cache.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let mut bucket = bucket.unwrap();
+
+            for (returned, unused_frames) in entries {
+                bucket.push(CacheEntry {
+                    item: TestResource(1),
+                    returned,
+                    unused_frames,
+                });
+            }
+        }
+
+        cache
+    }
+
+    fn test_cache_evictions(cache: &Cache<TestResource>) -> (usize, usize) {
+        let bucket = cache.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let bucket = bucket.unwrap();
+
+        (bucket.len(), bucket.evictions)
+    }
+
+    #[test]
+    pub fn age_cache_unlimited() {
+        let now = Instant::now();
+        let cache = test_cache([(now, 0), (now, u32::MAX)]);
+        let mut ages = vec![];
+
+        age_cache(&cache, &PoolInfo::default(), now, &mut ages);
+
+        assert_eq!(test_cache_evictions(&cache), (2, 0));
+        assert_eq!(ages, vec![(now, 1), (now, 1)]);
+    }
+
+    #[test]
+    pub fn age_cache_max_unused_duration() {
+        let now = Instant::now();
+        let returned = now - Duration::from_secs(2);
+        let cache = test_cache([(now, 0), (returned, 0)]);
+        let info = PoolInfoBuilder::default()
+            .max_unused_duration(Duration::from_secs(1))
+            .build();
+        let mut ages = vec![];
+
+        age_cache(&cache, &info, now, &mut ages);
+
+        assert_eq!(test_cache_evictions(&cache), (1, 1));
+        assert_eq!(ages, vec![(now, 1)]);
+
+        // Resources exactly at the limit are kept
+        let cache = test_cache([(returned, 0)]);
+        let info = PoolInfoBuilder::default()
+            .max_unused_duration(Duration::from_secs(2))
+            .build();
+        ages.clear();
+
+        age_cache(&cache, &info, now, &mut ages);
+
+        assert_eq!(test_cache_evictions(&cache), (1, 0));
+        assert_eq!(ages, vec![(returned, 1)]);
+    }
+
+    #[test]
+    pub fn age_cache_max_unused_frames() {
+        let now = Instant::now();
+        let cache = test_cache([(now, 0), (now, 1), (now, 2)]);
+        let info = PoolInfoBuilder::default().max_unused_frames(2).build();
+        let mut ages = vec![];
+
+        age_cache(&cache, &info, now, &mut ages);
+
+        assert_eq!(test_cache_evictions(&cache), (2, 1));
+        assert_eq!(ages.len(), 2);
+
+        // Each call counts as one frame
+        ages.clear();
+        age_cache(&cache, &info, now, &mut ages);
+
+        assert_eq!(test_cache_evictions(&cache), (1, 2));
+        assert_eq!(ages.len(), 1);
+    }
+
+    #[test]
+    pub fn eviction_cutoff_fits() {
+        let now = Instant::now();
+
+        assert_eq!(eviction_cutoff(vec![], 0), None);
+        assert_eq!(eviction_cutoff(vec![(now, 4), (now, 4)], 8), None);
+    }
+
+    #[test]
+    pub fn eviction_cutoff_least_recently_used() {
+        let now = Instant::now();
+        let t0 = now - Duration::from_secs(3);
+        let t1 = now - Duration::from_secs(2);
+        let t2 = now - Duration::from_secs(1);
+
+        // Ages are not required to be sorted
+        let ages = vec![(t2, 4), (t0, 4), (t1, 4)];
+
+        assert_eq!(eviction_cutoff(ages.clone(), 11), Some(t0));
+        assert_eq!(eviction_cutoff(ages.clone(), 8), Some(t0));
+        assert_eq!(eviction_cutoff(ages.clone(), 7), Some(t1));
+        assert_eq!(eviction_cutoff(ages.clone(), 4), Some(t1));
+        assert_eq!(eviction_cutoff(ages, 0), Some(t2));
+    }
+
+    #[test]
+    pub fn pool_info_size_class() {
+        let info = PoolInfoBuilder::default()
+            .buffer_size_class(SizeClass::Multiple(256))
+            .build();
+
+        assert_eq!(info.buffer_size_class, SizeClass::Multiple(256));
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple of zero")]
+    pub fn pool_info_size_class_multiple_of_zero() {
+        PoolInfoBuilder::default()
+            .buffer_size_class(SizeClass::Multiple(0))
+            .build();
+    }
+
+    #[test]
+    pub fn buffer_key_reuses_larger_buffer() {
+        let usage = vk::BufferUsageFlags::VERTEX_BUFFER;
+
+        // Similar requests share the key, and therefore the buffer, of the larger size class
+        for size_class in [SizeClass::PowerOfTwo, SizeClass::Multiple(256)] {
+            let small = buffer_key(BufferInfo::device_mem(150, usage), size_class);
+            let large = buffer_key(BufferInfo::device_mem(200, usage), size_class);
+
+            assert_eq!(small, large);
+            assert_eq!(small.size, 256);
+
+            // Leases of the larger buffer report the requested size
+            assert_eq!(lease_size(256, size_class, 150), 150);
+            assert_eq!(lease_size(256, size_class, 200), 200);
+        }
+
+        let small = buffer_key(BufferInfo::device_mem(100, usage), SizeClass::Exact);
+        let large = buffer_key(BufferInfo::device_mem(200, usage), SizeClass::Exact);
+
+        assert_ne!(small, large);
+        assert_eq!(small.size, 100);
+
+        // Exact leases of a reused larger buffer report the size of that buffer
+        assert_eq!(lease_size(200, SizeClass::Exact, 100), 200);
+    }
+
+    #[test]
+    pub fn size_class_round_up() {
+        assert_eq!(SizeClass::Exact.round_up(0), 0);
+        assert_eq!(SizeClass::Exact.round_up(1), 1);
+        assert_eq!(SizeClass::Exact.round_up(100), 100);
+
+        assert_eq!(SizeClass::PowerOfTwo.round_up(0), 1);
+        assert_eq!(SizeClass::PowerOfTwo.round_up(1), 1);
+        assert_eq!(SizeClass::PowerOfTwo.round_up(2), 2);
+        assert_eq!(SizeClass::PowerOfTwo.round_up(3), 4);
+        assert_eq!(SizeClass::PowerOfTwo.round_up(256), 256);
+        assert_eq!(SizeClass::PowerOfTwo.round_up(257), 512);
+
+        assert_eq!(SizeClass::Multiple(256).round_up(0), 0);
+        assert_eq!(SizeClass::Multiple(256).round_up(1), 256);
+        assert_eq!(SizeClass::Multiple(256).round_up(256), 256);
+        assert_eq!(SizeClass::Multiple(256).round_up(257), 512);
+        assert_eq!(SizeClass::Multiple(1).round_up(7), 7);
+        assert_eq!(SizeClass::Multiple(3).round_up(7), 9);
     }
 }